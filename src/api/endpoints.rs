@@ -1,10 +1,20 @@
 use anyhow::Result;
 
 use super::client::ApiClient;
-use crate::models::pokemon::{MoveDetail, PokemonDetail, PokemonListResponse};
+use crate::models::pokemon::{
+    AbilityDetail, EvolutionChain, Generation, MoveDetail, PokemonDetail, PokemonListResponse,
+    PokemonSpecies,
+};
 use crate::models::type_data::TypeInfo;
 
 const BASE_URL: &str = "https://pokeapi.co/api/v2";
+const SPRITE_BASE_URL: &str =
+    "https://raw.githubusercontent.com/PokeAPI/sprites/master/sprites/pokemon";
+
+/// Default front sprite for a Pokémon id, without fetching its detail first
+pub fn default_sprite_url(id: u32) -> String {
+    format!("{}/{}.png", SPRITE_BASE_URL, id)
+}
 
 impl ApiClient {
     pub async fn fetch_pokemon_list(&self) -> Result<PokemonListResponse> {
@@ -17,6 +27,21 @@ impl ApiClient {
         self.get_cached(&url).await
     }
 
+    pub async fn fetch_species(&self, id_or_name: &str) -> Result<PokemonSpecies> {
+        let url = format!("{}/pokemon-species/{}", BASE_URL, id_or_name);
+        self.get_cached(&url).await
+    }
+
+    /// Evolution chain at the url a species links to
+    pub async fn fetch_evolution_chain(&self, url: &str) -> Result<EvolutionChain> {
+        self.get_cached(url).await
+    }
+
+    pub async fn fetch_generation(&self, id: u8) -> Result<Generation> {
+        let url = format!("{}/generation/{}", BASE_URL, id);
+        self.get_cached(&url).await
+    }
+
     pub async fn fetch_type_info(&self, name: &str) -> Result<TypeInfo> {
         let url = format!("{}/type/{}", BASE_URL, name);
         self.get_cached(&url).await
@@ -27,9 +52,18 @@ impl ApiClient {
         self.get_cached(&url).await
     }
 
+    pub async fn fetch_ability(&self, name: &str) -> Result<AbilityDetail> {
+        let url = format!("{}/ability/{}", BASE_URL, name);
+        self.get_cached(&url).await
+    }
+
     pub async fn fetch_sprite_bytes(&self, url: &str) -> Result<Vec<u8>> {
         self.get_bytes_cached(url).await
     }
+
+    pub async fn fetch_thumbnail(&self, id: u32) -> Result<Vec<u8>> {
+        self.get_bytes_cached(&default_sprite_url(id)).await
+    }
 }
 
 #[cfg(test)]
@@ -89,6 +123,33 @@ mod tests {
         assert_eq!(detail.name, "bulbasaur");
     }
 
+    #[tokio::test]
+    async fn test_fetch_species() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = temp_dir.path().to_path_buf();
+        let client = ApiClient::new_with_cache_dir(cache_dir.clone());
+
+        // Pre-populate cache
+        let url = "https://pokeapi.co/api/v2/pokemon-species/6";
+        let cache_key = ApiClient::url_to_cache_key(url);
+        let cache_path = cache_dir.join(&cache_key);
+        let mock_response = r#"{
+            "id": 6,
+            "name": "charizard",
+            "generation": {"name": "generation-i", "url": "https://pokeapi.co/api/v2/generation/1/"},
+            "varieties": [
+                {"is_default": true, "pokemon": {"name": "charizard", "url": "https://pokeapi.co/api/v2/pokemon/6/"}}
+            ]
+        }"#;
+        std::fs::write(&cache_path, mock_response).unwrap();
+
+        let result = client.fetch_species("6").await;
+        assert!(result.is_ok());
+        let species = result.unwrap();
+        assert_eq!(species.id, 6);
+        assert_eq!(species.varieties.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_fetch_type_info() {
         let temp_dir = TempDir::new().unwrap();
@@ -166,4 +227,22 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), mock_bytes);
     }
+
+    #[tokio::test]
+    async fn test_fetch_thumbnail_uses_default_sprite_url() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = temp_dir.path().to_path_buf();
+        let client = ApiClient::new_with_cache_dir(cache_dir.clone());
+
+        let url = default_sprite_url(25);
+        assert_eq!(
+            url,
+            "https://raw.githubusercontent.com/PokeAPI/sprites/master/sprites/pokemon/25.png"
+        );
+        let cache_path = cache_dir.join(ApiClient::url_to_cache_key(&url));
+        std::fs::write(&cache_path, b"png bytes").unwrap();
+
+        let bytes = client.fetch_thumbnail(25).await.unwrap();
+        assert_eq!(bytes, b"png bytes");
+    }
 }
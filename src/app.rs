@@ -1,18 +1,86 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
 use crate::api::client::ApiClient;
-use crate::event::AppEvent;
-use crate::models::pokemon::{MoveDetail, PokemonDetail, PokemonSummary};
+use crate::event::{AppEvent, TICK_RATE};
+use crate::export;
+use crate::export::table::{DexEntry, ExportFormat};
+use crate::history::{History, Location};
+use crate::keymap::{Action, Context, Key, Keymap};
+use crate::models::collection::{self, game_label, Collection, Progress};
+use crate::models::notes::{normalize_tag, Notes};
+use crate::models::pokemon::{
+    EvolutionChain, MoveDetail, PokemonDetail, PokemonListResponse, PokemonSpecies, PokemonSummary,
+    SpriteVariant,
+};
 use crate::models::team::{Team, TeamData, TeamMember, TeamMove};
-use crate::models::type_data::TypeInfo;
+use crate::models::type_data::{TypeInfo, ALL_TYPES};
+use crate::palette::{self, Command, PaletteEntry};
+use crate::search::fuzzy::fuzzy_match;
+use crate::settings::{Filters, Session, Settings};
+use crate::sprite::animation::{decode_gif, AnimationFrame, SpriteAnimation};
+use crate::sprite::cache::SpriteCache;
+use crate::sprite::graphics::GraphicsProtocol;
+use crate::sprite::renderer::{BlockMode, ColorDepth};
+use crate::ui::theme::{Theme, ThemeName};
+
+/// Decoded animations kept in memory before the cache starts over
+const MAX_CACHED_ANIMATIONS: usize = 32;
+
+/// Rows PageUp/PageDown move by
+const PAGE_ROWS: usize = 10;
+
+/// Percent of the width a split view's divider moves by
+const PANE_STEP: i16 = 5;
+
+/// Move a selection within `len` rows for a movement action; other actions leave it
+fn step_selection(selected: usize, len: usize, action: Action) -> usize {
+    let last = len.saturating_sub(1);
+    match action {
+        Action::Up => selected.saturating_sub(1),
+        Action::Down => (selected + 1).min(last),
+        Action::PageUp => selected.saturating_sub(PAGE_ROWS),
+        Action::PageDown => (selected + PAGE_ROWS).min(last),
+        Action::Top => 0,
+        Action::Bottom => last,
+        _ => selected,
+    }
+}
+
+fn flatten_export(
+    result: Result<anyhow::Result<PathBuf>, tokio::task::JoinError>,
+) -> Result<PathBuf, String> {
+    match result {
+        Ok(Ok(path)) => Ok(path),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
 
 fn extract_id_from_url(url: &str) -> Option<u32> {
     url.trim_end_matches('/').rsplit('/').next()?.parse().ok()
 }
 
+/// Generation from species data, or guessed from the species number until
+/// that's loaded. Forms whose species isn't known yet have none.
+pub(crate) fn summary_generation(p: &PokemonSummary) -> Option<u8> {
+    p.generation
+        .or_else(|| p.known_species_id().map(pokemon_generation))
+}
+
+/// Generations PokéAPI has so far
+pub(crate) const LATEST_GENERATION: u8 = 9;
+
 /// Calculate Pokemon generation from ID based on standard ranges
-fn pokemon_generation(id: u32) -> u8 {
+pub(crate) fn pokemon_generation(id: u32) -> u8 {
     match id {
         1..=151 => 1,
         152..=251 => 2,
@@ -27,8 +95,91 @@ fn pokemon_generation(id: u32) -> u8 {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Modification time of a file, or None when it's missing
+fn modified(path: &std::path::Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Until the batch loader fills in species data, guess each form's species
+/// from its name: "charizard-mega-x" belongs to the longest base name it extends
+fn infer_species_ids(list: &mut [PokemonSummary]) {
+    let bases: Vec<(String, u32)> = list
+        .iter()
+        .filter(|p| !p.is_form())
+        .map(|p| (format!("{}-", p.name), p.id))
+        .collect();
+    for p in list.iter_mut() {
+        if !p.is_form() {
+            p.species_id = Some(p.id);
+            continue;
+        }
+        p.species_id = bases
+            .iter()
+            .filter(|(prefix, _)| p.name.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|&(_, id)| id);
+    }
+}
+
+/// Bare list rows (no types or stats yet) from the /pokemon index
+pub(crate) fn summaries_from_list(list: &PokemonListResponse) -> Vec<PokemonSummary> {
+    // Extract ID from URL: "https://pokeapi.co/api/v2/pokemon/25/" -> 25
+    let mut summaries: Vec<PokemonSummary> = list
+        .results
+        .iter()
+        .filter_map(|e| {
+            let id = extract_id_from_url(&e.url)?;
+            Some(PokemonSummary {
+                id,
+                name: e.name.clone(),
+                ..Default::default()
+            })
+        })
+        .collect();
+    infer_species_ids(&mut summaries);
+    summaries
+}
+
+/// Score a Pokemon against a search query: digits match the dex number,
+/// anything else is fuzzy-matched against the name. Returns (score, is_typo).
+fn search_score(p: &PokemonSummary, query: &str) -> Option<(i32, bool)> {
+    let q = query.trim();
+    if !q.is_empty() && q.chars().all(|c| c.is_ascii_digit()) {
+        let id = p.id.to_string();
+        if id == q.trim_start_matches('0') {
+            return Some((i32::MAX, false));
+        }
+        if id.contains(q) {
+            return Some((i32::MAX - 1 - id.len() as i32, false));
+        }
+    }
+    fuzzy_match(q, &p.name).map(|m| (m.score, m.is_typo()))
+}
+
+/// Keep only Pokemon matching `query`, best match first (ties keep list order).
+/// Typo-tolerant hits are only used when nothing matches directly.
+pub(crate) fn rank_by_query<'a>(
+    list: Vec<&'a PokemonSummary>,
+    query: &str,
+) -> Vec<&'a PokemonSummary> {
+    if query.trim().is_empty() {
+        return list;
+    }
+    let mut scored: Vec<(i32, bool, &PokemonSummary)> = list
+        .into_iter()
+        .filter_map(|p| search_score(p, query).map(|(s, typo)| (s, typo, p)))
+        .collect();
+    if scored.iter().any(|&(_, typo, _)| !typo) {
+        scored.retain(|&(_, typo, _)| !typo);
+    }
+    scored.sort_by_key(|&(score, _, _)| std::cmp::Reverse(score));
+    scored.into_iter().map(|(_, _, p)| p).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Screen {
+    #[default]
     PokemonList,
     PokemonDetail,
     TypeChart,
@@ -68,6 +219,134 @@ impl Screen {
 pub enum Modal {
     PokemonPicker,
     MovePicker,
+    TypeFilter,
+    CommandPalette,
+    TagFilter,
+    EditTags,
+    EditNote,
+    MarkRange,
+    Progress,
+    Recent,
+}
+
+impl Modal {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Modal::PokemonPicker => "Pokémon picker",
+            Modal::MovePicker => "Move picker",
+            Modal::TypeFilter => "Type filter",
+            Modal::CommandPalette => "Command palette",
+            Modal::TagFilter => "Tag filter",
+            Modal::EditTags => "Tags",
+            Modal::EditNote => "Notes",
+            Modal::MarkRange => "Mark range",
+            Modal::Progress => "Collection",
+            Modal::Recent => "Recently viewed",
+        }
+    }
+
+    /// Modals that take typing without `Search` first
+    pub fn is_typing(&self) -> bool {
+        matches!(
+            self,
+            Modal::CommandPalette
+                | Modal::TagFilter
+                | Modal::EditTags
+                | Modal::EditNote
+                | Modal::MarkRange
+        )
+    }
+
+    /// Modal actions that do something here, for the help overlay
+    pub fn actions(&self) -> &'static [Action] {
+        match self {
+            Modal::TypeFilter => &[
+                Action::Up,
+                Action::Down,
+                Action::PageUp,
+                Action::PageDown,
+                Action::Top,
+                Action::Bottom,
+                Action::ToggleType,
+                Action::Select,
+                Action::MatchMode,
+                Action::ClearTypes,
+                Action::Back,
+                Action::Help,
+            ],
+            Modal::PokemonPicker => &[
+                Action::Up,
+                Action::Down,
+                Action::PageUp,
+                Action::PageDown,
+                Action::Top,
+                Action::Bottom,
+                Action::Search,
+                Action::Select,
+                Action::Back,
+                Action::Help,
+            ],
+            Modal::EditNote | Modal::Progress => &[Action::Back],
+            Modal::MarkRange => &[Action::Select, Action::Back],
+            Modal::MovePicker
+            | Modal::CommandPalette
+            | Modal::TagFilter
+            | Modal::EditTags
+            | Modal::Recent => &[
+                Action::Up,
+                Action::Down,
+                Action::PageUp,
+                Action::PageDown,
+                Action::Top,
+                Action::Bottom,
+                Action::Select,
+                Action::Back,
+            ],
+        }
+    }
+}
+
+/// Something the last frame drew that responds to a click
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClickTarget {
+    Tab(Screen),
+    ListRow(usize),
+    TeamSlot(usize),
+    /// Row in whichever modal is open
+    ModalRow(usize),
+    Form(u32),
+    /// Species in the evolution chain, by dex number
+    Evolution(u32),
+    Ability(usize),
+}
+
+/// How the selected types in the type filter are matched
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeMatch {
+    /// Has at least one of the selected types
+    #[default]
+    Any,
+    /// Has exactly the selected types (a single type means mono-type)
+    Exact,
+}
+
+impl TypeMatch {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TypeMatch::Any => "either",
+            TypeMatch::Exact => "exact",
+        }
+    }
+
+    fn matches(&self, selected: &[String], types: &[String]) -> bool {
+        match self {
+            TypeMatch::Any => selected.iter().any(|t| types.contains(t)),
+            TypeMatch::Exact => {
+                types.len() == selected.len() && selected.iter().all(|t| types.contains(t))
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -78,9 +357,128 @@ pub enum LoadingState {
     Error,
 }
 
+/// Column the Pokédex list is ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    #[default]
+    Id,
+    Name,
+    Hp,
+    Attack,
+    Defense,
+    SpAttack,
+    SpDefense,
+    Speed,
+    Total,
+    Height,
+    Weight,
+    Type,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SortValue<'a> {
+    Num(u32),
+    Text(&'a str),
+}
+
+impl SortKey {
+    pub fn all() -> &'static [SortKey] {
+        &[
+            SortKey::Id,
+            SortKey::Name,
+            SortKey::Hp,
+            SortKey::Attack,
+            SortKey::Defense,
+            SortKey::SpAttack,
+            SortKey::SpDefense,
+            SortKey::Speed,
+            SortKey::Total,
+            SortKey::Height,
+            SortKey::Weight,
+            SortKey::Type,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortKey::Id => "No.",
+            SortKey::Name => "Name",
+            SortKey::Hp => "HP",
+            SortKey::Attack => "Atk",
+            SortKey::Defense => "Def",
+            SortKey::SpAttack => "SpA",
+            SortKey::SpDefense => "SpD",
+            SortKey::Speed => "Spe",
+            SortKey::Total => "Total",
+            SortKey::Height => "Height",
+            SortKey::Weight => "Weight",
+            SortKey::Type => "Type",
+        }
+    }
+
+    pub fn next(&self) -> SortKey {
+        let all = Self::all();
+        let idx = all.iter().position(|k| k == self).unwrap_or(0);
+        all[(idx + 1) % all.len()]
+    }
+
+    pub fn prev(&self) -> SortKey {
+        let all = Self::all();
+        let idx = all.iter().position(|k| k == self).unwrap_or(0);
+        all[(idx + all.len() - 1) % all.len()]
+    }
+
+    /// Index into `PokemonSummary::stats` for the individual base stat keys
+    pub fn stat_index(&self) -> Option<usize> {
+        match self {
+            SortKey::Hp => Some(0),
+            SortKey::Attack => Some(1),
+            SortKey::Defense => Some(2),
+            SortKey::SpAttack => Some(3),
+            SortKey::SpDefense => Some(4),
+            SortKey::Speed => Some(5),
+            _ => None,
+        }
+    }
+
+    /// None when the value hasn't been fetched yet
+    fn value<'a>(&self, p: &'a PokemonSummary) -> Option<SortValue<'a>> {
+        match self {
+            // Forms sort right after their base species (ties break on id)
+            SortKey::Id => Some(SortValue::Num(p.species_number())),
+            SortKey::Name => Some(SortValue::Text(&p.name)),
+            SortKey::Total => p.base_stat_total().map(SortValue::Num),
+            SortKey::Height => p.height.map(SortValue::Num),
+            SortKey::Weight => p.weight.map(SortValue::Num),
+            SortKey::Type => p.types.first().map(|t| SortValue::Text(t.as_str())),
+            _ => self
+                .stat_index()
+                .and_then(|i| p.stats.get(i).copied())
+                .map(SortValue::Num),
+        }
+    }
+}
+
+/// Sort by `key`, breaking ties by dex number. Pokemon whose value is still
+/// loading always go last, whichever the direction.
+fn sort_list(list: &mut [&PokemonSummary], key: SortKey, descending: bool) {
+    list.sort_by(|a, b| match (key.value(a), key.value(b)) {
+        (Some(x), Some(y)) => {
+            let ord = if descending { y.cmp(&x) } else { x.cmp(&y) };
+            ord.then(a.id.cmp(&b.id))
+        }
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.id.cmp(&b.id),
+    });
+}
+
 pub struct App {
     pub running: bool,
     pub screen: Screen,
+    /// Back/forward through screens and opened Pokémon, and the recently viewed
+    pub history: History,
 
     // Pokemon list
     pub pokemon_list: Vec<PokemonSummary>,
@@ -89,13 +487,54 @@ pub struct App {
     pub search_mode: bool,
     pub search_query: String,
     pub generation_filter: Option<u8>, // None = all generations, Some(1-9) = specific generation
+    pub type_filter: Vec<String>,      // up to two types; empty = no type filter
+    pub type_match: TypeMatch,
+    pub sort_key: SortKey,
+    pub sort_descending: bool,
+    pub summaries_loading: LoadingState, // background fill-in of types and stats
+    /// Generation of each species id, from species data as it arrives
+    species_generations: HashMap<u32, u8>,
+    pub show_forms: bool, // include regional/Mega/Gigantamax forms in the list
+    pub favorites_only: bool,
+    pub tag_filter: Vec<String>, // Pokémon must have all of these tags
+    pub notes: Notes,
+    notes_file: Option<PathBuf>,
+    notes_modified: Option<SystemTime>,
+    pub collection: Collection,
+    collection_file: Option<PathBuf>,
+    collection_modified: Option<SystemTime>,
 
     // Pokemon detail
     pub detail: Option<Box<PokemonDetail>>,
     pub detail_loading: LoadingState,
     pub sprite_bytes: Option<Vec<u8>>,
+    pub sprite_variant: SpriteVariant,
+    pub graphics_protocol: GraphicsProtocol,
+    pub sprite_mode: BlockMode,
+    pub color_depth: ColorDepth,
+    /// Play animated sprites where PokéAPI has them; off means static images only
+    pub animate_sprites: bool,
+    pub animation_paused: bool,
+    pub sprite_animation: Option<SpriteAnimation>,
+    animation_cache: HashMap<(u32, SpriteVariant), Arc<Vec<AnimationFrame>>>,
+    pub sprite_cache: SpriteCache,
+    /// Front sprites for list rows and team slots, fetched as rows come into view
+    pub thumbnails: HashMap<u32, Vec<u8>>,
+    thumbnails_requested: HashSet<u32>,
+    /// Ids the last draw wanted but didn't have; fetched after the frame
+    thumbnails_wanted: RefCell<Vec<u32>>,
+    /// Taller list rows with bigger thumbnails
+    pub expanded_rows: bool,
     pub detail_pokemon_id: Option<u32>,
     pub detail_list_index: Option<usize>, // index in filtered list when viewing detail
+    pub detail_species: Option<PokemonSpecies>,
+    /// Evolution chain of the species on the detail screen
+    pub detail_evolution: Option<EvolutionChain>,
+    /// Ability on the detail screen whose effect is shown, by slot
+    pub selected_ability: Option<usize>,
+    /// Short effects of abilities looked at so far, by ability name
+    pub ability_effects: HashMap<String, String>,
+    abilities_requested: HashSet<String>,
 
     // Type chart
     pub type_infos: Vec<TypeInfo>,
@@ -105,17 +544,39 @@ pub struct App {
 
     // Team builder
     pub team_data: TeamData,
+    /// teams.json, when the teams came from it, and its modification time as
+    /// of the last load or save
+    pub teams_file: Option<PathBuf>,
+    teams_modified: Option<SystemTime>,
     pub current_team: usize,
     pub team_slot_selected: usize,
     pub modal: Option<Modal>,
     pub modal_selected: usize,
+    /// Text typed into the open modal; the note itself while editing one
     pub modal_search: String,
+    /// Key help overlay, drawn over everything else
+    pub show_help: bool,
+    pub help_scroll: usize,
 
     // Move picker
     pub available_moves: Vec<MoveDetail>,
     pub moves_loading: LoadingState,
 
     pub error_message: Option<String>,
+    /// One-line notice in the tab bar, cleared by the next key press
+    pub status_message: Option<String>,
+    pub export_format: ExportFormat,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    /// Preferences as loaded, updated with choices made in the app and saved on exit
+    pub settings: Settings,
+    /// Whether the terminal reports mouse events; off leaves text selection to the terminal
+    pub mouse_capture: bool,
+    /// Screen reader friendly: no sprites, one column, text effectiveness labels
+    /// and a status line describing what's selected
+    pub accessible: bool,
+    /// Clickable areas of the last frame, topmost last
+    click_regions: RefCell<Vec<(Rect, ClickTarget)>>,
     tx: mpsc::UnboundedSender<AppEvent>,
 }
 
@@ -124,30 +585,76 @@ impl App {
         Self {
             running: true,
             screen: Screen::PokemonList,
+            history: History::default(),
             pokemon_list: Vec::new(),
             list_state: 0,
             list_loading: LoadingState::Idle,
             search_mode: false,
             search_query: String::new(),
             generation_filter: None,
+            type_filter: Vec::new(),
+            type_match: TypeMatch::Any,
+            favorites_only: false,
+            tag_filter: Vec::new(),
+            notes: Notes::default(),
+            notes_file: None,
+            notes_modified: None,
+            collection: Collection::default(),
+            collection_file: None,
+            collection_modified: None,
+            sort_key: SortKey::Id,
+            sort_descending: false,
+            summaries_loading: LoadingState::Idle,
+            species_generations: HashMap::new(),
+            show_forms: false,
             detail: None,
             detail_loading: LoadingState::Idle,
             sprite_bytes: None,
+            sprite_variant: SpriteVariant::FrontDefault,
+            graphics_protocol: GraphicsProtocol::HalfBlocks,
+            sprite_mode: BlockMode::HalfBlock,
+            color_depth: ColorDepth::TrueColor,
+            animate_sprites: true,
+            animation_paused: false,
+            sprite_animation: None,
+            animation_cache: HashMap::new(),
+            sprite_cache: SpriteCache::default(),
+            thumbnails: HashMap::new(),
+            thumbnails_requested: HashSet::new(),
+            thumbnails_wanted: RefCell::new(Vec::new()),
+            expanded_rows: false,
             detail_pokemon_id: None,
             detail_list_index: None,
+            detail_species: None,
+            detail_evolution: None,
+            selected_ability: None,
+            ability_effects: HashMap::new(),
+            abilities_requested: HashSet::new(),
             type_infos: Vec::new(),
             type_chart_loading: LoadingState::Idle,
             type_chart_scroll_x: 0,
             type_chart_scroll_y: 0,
-            team_data: TeamData::load(),
+            team_data: TeamData::default(),
+            teams_file: None,
+            teams_modified: None,
             current_team: 0,
             team_slot_selected: 0,
             modal: None,
             modal_selected: 0,
             modal_search: String::new(),
+            show_help: false,
+            help_scroll: 0,
             available_moves: Vec::new(),
             moves_loading: LoadingState::Idle,
             error_message: None,
+            status_message: None,
+            export_format: ExportFormat::Markdown,
+            keymap: Keymap::default(),
+            theme: Theme::default(),
+            settings: Settings::default(),
+            accessible: false,
+            mouse_capture: true,
+            click_regions: RefCell::new(Vec::new()),
             tx,
         }
     }
@@ -158,53 +665,93 @@ impl App {
         team_data: TeamData,
     ) -> Self {
         Self {
-            running: true,
-            screen: Screen::PokemonList,
-            pokemon_list: Vec::new(),
-            list_state: 0,
-            list_loading: LoadingState::Idle,
-            search_mode: false,
-            search_query: String::new(),
-            generation_filter: None,
-            detail: None,
-            detail_loading: LoadingState::Idle,
-            sprite_bytes: None,
-            detail_pokemon_id: None,
-            detail_list_index: None,
-            type_infos: Vec::new(),
-            type_chart_loading: LoadingState::Idle,
-            type_chart_scroll_x: 0,
-            type_chart_scroll_y: 0,
             team_data,
-            current_team: 0,
-            team_slot_selected: 0,
-            modal: None,
-            modal_selected: 0,
-            modal_search: String::new(),
-            available_moves: Vec::new(),
-            moves_loading: LoadingState::Idle,
-            error_message: None,
-            tx,
+            ..Self::new(tx)
         }
     }
 
-    pub fn filtered_list(&self) -> Vec<&PokemonSummary> {
-        let mut filtered: Vec<&PokemonSummary> = self.pokemon_list.iter().collect();
+    /// Form and generation filters, the ones the team builder's picker shares
+    /// with the list
+    fn passes_picker_filters(&self, p: &PokemonSummary) -> bool {
+        if !self.show_forms && p.is_form() {
+            return false;
+        }
+        // Forms take their species' generation; unresolved forms match no generation
+        match self.generation_filter {
+            Some(gen) => summary_generation(p) == Some(gen),
+            None => true,
+        }
+    }
 
-        // Apply generation filter
-        if let Some(gen) = self.generation_filter {
-            filtered.retain(|p| pokemon_generation(p.id) == gen);
+    /// Picker filters plus the list-only type, favorite and tag filters
+    fn passes_filters(&self, p: &PokemonSummary) -> bool {
+        if !self.passes_picker_filters(p) {
+            return false;
+        }
+        if self.favorites_only && !self.notes.is_favorite(p.id) {
+            return false;
         }
+        if !self.notes.has_tags(p.id, &self.tag_filter) {
+            return false;
+        }
+        self.type_filter.is_empty() || self.type_match.matches(&self.type_filter, &p.types)
+    }
+
+    pub fn filtered_list(&self) -> Vec<&PokemonSummary> {
+        let filtered: Vec<&PokemonSummary> = self
+            .pokemon_list
+            .iter()
+            .filter(|p| self.passes_filters(p))
+            .collect();
 
-        // Apply search query filter
-        if !self.search_query.is_empty() {
-            let q = self.search_query.to_lowercase();
-            filtered.retain(|p| p.name.contains(&q) || p.id.to_string().contains(&q));
+        // Apply search query filter, ranked by match quality
+        let mut filtered = rank_by_query(filtered, &self.search_query);
+
+        // The default order keeps search results ranked by relevance
+        if self.search_query.trim().is_empty() || self.is_custom_sort() {
+            sort_list(&mut filtered, self.sort_key, self.sort_descending);
         }
 
         filtered
     }
 
+    pub fn is_custom_sort(&self) -> bool {
+        self.sort_key != SortKey::Id || self.sort_descending
+    }
+
+    /// Add or remove a type from the filter; picking a third type drops the oldest
+    pub fn toggle_type_filter(&mut self, type_name: &str) {
+        if let Some(pos) = self.type_filter.iter().position(|t| t == type_name) {
+            self.type_filter.remove(pos);
+        } else {
+            if self.type_filter.len() >= 2 {
+                self.type_filter.remove(0);
+            }
+            self.type_filter.push(type_name.to_string());
+        }
+        self.list_state = 0;
+    }
+
+    /// Number of Pokémon whose types have been filled in by the background loader
+    pub fn types_known(&self) -> usize {
+        self.pokemon_list
+            .iter()
+            .filter(|p| !p.types.is_empty())
+            .count()
+    }
+
+    /// Give each summary its species' generation where that's known
+    fn fill_generations(&mut self) {
+        for p in &mut self.pokemon_list {
+            if let Some(&generation) = p
+                .known_species_id()
+                .and_then(|id| self.species_generations.get(&id))
+            {
+                p.generation = Some(generation);
+            }
+        }
+    }
+
     pub fn current_team(&self) -> &Team {
         &self.team_data.teams[self.current_team]
     }
@@ -223,24 +770,35 @@ impl App {
             let client = std::sync::Arc::new(ApiClient::new());
             match client.fetch_pokemon_list().await {
                 Ok(list) => {
-                    // Extract ID from URL: "https://pokeapi.co/api/v2/pokemon/25/" -> 25
-                    let summaries: Vec<PokemonSummary> = list
-                        .results
-                        .iter()
-                        .filter_map(|e| {
-                            let id = extract_id_from_url(&e.url)?;
-                            Some(PokemonSummary {
-                                id,
-                                name: e.name.clone(),
-                                types: Vec::new(),
-                            })
-                        })
-                        .collect();
+                    let summaries = summaries_from_list(&list);
 
                     // Send list immediately (no types yet)
                     let _ = tx.send(AppEvent::PokemonListLoaded(summaries.clone()));
 
-                    // Background-fetch types in batches of 30
+                    // Each generation lists the species it introduced, which
+                    // places every Pokémon in a handful of requests
+                    let handles: Vec<_> = (1..=LATEST_GENERATION)
+                        .map(|n| {
+                            let client = client.clone();
+                            tokio::spawn(async move { client.fetch_generation(n).await })
+                        })
+                        .collect();
+                    let mut generations = HashMap::new();
+                    let mut failed = Vec::new();
+                    for (n, handle) in (1..=LATEST_GENERATION).zip(handles) {
+                        let Ok(Ok(generation)) = handle.await else {
+                            failed.push(n);
+                            continue;
+                        };
+                        for species in &generation.pokemon_species {
+                            if let Some(id) = species.id() {
+                                generations.insert(id, generation.id);
+                            }
+                        }
+                    }
+                    let _ = tx.send(AppEvent::GenerationsLoaded(generations, failed));
+
+                    // Background-fetch types and stats in batches of 30
                     let entries: Vec<(u32, String)> =
                         summaries.iter().map(|s| (s.id, s.name.clone())).collect();
 
@@ -250,14 +808,7 @@ impl App {
                             let client = client.clone();
                             handles.push(tokio::spawn(async move {
                                 match client.fetch_pokemon_detail(&id.to_string()).await {
-                                    Ok(detail) => Some((
-                                        id,
-                                        detail
-                                            .types
-                                            .iter()
-                                            .map(|t| t.type_info.name.clone())
-                                            .collect::<Vec<String>>(),
-                                    )),
+                                    Ok(detail) => Some(PokemonSummary::from_detail(&detail)),
                                     Err(_) => None,
                                 }
                             }));
@@ -269,9 +820,10 @@ impl App {
                             }
                         }
                         if !batch.is_empty() {
-                            let _ = tx.send(AppEvent::PokemonTypesUpdated(batch));
+                            let _ = tx.send(AppEvent::PokemonSummariesUpdated(batch));
                         }
                     }
+                    let _ = tx.send(AppEvent::PokemonSummariesFinished);
                 }
                 Err(e) => {
                     let _ = tx.send(AppEvent::ApiError(format!(
@@ -290,22 +842,56 @@ impl App {
         // Find the index of this Pokemon in the filtered list
         let filtered = self.filtered_list();
         self.detail_list_index = filtered.iter().position(|p| p.id == id);
+        // Species data stays valid while switching between forms of one species
+        let same_species = self
+            .detail_species
+            .as_ref()
+            .is_some_and(|s| s.varieties.iter().any(|v| v.pokemon.id() == Some(id)));
+        if !same_species {
+            self.detail_species = None;
+            self.detail_evolution = None;
+        }
         self.detail = None;
         self.sprite_bytes = None;
+        self.selected_ability = None;
         self.detail_pokemon_id = Some(id);
         self.detail_loading = LoadingState::Loading;
         let tx = self.tx.clone();
+        let wanted = self.sprite_variant;
         tokio::spawn(async move {
             let client = ApiClient::new();
             match client.fetch_pokemon_detail(&id.to_string()).await {
                 Ok(detail) => {
+                    // Keep the chosen variant while browsing, if this Pokémon has it
+                    let variant = if detail.sprites.url(wanted).is_some() {
+                        wanted
+                    } else {
+                        SpriteVariant::FrontDefault
+                    };
+                    let sprite_url = detail.sprites.url(variant).map(str::to_string);
+                    let species_key = detail
+                        .species
+                        .as_ref()
+                        .map(|s| s.id().map(|i| i.to_string()).unwrap_or(s.name.clone()));
+                    let _ = tx.send(AppEvent::PokemonDetailLoaded(Box::new(detail)));
                     // Also fetch sprite
-                    if let Some(ref url) = detail.sprites.front_default {
-                        if let Ok(bytes) = client.fetch_sprite_bytes(url).await {
-                            let _ = tx.send(AppEvent::SpriteLoaded(id, bytes));
+                    if let Some(url) = sprite_url {
+                        if let Ok(bytes) = client.fetch_sprite_bytes(&url).await {
+                            let _ = tx.send(AppEvent::SpriteLoaded(id, variant, bytes));
+                        }
+                    }
+                    if let Some(key) = species_key {
+                        if let Ok(species) = client.fetch_species(&key).await {
+                            let chain_url = species.evolution_chain.as_ref().map(|c| c.url.clone());
+                            let _ = tx.send(AppEvent::SpeciesLoaded(Box::new(species)));
+                            if let Some(url) = chain_url {
+                                if let Ok(chain) = client.fetch_evolution_chain(&url).await {
+                                    let _ =
+                                        tx.send(AppEvent::EvolutionChainLoaded(Box::new(chain)));
+                                }
+                            }
                         }
                     }
-                    let _ = tx.send(AppEvent::PokemonDetailLoaded(Box::new(detail)));
                 }
                 Err(e) => {
                     let _ = tx.send(AppEvent::ApiError(format!("Failed to load detail: {}", e)));
@@ -314,6 +900,234 @@ impl App {
         });
     }
 
+    /// Switch the detail sprite to the next (or previous) variant this Pokémon has
+    pub fn cycle_sprite_variant(&mut self, forward: bool) {
+        let Some(ref detail) = self.detail else {
+            return;
+        };
+        let available = detail.sprites.available();
+        if available.is_empty() {
+            return;
+        }
+        let next = match available.iter().position(|&v| v == self.sprite_variant) {
+            Some(pos) if forward => available[(pos + 1) % available.len()],
+            Some(pos) => available[(pos + available.len() - 1) % available.len()],
+            None => available[0],
+        };
+        if next == self.sprite_variant {
+            return;
+        }
+        let Some(url) = detail.sprites.url(next).map(str::to_string) else {
+            return;
+        };
+        let id = detail.id;
+        self.sprite_variant = next;
+        self.sprite_bytes = None;
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let client = ApiClient::new();
+            if let Ok(bytes) = client.fetch_sprite_bytes(&url).await {
+                let _ = tx.send(AppEvent::SpriteLoaded(id, next, bytes));
+            }
+        });
+        self.load_animation();
+    }
+
+    /// Start the animated version of the current sprite, from cache or the Black/White GIFs
+    fn load_animation(&mut self) {
+        self.sprite_animation = None;
+        if !self.animate_sprites {
+            return;
+        }
+        let Some(ref detail) = self.detail else {
+            return;
+        };
+        let (id, variant) = (detail.id, self.sprite_variant);
+        if let Some(frames) = self.animation_cache.get(&(id, variant)) {
+            self.sprite_animation = Some(SpriteAnimation::new(id, variant, frames.clone()));
+            return;
+        }
+        let Some(url) = detail.sprites.animated_url(variant).map(str::to_string) else {
+            return;
+        };
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let client = ApiClient::new();
+            let Ok(bytes) = client.fetch_sprite_bytes(&url).await else {
+                return;
+            };
+            if let Ok(Some(frames)) = tokio::task::spawn_blocking(move || decode_gif(&bytes)).await
+            {
+                let _ = tx.send(AppEvent::AnimationLoaded(id, variant, frames));
+            }
+        });
+    }
+
+    /// The animation for the sprite on screen, if it's playing
+    fn active_animation(&self) -> Option<&SpriteAnimation> {
+        self.sprite_animation.as_ref().filter(|anim| {
+            self.animate_sprites
+                && Some(anim.pokemon_id) == self.detail_pokemon_id
+                && anim.variant == self.sprite_variant
+        })
+    }
+
+    /// PNG bytes to draw for the detail sprite: the current animation frame when playing
+    pub fn current_sprite_bytes(&self) -> Option<&[u8]> {
+        match self.active_animation() {
+            Some(anim) => Some(anim.frame()),
+            None => self.sprite_bytes.as_deref(),
+        }
+    }
+
+    /// Thumbnail bytes for `id`, noting it for fetching if it isn't loaded yet
+    pub fn want_thumbnail(&self, id: u32) -> Option<&[u8]> {
+        let bytes = self.thumbnails.get(&id).map(Vec::as_slice);
+        if bytes.is_none() && !self.thumbnails_requested.contains(&id) {
+            self.thumbnails_wanted.borrow_mut().push(id);
+        }
+        bytes
+    }
+
+    /// Fetch the thumbnails the last frame asked for
+    pub fn fetch_wanted_thumbnails(&mut self) {
+        let wanted = std::mem::take(&mut *self.thumbnails_wanted.borrow_mut());
+        for id in wanted {
+            if !self.thumbnails_requested.insert(id) {
+                continue;
+            }
+            let tx = self.tx.clone();
+            tokio::spawn(async move {
+                let client = ApiClient::new();
+                if let Ok(bytes) = client.fetch_thumbnail(id).await {
+                    let _ = tx.send(AppEvent::ThumbnailLoaded(id, bytes));
+                }
+            });
+        }
+    }
+
+    /// Lines per row in the Pokédex list, picker and team slots
+    pub fn row_height(&self) -> usize {
+        if self.expanded_rows {
+            3
+        } else {
+            1
+        }
+    }
+
+    /// Animation frame index for the sprite on screen, None for a still image
+    pub fn current_sprite_frame(&self) -> Option<usize> {
+        self.active_animation().map(|anim| anim.frame_index())
+    }
+
+    /// Render the current team as a PNG card in the export directory
+    pub fn export_team_card(&mut self) {
+        self.export_team_card_to(export::export_dir());
+    }
+
+    pub(crate) fn export_team_card_to(&mut self, dir: PathBuf) {
+        let team = self.current_team().clone();
+        let mut sprites: HashMap<u32, Vec<u8>> = team
+            .members
+            .iter()
+            .filter_map(|m| Some((m.pokemon_id, self.thumbnails.get(&m.pokemon_id)?.clone())))
+            .collect();
+        let stem = format!("team-{}", export::slug(&team.name));
+        self.status_message = Some("Exporting team card...".to_string());
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let client = ApiClient::new();
+            for m in &team.members {
+                if let std::collections::hash_map::Entry::Vacant(slot) = sprites.entry(m.pokemon_id)
+                {
+                    if let Ok(bytes) = client.fetch_thumbnail(m.pokemon_id).await {
+                        slot.insert(bytes);
+                    }
+                }
+            }
+            let result = tokio::task::spawn_blocking(move || {
+                let img = export::card::team_card(&team, &sprites);
+                let path = export::unused_path(&dir, &stem, "png");
+                export::save_png(&img, &path).map(|_| path)
+            })
+            .await;
+            let _ = tx.send(AppEvent::ExportFinished(flatten_export(result), 0));
+        });
+    }
+
+    /// Render the Pokémon on the detail screen as a PNG card, using the sprite variant shown
+    pub fn export_detail_card(&mut self) {
+        self.export_detail_card_to(export::export_dir());
+    }
+
+    pub(crate) fn export_detail_card_to(&mut self, dir: PathBuf) {
+        let Some(detail) = self.detail.clone() else {
+            return;
+        };
+        let sprite = self.sprite_bytes.clone();
+        let stem = format!("pokemon-{}", export::slug(&detail.name));
+        self.status_message = Some("Exporting card...".to_string());
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                let img = export::card::detail_card(&detail, sprite.as_deref());
+                let path = export::unused_path(&dir, &stem, "png");
+                export::save_png(&img, &path).map(|_| path)
+            })
+            .await;
+            let _ = tx.send(AppEvent::ExportFinished(flatten_export(result), 0));
+        });
+    }
+
+    /// Write the filtered list (list screen) or the open Pokémon (detail screen) as text
+    pub fn export_entries(&mut self) {
+        self.export_entries_to(export::export_dir());
+    }
+
+    pub(crate) fn export_entries_to(&mut self, dir: PathBuf) {
+        let format = self.export_format;
+        let (name, summaries, detail) = match self.screen {
+            Screen::PokemonDetail => {
+                let Some(detail) = self.detail.clone() else {
+                    return;
+                };
+                let name = format!("pokemon-{}", export::slug(&detail.name));
+                (name, Vec::new(), Some(detail))
+            }
+            _ => {
+                let summaries: Vec<PokemonSummary> =
+                    self.filtered_list().into_iter().cloned().collect();
+                if summaries.is_empty() {
+                    return;
+                }
+                ("pokedex".to_string(), summaries, None)
+            }
+        };
+        self.status_message = Some(format!("Exporting {}...", format.label()));
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let (entries, incomplete) = match detail {
+                Some(detail) => (vec![DexEntry::from_detail(&detail)], 0),
+                None => export::fetch_entries(Arc::new(ApiClient::new()), summaries).await,
+            };
+            let result = export::table::render(&entries, format).and_then(|text| {
+                std::fs::create_dir_all(&dir)?;
+                let path = export::unused_path(&dir, &name, format.extension());
+                std::fs::write(&path, text)?;
+                Ok(path)
+            });
+            let _ = tx.send(AppEvent::ExportFinished(
+                result.map_err(|e| e.to_string()),
+                incomplete,
+            ));
+        });
+    }
+
+    pub fn cycle_export_format(&mut self) {
+        self.export_format = self.export_format.next();
+        self.status_message = Some(format!("Export format: {}", self.export_format.label()));
+    }
+
     pub fn load_types(&mut self) {
         if self.type_chart_loading == LoadingState::Loaded {
             return;
@@ -374,30 +1188,121 @@ impl App {
         });
     }
 
+    /// Advance the animation on screen; returns true when its frame changed,
+    /// the only time a tick needs a redraw
+    pub fn tick(&mut self) -> bool {
+        if !self.shows_detail() || self.animation_paused || self.active_animation().is_none() {
+            return false;
+        }
+        self.sprite_animation
+            .as_mut()
+            .is_some_and(|anim| anim.advance(TICK_RATE))
+    }
+
     pub fn handle_event(&mut self, event: AppEvent) {
         match event {
             AppEvent::Key(key) => self.handle_key(key),
-            AppEvent::Tick => {}
+            AppEvent::Mouse(mouse) => self.handle_mouse(mouse),
+            AppEvent::Resize(_, _) => self.sprite_cache.clear(),
+            AppEvent::Tick => {
+                self.tick();
+            }
             AppEvent::PokemonListLoaded(list) => {
                 self.pokemon_list = list;
+                self.fill_generations();
                 self.list_loading = LoadingState::Loaded;
+                self.summaries_loading = LoadingState::Loading;
+                // A restored session may point past the end, or at a Pokémon
+                // opened before the list was there
+                let filtered = self.filtered_list();
+                let list_state = self.list_state.min(filtered.len().saturating_sub(1));
+                let detail_index = self
+                    .detail_pokemon_id
+                    .and_then(|id| filtered.iter().position(|p| p.id == id));
+                self.list_state = list_state;
+                self.detail_list_index = self.detail_list_index.or(detail_index);
+                self.follow_selection();
             }
-            AppEvent::PokemonTypesUpdated(batch) => {
-                for (id, types) in batch {
-                    if let Some(p) = self.pokemon_list.iter_mut().find(|p| p.id == id) {
-                        p.types = types;
+            AppEvent::PokemonSummariesUpdated(batch) => {
+                for mut summary in batch {
+                    if let Some(p) = self.pokemon_list.iter_mut().find(|p| p.id == summary.id) {
+                        // Keep the inferred species if the detail didn't carry one
+                        summary.species_id = summary.species_id.or(p.species_id);
+                        summary.generation = p.generation;
+                        *p = summary;
                     }
                 }
+                // Forms may only now have a species to look up
+                self.fill_generations();
+            }
+            AppEvent::GenerationsLoaded(generations, failed) => {
+                self.species_generations.extend(generations);
+                self.fill_generations();
+                if !failed.is_empty() {
+                    let list: Vec<String> = failed.iter().map(u8::to_string).collect();
+                    self.error_message = Some(format!(
+                        "Couldn't load generation {}; going by Pokédex number there",
+                        list.join(", ")
+                    ));
+                }
+            }
+            AppEvent::PokemonSummariesFinished => {
+                self.summaries_loading = LoadingState::Loaded;
             }
+            // Moving through the list with the detail beside it can leave
+            // replies behind for Pokémon no longer selected
+            AppEvent::PokemonDetailLoaded(detail)
+                if self.detail_pokemon_id.is_some_and(|id| id != detail.id) => {}
             AppEvent::PokemonDetailLoaded(detail) => {
+                if detail.sprites.url(self.sprite_variant).is_none() {
+                    self.sprite_variant = SpriteVariant::FrontDefault;
+                }
+                // Only Pokémon opened on the detail screen count as viewed
+                if self.screen == Screen::PokemonDetail {
+                    self.history.viewed(detail.id);
+                }
                 self.detail = Some(detail);
                 self.detail_loading = LoadingState::Loaded;
+                self.load_animation();
+            }
+            AppEvent::SpeciesLoaded(species) => {
+                if let Some(generation) = species.generation_number() {
+                    self.species_generations.insert(species.id, generation);
+                    self.fill_generations();
+                }
+                let current = self.detail_pokemon_id;
+                if species
+                    .varieties
+                    .iter()
+                    .any(|v| v.pokemon.id().is_some() && v.pokemon.id() == current)
+                {
+                    self.detail_species = Some(*species);
+                }
+            }
+            AppEvent::EvolutionChainLoaded(chain) => {
+                let species = self.detail_species.as_ref().map(|s| s.id);
+                if species.is_some_and(|id| chain.contains(id)) {
+                    self.detail_evolution = Some(*chain);
+                }
             }
-            AppEvent::SpriteLoaded(id, bytes) => {
-                if self.detail_pokemon_id == Some(id) {
+            AppEvent::SpriteLoaded(id, variant, bytes) => {
+                if self.detail_pokemon_id == Some(id) && self.sprite_variant == variant {
                     self.sprite_bytes = Some(bytes);
                 }
             }
+            AppEvent::AnimationLoaded(id, variant, frames) => {
+                if self.animation_cache.len() >= MAX_CACHED_ANIMATIONS {
+                    self.animation_cache.clear();
+                }
+                let frames = Arc::new(frames);
+                self.animation_cache.insert((id, variant), frames.clone());
+                if self.detail_pokemon_id == Some(id) && self.sprite_variant == variant {
+                    self.sprite_animation = Some(SpriteAnimation::new(id, variant, frames));
+                }
+            }
+            AppEvent::ThumbnailLoaded(id, bytes) => {
+                self.thumbnails.insert(id, bytes);
+            }
             AppEvent::TypesLoaded(infos) => {
                 self.type_infos = infos;
                 self.type_chart_loading = LoadingState::Loaded;
@@ -406,6 +1311,25 @@ impl App {
                 self.available_moves = moves;
                 self.moves_loading = LoadingState::Loaded;
             }
+            AppEvent::AbilityLoaded(name, effect) => {
+                self.abilities_requested.remove(&name);
+                match effect {
+                    Some(effect) => {
+                        self.ability_effects.insert(name, effect);
+                    }
+                    None => {
+                        self.status_message =
+                            Some(format!("Couldn't load ability {}", name.replace('-', " ")))
+                    }
+                }
+            }
+            AppEvent::ExportFinished(result, incomplete) => {
+                self.status_message = Some(match (result, export::incomplete_note(incomplete)) {
+                    (Ok(path), Some(note)) => format!("Saved {}, but {}", path.display(), note),
+                    (Ok(path), None) => format!("Saved {}", path.display()),
+                    (Err(e), _) => format!("Export failed: {}", e),
+                });
+            }
             AppEvent::ApiError(msg) => {
                 self.error_message = Some(msg);
                 self.list_loading = LoadingState::Error;
@@ -415,69 +1339,430 @@ impl App {
         }
     }
 
+    /// Bindings for the current screen
+    pub(crate) fn screen_context(&self) -> Context {
+        match self.screen {
+            Screen::PokemonList => Context::List,
+            Screen::PokemonDetail => Context::Detail,
+            Screen::TypeChart => Context::Chart,
+            Screen::TeamBuilder => Context::Team,
+        }
+    }
+
     fn handle_key(&mut self, key: KeyEvent) {
+        self.dispatch_key(key);
+        self.follow_selection();
+        self.history.visit(self.location());
+    }
+
+    fn dispatch_key(&mut self, key: KeyEvent) {
+        self.status_message = None;
+
         // Dismiss error on any key
         if self.error_message.is_some() {
             self.error_message = None;
             return;
         }
 
+        if self.show_help {
+            self.handle_help_key(key);
+            return;
+        }
+
         // Modal handling
         if let Some(modal) = self.modal {
             self.handle_modal_key(key, modal);
             return;
         }
 
-        // Global keys
-        match key.code {
-            KeyCode::Char('q') if !self.search_mode => {
-                self.running = false;
-                return;
+        // Typing goes into the query; only Ctrl/Alt global keys get through
+        if self.search_mode {
+            if !self.handle_search_input(key) && Key::from_event(&key).is_modified() {
+                if let Some(action) = self.keymap.action(Context::Global, &key) {
+                    self.global_action(action);
+                }
+            }
+            return;
+        }
+
+        if let Some(action) = self.keymap.action(self.screen_context(), &key) {
+            if !self.global_action(action) {
+                self.screen_action(action);
             }
-            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+        }
+    }
+
+    /// Run an action that works on every screen; false if it's a screen's own
+    fn global_action(&mut self, action: Action) -> bool {
+        let screens = Screen::all();
+        let idx = self.screen.index();
+        self.screen = match action {
+            Action::Quit => {
                 self.running = false;
-                return;
+                return true;
             }
-            KeyCode::Tab if !self.search_mode => {
-                let screens = Screen::all();
-                let idx = self.screen.index();
-                self.screen = screens[(idx + 1) % screens.len()];
-                self.on_screen_enter();
-                return;
+            Action::ToggleMouse => {
+                self.mouse_capture = !self.mouse_capture;
+                self.status_message = Some(if self.mouse_capture {
+                    "Mouse on".to_string()
+                } else {
+                    "Mouse off, text selection enabled".to_string()
+                });
+                return true;
             }
-            KeyCode::BackTab if !self.search_mode => {
-                let screens = Screen::all();
-                let idx = self.screen.index();
-                self.screen = screens[(idx + screens.len() - 1) % screens.len()];
-                self.on_screen_enter();
-                return;
+            Action::CycleTheme => {
+                self.set_theme(self.theme.name.next());
+                return true;
             }
-            KeyCode::Char(c @ '1'..='4')
-                if !self.search_mode && self.screen != Screen::PokemonList =>
-            {
-                let idx = (c as usize) - ('1' as usize);
-                self.screen = Screen::all()[idx];
-                self.on_screen_enter();
-                return;
+            Action::Help => {
+                self.open_help();
+                return true;
             }
-            _ => {}
+            Action::CommandPalette => {
+                self.modal = Some(Modal::CommandPalette);
+                self.modal_selected = 0;
+                self.modal_search.clear();
+                return true;
+            }
+            Action::HistoryBack => {
+                self.step_history(true);
+                return true;
+            }
+            Action::HistoryForward => {
+                self.step_history(false);
+                return true;
+            }
+            Action::RecentlyViewed => {
+                self.modal = Some(Modal::Recent);
+                self.modal_selected = 0;
+                return true;
+            }
+            Action::ToggleSplit => {
+                self.toggle_split();
+                return true;
+            }
+            Action::GrowPane => {
+                self.resize_split(PANE_STEP);
+                return true;
+            }
+            Action::ShrinkPane => {
+                self.resize_split(-PANE_STEP);
+                return true;
+            }
+            Action::NextScreen => screens[(idx + 1) % screens.len()],
+            Action::PrevScreen => screens[(idx + screens.len() - 1) % screens.len()],
+            Action::ShowList => Screen::PokemonList,
+            Action::ShowDetail => Screen::PokemonDetail,
+            Action::ShowChart => Screen::TypeChart,
+            Action::ShowTeam => Screen::TeamBuilder,
+            _ => return false,
+        };
+        self.on_screen_enter();
+        true
+    }
+
+    fn screen_action(&mut self, action: Action) {
+        match self.screen {
+            Screen::PokemonList => self.list_action(action),
+            Screen::PokemonDetail => self.detail_action(action),
+            Screen::TypeChart => self.type_chart_action(action),
+            Screen::TeamBuilder => self.team_action(action),
+        }
+    }
+
+    /// Edit the list or picker search query. False for keys that aren't editing,
+    /// which then go through the keymap.
+    fn handle_search_input(&mut self, key: KeyEvent) -> bool {
+        if Key::from_event(&key).is_modified() {
+            return false;
+        }
+        let in_picker = self.modal.is_some();
+        let (query, selected) = if in_picker {
+            (&mut self.modal_search, &mut self.modal_selected)
+        } else {
+            (&mut self.search_query, &mut self.list_state)
+        };
+        match key.code {
+            // Typing modals use it on the selected row instead
+            KeyCode::Enter if self.modal.is_some_and(|m| m.is_typing()) => return false,
+            KeyCode::Enter => self.search_mode = false,
+            // Esc in the picker closes it, via the keymap
+            KeyCode::Esc if !in_picker => self.search_mode = false,
+            KeyCode::Backspace => {
+                query.pop();
+                *selected = 0;
+            }
+            KeyCode::Char(c) => {
+                query.push(c);
+                *selected = 0;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Remember an area of the frame being drawn as clickable
+    pub fn register_click(&self, area: Rect, target: ClickTarget) {
+        if !area.is_empty() {
+            self.click_regions.borrow_mut().push((area, target));
+        }
+    }
+
+    /// Called at the start of every draw
+    pub fn clear_click_regions(&self) {
+        self.click_regions.borrow_mut().clear();
+    }
+
+    /// Topmost clickable target under a cell
+    fn click_target_at(&self, column: u16, row: u16) -> Option<ClickTarget> {
+        self.click_regions
+            .borrow()
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map(|(_, target)| *target)
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.handle_click(mouse.column, mouse.row),
+            MouseEventKind::ScrollUp => self.handle_scroll(true, mouse.modifiers),
+            MouseEventKind::ScrollDown => self.handle_scroll(false, mouse.modifiers),
+            _ => return,
+        }
+        self.follow_selection();
+        self.history.visit(self.location());
+    }
+
+    /// A click selects; clicking what's already selected acts like Enter
+    fn handle_click(&mut self, column: u16, row: u16) {
+        self.status_message = None;
+        if self.error_message.is_some() {
+            self.error_message = None;
+            return;
+        }
+        if self.show_help {
+            self.show_help = false;
+            return;
+        }
+        let Some(target) = self.click_target_at(column, row) else {
+            return;
+        };
+        // Only the modal's own rows respond while one is open
+        if self.modal.is_some() && !matches!(target, ClickTarget::ModalRow(_)) {
+            return;
+        }
+        // Reaching for the mouse ends typing, keeping the query like Enter does
+        self.search_mode = false;
+        match target {
+            ClickTarget::Tab(screen) => {
+                if self.screen != screen {
+                    self.screen = screen;
+                    self.on_screen_enter();
+                }
+            }
+            ClickTarget::ListRow(i) => {
+                if self.list_state == i {
+                    self.list_action(Action::Select);
+                } else {
+                    self.list_state = i;
+                }
+            }
+            ClickTarget::TeamSlot(i) => {
+                if self.team_slot_selected == i {
+                    self.team_action(Action::Select);
+                } else {
+                    self.team_slot_selected = i;
+                }
+            }
+            ClickTarget::ModalRow(i) => {
+                let Some(modal) = self.modal else {
+                    return;
+                };
+                // Type and tag rows toggle straight away
+                let activate = matches!(
+                    modal,
+                    Modal::TypeFilter | Modal::TagFilter | Modal::EditTags
+                ) || self.modal_selected == i;
+                self.modal_selected = i;
+                if activate {
+                    self.modal_action(Action::Select, modal);
+                }
+            }
+            ClickTarget::Form(id) => {
+                if self.detail_pokemon_id != Some(id) {
+                    self.show_form(id);
+                }
+            }
+            ClickTarget::Evolution(id) => {
+                self.load_detail(id);
+                self.screen = Screen::PokemonDetail;
+            }
+            ClickTarget::Ability(slot) => self.select_ability(slot),
         }
+    }
 
-        // Per-screen keys
+    /// The wheel moves the selection of the open modal or the current screen;
+    /// with Shift it scrolls the type chart sideways
+    fn handle_scroll(&mut self, up: bool, modifiers: KeyModifiers) {
+        if self.error_message.is_some() {
+            return;
+        }
+        let sideways = modifiers.contains(KeyModifiers::SHIFT);
+        let action = match (up, sideways) {
+            (true, false) => Action::Up,
+            (false, false) => Action::Down,
+            (true, true) => Action::Left,
+            (false, true) => Action::Right,
+        };
+        if self.show_help {
+            if !sideways {
+                self.scroll_help(action);
+            }
+            return;
+        }
+        if let Some(modal) = self.modal {
+            if !sideways {
+                self.search_mode = false;
+                self.modal_action(action, modal);
+            }
+            return;
+        }
         match self.screen {
-            Screen::PokemonList => self.handle_list_key(key),
-            Screen::PokemonDetail => self.handle_detail_key(key),
-            Screen::TypeChart => self.handle_type_chart_key(key),
-            Screen::TeamBuilder => self.handle_team_key(key),
+            Screen::PokemonList if !sideways => {
+                self.search_mode = false;
+                self.list_action(action);
+            }
+            Screen::TeamBuilder if !sideways => self.team_action(action),
+            // Sideways scrolls the chart beside the team
+            Screen::TeamBuilder if self.split().is_some() => self.type_chart_action(action),
+            Screen::TypeChart => self.type_chart_action(action),
+            _ => {}
+        }
+    }
+
+    /// Apply saved preferences and pick up where the last session ended
+    pub fn restore(&mut self, settings: Settings, session: Session) {
+        let filters = &settings.filters;
+        self.type_filter = filters.types.clone();
+        self.type_filter.truncate(2);
+        self.type_match = filters.type_match;
+        self.show_forms = filters.show_forms;
+        self.sort_key = filters.sort;
+        self.sort_descending = filters.descending;
+        self.favorites_only = filters.favorites_only;
+        self.tag_filter = filters.tags.clone();
+        self.sprite_mode = settings.sprite_mode;
+
+        self.search_query = session.search_query;
+        self.generation_filter = session
+            .generation_filter
+            .filter(|g| (1..=LATEST_GENERATION).contains(g));
+        self.list_state = session.list_state;
+        self.current_team = session
+            .current_team
+            .min(self.team_data.teams.len().saturating_sub(1));
+        self.history.set_recent(session.recent);
+        if let Some(id) = session.pokemon_id {
+            self.load_detail(id);
+        }
+        self.screen = settings.default_screen.unwrap_or(session.screen);
+        if self.screen == Screen::PokemonDetail && session.pokemon_id.is_none() {
+            self.screen = Screen::PokemonList;
+        }
+        // The list is loaded at startup anyway
+        if self.screen != Screen::PokemonList {
+            self.on_screen_enter();
+        }
+        self.history.visit(self.location());
+        self.settings = settings;
+    }
+
+    /// Use the teams in `path` from now on, loaded and saved there
+    pub fn set_teams(&mut self, team_data: TeamData, path: PathBuf) {
+        self.team_data = team_data;
+        self.teams_modified = modified(&path);
+        self.teams_file = Some(path);
+    }
+
+    /// Pick up changes made to teams.json since it was last loaded or saved,
+    /// such as through the API server, so the next save doesn't undo them.
+    /// Returns false if the changed file couldn't be read, in which case the
+    /// edit must be skipped rather than saved over it.
+    fn refresh_teams(&mut self) -> bool {
+        let Some(path) = &self.teams_file else {
+            return true;
+        };
+        let now = modified(path);
+        // Read-only teams stand in for a file this version can't read, so
+        // try it again in case it was replaced
+        if now == self.teams_modified && !self.team_data.read_only {
+            return true;
+        }
+        match TeamData::read_from(path) {
+            Ok(data) => {
+                self.team_data = data;
+                self.teams_modified = now;
+                self.current_team = self
+                    .current_team
+                    .min(self.team_data.teams.len().saturating_sub(1));
+                true
+            }
+            Err(e) => {
+                self.error_message = Some(format!("{:#}", e));
+                false
+            }
+        }
+    }
+
+    /// Save teams after an edit, showing an error if that fails
+    fn save_teams(&mut self) {
+        let Some(path) = &self.teams_file else {
+            return;
+        };
+        match self.team_data.save_to(path) {
+            Ok(()) => self.teams_modified = modified(path),
+            Err(e) => self.error_message = Some(format!("Couldn't save teams: {}", e)),
+        }
+    }
+
+    pub fn session(&self) -> Session {
+        Session {
+            screen: self.screen,
+            search_query: self.search_query.clone(),
+            generation_filter: self.generation_filter,
+            list_state: self.list_state,
+            pokemon_id: self.detail_pokemon_id,
+            current_team: self.current_team,
+            recent: self.history.recent().to_vec(),
         }
     }
 
+    /// Write settings and the session for next time
+    pub fn save_state(&mut self) {
+        self.settings.filters = Filters {
+            types: self.type_filter.clone(),
+            type_match: self.type_match,
+            show_forms: self.show_forms,
+            sort: self.sort_key,
+            descending: self.sort_descending,
+            favorites_only: self.favorites_only,
+            tags: self.tag_filter.clone(),
+        };
+        self.settings.sprite_mode = self.sprite_mode;
+        let _ = self.settings.save();
+        let _ = self.session().save();
+    }
+
     fn on_screen_enter(&mut self) {
         match self.screen {
-            Screen::PokemonList => self.start_loading_list(),
+            Screen::PokemonList => {
+                self.start_loading_list();
+                self.follow_selection();
+            }
             Screen::PokemonDetail => {
-                // If no detail loaded, pick from list
-                if self.detail.is_none() && !self.pokemon_list.is_empty() {
+                // If no Pokémon was opened yet, pick from list
+                if self.detail_pokemon_id.is_none() && !self.pokemon_list.is_empty() {
                     let filtered = self.filtered_list();
                     if let Some(p) = filtered.first() {
                         self.load_detail(p.id);
@@ -485,38 +1770,77 @@ impl App {
                 }
             }
             Screen::TypeChart => self.load_types(),
-            Screen::TeamBuilder => {}
+            Screen::TeamBuilder => {
+                // A failed reload is reported; edits stay blocked until it works
+                let _ = self.refresh_teams();
+                if self.split().is_some() {
+                    self.load_types();
+                }
+            }
         }
     }
 
-    fn handle_list_key(&mut self, key: KeyEvent) {
-        if self.search_mode {
-            match key.code {
-                KeyCode::Esc => {
-                    self.search_mode = false;
-                }
-                KeyCode::Enter => {
-                    self.search_mode = false;
-                }
-                KeyCode::Backspace => {
-                    self.search_query.pop();
-                    self.list_state = 0;
-                }
-                KeyCode::Char(c) => {
-                    self.search_query.push(c);
-                    self.list_state = 0;
-                }
-                _ => {}
-            }
+    /// Left pane's width in percent when the current screen is split. The
+    /// accessible mode keeps to one column.
+    pub fn split(&self) -> Option<u16> {
+        if self.accessible {
+            return None;
+        }
+        let split = self.settings.panes.get(self.screen)?;
+        split.enabled.then(|| split.left_percent())
+    }
+
+    /// Whether the detail is drawn, on its own screen or beside the list
+    pub fn shows_detail(&self) -> bool {
+        match self.screen {
+            Screen::PokemonDetail => true,
+            Screen::PokemonList => self.split().is_some(),
+            _ => false,
+        }
+    }
+
+    fn toggle_split(&mut self) {
+        let Some(split) = self.settings.panes.get_mut(self.screen) else {
+            self.status_message = Some(format!("No split view for the {}", self.screen.label()));
             return;
+        };
+        split.enabled = !split.enabled;
+        let enabled = split.enabled;
+        self.status_message = Some(format!("Split view {}", if enabled { "on" } else { "off" }));
+        // Load whatever the second pane shows
+        if enabled {
+            self.on_screen_enter();
         }
+    }
 
-        match key.code {
-            KeyCode::Char('/') => {
+    fn resize_split(&mut self, step: i16) {
+        if self.split().is_none() {
+            return;
+        }
+        if let Some(split) = self.settings.panes.get_mut(self.screen) {
+            split.resize(step);
+            self.sprite_cache.clear();
+        }
+    }
+
+    /// With the detail beside the list, show whichever Pokémon is selected
+    fn follow_selection(&mut self) {
+        if self.screen != Screen::PokemonList || self.split().is_none() {
+            return;
+        }
+        let selected = self.filtered_list().get(self.list_state).map(|p| p.id);
+        if let Some(id) = selected.filter(|&id| self.detail_pokemon_id != Some(id)) {
+            self.load_detail(id);
+        }
+    }
+
+    fn list_action(&mut self, action: Action) {
+        match action {
+            Action::Search => {
                 self.search_mode = true;
                 self.search_query.clear();
             }
-            KeyCode::Char('G') => {
+            Action::NextGeneration => {
                 // Cycle through generations: None -> Gen 1 -> ... -> Gen 9 -> None
                 self.generation_filter = match self.generation_filter {
                     None => Some(1),
@@ -525,38 +1849,66 @@ impl App {
                 };
                 self.list_state = 0;
             }
-            KeyCode::Char('g') => {
-                // Also allow lowercase 'g' to cycle
-                self.generation_filter = match self.generation_filter {
-                    None => Some(1),
-                    Some(9) => None,
-                    Some(n) => Some(n + 1),
-                };
-                self.list_state = 0;
-            }
-            KeyCode::Char('0') => {
-                // Clear generation filter
+            Action::AllGenerations => {
                 self.generation_filter = None;
                 self.list_state = 0;
             }
-            KeyCode::Char(c @ '1'..='9') => {
-                // Direct generation selection (1-9)
-                let gen = (c as u8) - b'0';
+            Action::Generation(gen) => {
                 self.generation_filter = Some(gen);
                 self.list_state = 0;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.list_state > 0 {
-                    self.list_state -= 1;
+            Action::TypeFilter => {
+                self.modal = Some(Modal::TypeFilter);
+                self.modal_selected = 0;
+            }
+            Action::ClearTypeFilter => {
+                self.type_filter.clear();
+                self.list_state = 0;
+            }
+            Action::ToggleForms => {
+                self.show_forms = !self.show_forms;
+                self.list_state = 0;
+            }
+            Action::ToggleRowSize => self.expanded_rows = !self.expanded_rows,
+            Action::Export => self.export_entries(),
+            Action::CycleExportFormat => self.cycle_export_format(),
+            Action::ToggleFavorite => {
+                let selected = self.filtered_list().get(self.list_state).map(|p| p.id);
+                if let Some(id) = selected {
+                    self.toggle_favorite(id);
+                    let len = self.filtered_list().len();
+                    self.list_state = self.list_state.min(len.saturating_sub(1));
                 }
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                let max = self.filtered_list().len().saturating_sub(1);
-                if self.list_state < max {
-                    self.list_state += 1;
+            Action::FavoritesOnly => {
+                self.favorites_only = !self.favorites_only;
+                self.list_state = 0;
+            }
+            Action::TagFilter => self.open_typing_modal(Modal::TagFilter),
+            Action::CycleCollected => {
+                let selected = self.filtered_list().get(self.list_state).map(|p| p.id);
+                if let Some(id) = selected {
+                    self.cycle_collected(id);
                 }
             }
-            KeyCode::Enter => {
+            Action::MarkRange => self.open_typing_modal(Modal::MarkRange),
+            Action::CollectionProgress => {
+                self.modal = Some(Modal::Progress);
+                self.modal_selected = 0;
+            }
+            Action::NextSort => {
+                self.sort_key = self.sort_key.next();
+                self.list_state = 0;
+            }
+            Action::PrevSort => {
+                self.sort_key = self.sort_key.prev();
+                self.list_state = 0;
+            }
+            Action::ReverseSort => {
+                self.sort_descending = !self.sort_descending;
+                self.list_state = 0;
+            }
+            Action::Select => {
                 let filtered = self.filtered_list();
                 if let Some(p) = filtered.get(self.list_state) {
                     let id = p.id;
@@ -564,16 +1916,24 @@ impl App {
                     self.screen = Screen::PokemonDetail;
                 }
             }
-            _ => {}
+            _ => {
+                let len = self.filtered_list().len();
+                self.list_state = step_selection(self.list_state, len, action);
+            }
         }
     }
 
-    fn handle_detail_key(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Esc => {
+    fn detail_action(&mut self, action: Action) {
+        match action {
+            // Back to wherever the Pokémon was opened from
+            Action::Back if self.history.can_go_back() => self.step_history(true),
+            Action::Back => {
                 self.screen = Screen::PokemonList;
             }
-            KeyCode::Char('a') => {
+            Action::AddToTeam => {
+                if !self.refresh_teams() {
+                    return;
+                }
                 if let Some(ref detail) = self.detail {
                     if self.current_team().members.len() < 6 {
                         let member = TeamMember {
@@ -587,28 +1947,62 @@ impl App {
                             moves: Vec::new(),
                         };
                         self.current_team_mut().members.push(member);
-                        self.team_data.save();
+                        self.save_teams();
                     }
                 }
             }
-            KeyCode::Left => {
+            Action::CycleBlocks => self.sprite_mode = self.sprite_mode.next(),
+            Action::ToggleFavorite => {
+                if let Some(id) = self.detail.as_ref().map(|d| d.id) {
+                    self.toggle_favorite(id);
+                }
+            }
+            Action::CycleCollected => {
+                if let Some(id) = self.detail.as_ref().map(|d| d.id) {
+                    self.cycle_collected(id);
+                }
+            }
+            Action::EditTags if self.detail.is_some() => {
+                self.open_typing_modal(Modal::EditTags);
+            }
+            Action::EditNote => {
+                if let Some(id) = self.detail.as_ref().map(|d| d.id) {
+                    self.open_typing_modal(Modal::EditNote);
+                    self.modal_search = self
+                        .notes
+                        .get(id)
+                        .map(|n| n.note.clone())
+                        .unwrap_or_default();
+                }
+            }
+            Action::ExportCard => self.export_detail_card(),
+            Action::Export => self.export_entries(),
+            Action::CycleExportFormat => self.cycle_export_format(),
+            Action::PauseAnimation => self.animation_paused = !self.animation_paused,
+            Action::NextSprite => self.cycle_sprite_variant(true),
+            Action::PrevSprite => self.cycle_sprite_variant(false),
+            Action::NextForm => self.switch_form(true),
+            Action::PrevForm => self.switch_form(false),
+            Action::Left => {
                 // Navigate to previous Pokemon in filtered list
                 if let Some(current_index) = self.detail_list_index {
                     if current_index > 0 {
                         let filtered = self.filtered_list();
                         if let Some(prev_pokemon) = filtered.get(current_index - 1) {
                             self.load_detail(prev_pokemon.id);
+                            self.history.replace(self.location());
                         }
                     }
                 }
             }
-            KeyCode::Right => {
+            Action::Right => {
                 // Navigate to next Pokemon in filtered list
                 if let Some(current_index) = self.detail_list_index {
                     let filtered = self.filtered_list();
                     if current_index < filtered.len().saturating_sub(1) {
                         if let Some(next_pokemon) = filtered.get(current_index + 1) {
                             self.load_detail(next_pokemon.id);
+                            self.history.replace(self.location());
                         }
                     }
                 }
@@ -617,41 +2011,94 @@ impl App {
         }
     }
 
-    fn handle_type_chart_key(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
+    /// Cycle the detail screen through the forms of the current species
+    fn switch_form(&mut self, forward: bool) {
+        let (Some(species), Some(current)) = (&self.detail_species, self.detail_pokemon_id) else {
+            return;
+        };
+        let ids: Vec<u32> = species
+            .varieties
+            .iter()
+            .filter_map(|v| v.pokemon.id())
+            .collect();
+        if ids.len() < 2 {
+            return;
+        }
+        let pos = ids.iter().position(|&id| id == current).unwrap_or(0);
+        let next = if forward {
+            ids[(pos + 1) % ids.len()]
+        } else {
+            ids[(pos + ids.len() - 1) % ids.len()]
+        };
+        self.show_form(next);
+    }
+
+    fn show_form(&mut self, id: u32) {
+        // Forms are usually hidden from the list, so stay anchored to the base's row
+        let list_index = self.detail_list_index;
+        self.load_detail(id);
+        if self.detail_list_index.is_none() {
+            self.detail_list_index = list_index;
+        }
+        self.history.replace(self.location());
+    }
+
+    /// Show an ability's effect under the abilities line, fetching it the first time
+    fn select_ability(&mut self, slot: usize) {
+        if self.selected_ability == Some(slot) {
+            self.selected_ability = None;
+            return;
+        }
+        let Some(name) = self
+            .detail
+            .as_ref()
+            .and_then(|d| d.abilities.get(slot))
+            .map(|a| a.ability.name.clone())
+        else {
+            return;
+        };
+        self.selected_ability = Some(slot);
+        if self.ability_effects.contains_key(&name)
+            || !self.abilities_requested.insert(name.clone())
+        {
+            return;
+        }
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let client = ApiClient::new();
+            let effect = client
+                .fetch_ability(&name)
+                .await
+                .ok()
+                .map(|a| a.short_effect().unwrap_or("No description.").to_string());
+            let _ = tx.send(AppEvent::AbilityLoaded(name, effect));
+        });
+    }
+
+    fn type_chart_action(&mut self, action: Action) {
+        match action {
+            Action::Up => {
                 self.type_chart_scroll_y = self.type_chart_scroll_y.saturating_sub(1);
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.type_chart_scroll_y < 17 {
-                    self.type_chart_scroll_y += 1;
-                }
+            Action::Down if self.type_chart_scroll_y < 17 => {
+                self.type_chart_scroll_y += 1;
             }
-            KeyCode::Left | KeyCode::Char('h') => {
+            Action::Left => {
                 self.type_chart_scroll_x = self.type_chart_scroll_x.saturating_sub(1);
             }
-            KeyCode::Right | KeyCode::Char('l') => {
-                if self.type_chart_scroll_x < 17 {
-                    self.type_chart_scroll_x += 1;
-                }
+            Action::Right if self.type_chart_scroll_x < 17 => {
+                self.type_chart_scroll_x += 1;
             }
             _ => {}
         }
     }
 
-    fn handle_team_key(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.team_slot_selected > 0 {
-                    self.team_slot_selected -= 1;
-                }
-            }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.team_slot_selected < 5 {
-                    self.team_slot_selected += 1;
-                }
+    fn team_action(&mut self, action: Action) {
+        match action {
+            Action::Up | Action::Down => {
+                self.team_slot_selected = step_selection(self.team_slot_selected, 6, action);
             }
-            KeyCode::Enter => {
+            Action::Select => {
                 let slot = self.team_slot_selected;
                 let member_count = self.team_data.teams[self.current_team].members.len();
                 if slot < member_count {
@@ -678,72 +2125,566 @@ impl App {
                     self.start_loading_list();
                 }
             }
-            KeyCode::Char('d') | KeyCode::Delete => {
+            Action::RemoveMember => {
+                if !self.refresh_teams() {
+                    return;
+                }
                 let slot = self.team_slot_selected;
                 let team = &mut self.team_data.teams[self.current_team];
                 if slot < team.members.len() {
                     team.members.remove(slot);
-                    self.team_data.save();
+                    self.save_teams();
                 }
             }
-            KeyCode::Char('n') => {
-                // New team
+            Action::ToggleRowSize => self.expanded_rows = !self.expanded_rows,
+            Action::ExportCard => self.export_team_card(),
+            Action::NewTeam => {
+                if !self.refresh_teams() {
+                    return;
+                }
                 self.team_data.teams.push(Team {
                     name: format!("Team {}", self.team_data.teams.len() + 1),
                     members: Vec::new(),
                 });
                 self.current_team = self.team_data.teams.len() - 1;
                 self.team_slot_selected = 0;
-                self.team_data.save();
+                self.save_teams();
             }
-            KeyCode::Left | KeyCode::Char('h') => {
-                if self.current_team > 0 {
-                    self.current_team -= 1;
-                    self.team_slot_selected = 0;
-                }
+            Action::Left if self.current_team > 0 => {
+                self.current_team -= 1;
+                self.team_slot_selected = 0;
             }
-            KeyCode::Right | KeyCode::Char('l') => {
-                if self.current_team < self.team_data.teams.len() - 1 {
-                    self.current_team += 1;
-                    self.team_slot_selected = 0;
-                }
+            Action::Right if self.current_team < self.team_data.teams.len() - 1 => {
+                self.current_team += 1;
+                self.team_slot_selected = 0;
             }
             _ => {}
         }
     }
 
     fn handle_modal_key(&mut self, key: KeyEvent, modal: Modal) {
-        if key.code == KeyCode::Esc {
-            self.modal = None;
+        if modal == Modal::EditNote && self.handle_note_input(key) {
             return;
         }
+        let typing = self.search_mode || modal.is_typing();
+        if typing && self.handle_search_input(key) {
+            return;
+        }
+        if let Some(action) = self.keymap.action(Context::Modal, &key) {
+            self.modal_action(action, modal);
+        }
+    }
+
+    fn modal_action(&mut self, action: Action, modal: Modal) {
+        match action {
+            Action::Back if modal == Modal::EditNote => {
+                // Stay in the editor if the note couldn't be kept, so the text isn't lost
+                if self.save_note() {
+                    self.modal = None;
+                }
+                return;
+            }
+            Action::Back => {
+                self.modal = None;
+                self.search_mode = false;
+                return;
+            }
+            Action::Help => {
+                self.open_help();
+                return;
+            }
+            _ => {}
+        }
 
         match modal {
-            Modal::PokemonPicker => self.handle_pokemon_picker_key(key),
-            Modal::MovePicker => self.handle_move_picker_key(key),
+            Modal::PokemonPicker => self.pokemon_picker_action(action),
+            Modal::MovePicker => self.move_picker_action(action),
+            Modal::TypeFilter => self.type_filter_action(action),
+            Modal::CommandPalette => self.palette_action(action),
+            Modal::TagFilter | Modal::EditTags => self.tag_action(action, modal),
+            Modal::MarkRange if action == Action::Select => self.mark_range(),
+            Modal::Recent => self.recent_action(action),
+            Modal::EditNote | Modal::MarkRange | Modal::Progress => {}
+        }
+    }
+
+    fn recent_action(&mut self, action: Action) {
+        let recent = self.history.recent();
+        if action != Action::Select {
+            self.modal_selected = step_selection(self.modal_selected, recent.len(), action);
+            return;
+        }
+        let Some(&id) = recent.get(self.modal_selected) else {
+            return;
+        };
+        self.modal = None;
+        self.load_detail(id);
+        self.screen = Screen::PokemonDetail;
+    }
+
+    /// Recently viewed Pokémon as (id, name); names come from the list, so
+    /// they're empty until it loads
+    pub fn recent_rows(&self) -> Vec<(u32, &str)> {
+        self.history
+            .recent()
+            .iter()
+            .map(|&id| {
+                let name = self.pokemon_list.iter().find(|p| p.id == id);
+                (id, name.map_or("", |p| p.name.as_str()))
+            })
+            .collect()
+    }
+
+    /// Where the app is, for the history
+    fn location(&self) -> Location {
+        Location {
+            screen: self.screen,
+            pokemon_id: self.detail_pokemon_id,
+            list_state: self.list_state,
+            list_pokemon: self.filtered_list().get(self.list_state).map(|p| p.id),
+            current_team: self.current_team,
+            team_slot: self.team_slot_selected,
+            chart_scroll: (self.type_chart_scroll_x, self.type_chart_scroll_y),
+        }
+    }
+
+    /// Go back or forward a place, keeping the selection being left for a return
+    fn step_history(&mut self, back: bool) {
+        self.history.visit(self.location());
+        let location = if back {
+            self.history.back()
+        } else {
+            self.history.forward()
+        };
+        let Some(location) = location else {
+            self.status_message = Some(
+                if back {
+                    "Nothing to go back to"
+                } else {
+                    "Nothing to go forward to"
+                }
+                .to_string(),
+            );
+            return;
+        };
+        if location.screen == Screen::PokemonDetail {
+            if let Some(id) = location.pokemon_id {
+                self.load_detail(id);
+            }
+        }
+        // The list may have changed since; find the same Pokémon if it's still there
+        let filtered = self.filtered_list();
+        let row = location
+            .list_pokemon
+            .and_then(|id| filtered.iter().position(|p| p.id == id));
+        self.list_state = row.unwrap_or(location.list_state.min(filtered.len().saturating_sub(1)));
+        self.current_team = location
+            .current_team
+            .min(self.team_data.teams.len().saturating_sub(1));
+        self.team_slot_selected = location.team_slot;
+        (self.type_chart_scroll_x, self.type_chart_scroll_y) = location.chart_scroll;
+        self.search_mode = false;
+        self.screen = location.screen;
+        self.on_screen_enter();
+    }
+
+    fn cycle_collected(&mut self, id: u32) {
+        if !self.refresh_collection() {
+            return;
+        }
+        let status = self.collection.cycle(id);
+        let name = self
+            .pokemon_list
+            .iter()
+            .find(|p| p.id == id)
+            .map(|p| p.name.replace('-', " "))
+            .unwrap_or_else(|| format!("#{}", id));
+        let game = game_label(&self.collection.game);
+        self.status_message = Some(match status {
+            Some(status) => format!("Marked {} as {} in {}", name, status.label(), game),
+            None => format!("Unmarked {} in {}", name, game),
+        });
+        self.save_collection();
+    }
+
+    /// Apply the typed bulk mark, e.g. "1-151 caught", to species in the range
+    fn mark_range(&mut self) {
+        let (range, status) = match collection::parse_mark(&self.modal_search) {
+            Ok(mark) => mark,
+            Err(e) => {
+                self.status_message = Some(format!("Can't mark: {}", e));
+                return;
+            }
+        };
+        let ids: Vec<u32> = self
+            .pokemon_list
+            .iter()
+            .filter(|p| !p.is_form() && range.contains(&p.id))
+            .map(|p| p.id)
+            .collect();
+        if !self.refresh_collection() {
+            return;
+        }
+        let count = self.collection.mark(ids, status);
+        let game = game_label(&self.collection.game);
+        self.status_message = Some(match status {
+            Some(status) => format!("Marked {} as {} in {}", count, status.label(), game),
+            None => format!("Unmarked {} in {}", count, game),
+        });
+        self.modal = None;
+        self.save_collection();
+    }
+
+    /// Seen, caught and shiny counts per generation in the current game,
+    /// counting species and leaving out forms
+    pub fn collection_progress(&self) -> Vec<Progress> {
+        self.collection.progress(
+            self.pokemon_list
+                .iter()
+                .filter(|p| !p.is_form())
+                .filter_map(|p| Some((p.id, summary_generation(p)?))),
+        )
+    }
+
+    /// Use the collection in `path` from now on, loaded and saved there
+    pub fn set_collection(&mut self, collection: Collection, path: PathBuf) {
+        self.collection = collection;
+        self.collection_modified = modified(&path);
+        self.collection_file = Some(path);
+    }
+
+    /// Pick up changes made to collection.json since it was last loaded or
+    /// saved, such as by `pokedex collection import`. Returns false if it
+    /// couldn't be read, in which case the mark must be skipped.
+    fn refresh_collection(&mut self) -> bool {
+        let Some(path) = &self.collection_file else {
+            return true;
+        };
+        let now = modified(path);
+        if now == self.collection_modified && !self.collection.read_only {
+            return true;
+        }
+        match Collection::read_from(path) {
+            Ok(collection) => {
+                self.collection = collection;
+                self.collection_modified = now;
+                true
+            }
+            Err(e) => {
+                self.error_message = Some(format!("{:#}", e));
+                false
+            }
+        }
+    }
+
+    fn save_collection(&mut self) {
+        let Some(path) = &self.collection_file else {
+            return;
+        };
+        match self.collection.save_to(path) {
+            Ok(()) => self.collection_modified = modified(path),
+            Err(e) => self.error_message = Some(format!("Couldn't save the collection: {}", e)),
+        }
+    }
+
+    fn open_typing_modal(&mut self, modal: Modal) {
+        self.modal = Some(modal);
+        self.modal_selected = 0;
+        self.modal_search.clear();
+    }
+
+    fn toggle_favorite(&mut self, id: u32) {
+        if !self.refresh_notes() {
+            return;
+        }
+        let favorite = self.notes.toggle_favorite(id);
+        self.status_message = Some(
+            if favorite {
+                "Added to favorites"
+            } else {
+                "Removed from favorites"
+            }
+            .to_string(),
+        );
+        self.save_notes();
+    }
+
+    /// Rows of the tag modals as (tag, Pokémon with it, checked). Editing
+    /// offers the typed text as a new tag first; filtering lists tags in use.
+    pub fn tag_rows(&self, modal: Modal) -> Vec<(String, usize, bool)> {
+        let typed = normalize_tag(&self.modal_search);
+        let id = self.detail.as_ref().map(|d| d.id);
+        let all = self.notes.all_tags();
+        let mut rows = Vec::new();
+        if modal == Modal::EditTags && !typed.is_empty() && !all.iter().any(|(t, _)| *t == typed) {
+            rows.push((typed.clone(), 0, false));
+        }
+        rows.extend(
+            all.into_iter()
+                .filter(|(tag, _)| tag.contains(&typed))
+                .map(|(tag, count)| {
+                    let checked = match modal {
+                        Modal::EditTags => id.is_some_and(|id| self.notes.tags(id).contains(&tag)),
+                        _ => self.tag_filter.contains(&tag),
+                    };
+                    (tag, count, checked)
+                }),
+        );
+        rows
+    }
+
+    fn tag_action(&mut self, action: Action, modal: Modal) {
+        let rows = self.tag_rows(modal);
+        if action != Action::Select {
+            self.modal_selected = step_selection(self.modal_selected, rows.len(), action);
+            return;
+        }
+        let Some((tag, _, _)) = rows.into_iter().nth(self.modal_selected) else {
+            return;
+        };
+        if modal == Modal::TagFilter {
+            match self.tag_filter.iter().position(|t| *t == tag) {
+                Some(pos) => {
+                    self.tag_filter.remove(pos);
+                }
+                None => self.tag_filter.push(tag),
+            }
+            self.list_state = 0;
+        } else if let Some(id) = self.detail.as_ref().map(|d| d.id) {
+            if !self.refresh_notes() {
+                return;
+            }
+            self.notes.toggle_tag(id, &tag);
+            // A new tag moves from the top row into the list
+            self.modal_search.clear();
+            self.modal_selected = 0;
+            self.save_notes();
         }
     }
 
-    fn handle_pokemon_picker_key(&mut self, key: KeyEvent) {
+    /// The note editor takes any text; Enter starts a new line
+    fn handle_note_input(&mut self, key: KeyEvent) -> bool {
+        if Key::from_event(&key).is_modified() {
+            return false;
+        }
         match key.code {
-            KeyCode::Up | KeyCode::Char('k') if !self.search_mode => {
-                if self.modal_selected > 0 {
-                    self.modal_selected -= 1;
+            KeyCode::Enter => self.modal_search.push('\n'),
+            KeyCode::Backspace => {
+                self.modal_search.pop();
+            }
+            KeyCode::Char(c) => self.modal_search.push(c),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Keep the typed note; false if notes.json changed and couldn't be reloaded
+    fn save_note(&mut self) -> bool {
+        if let Some(id) = self.detail.as_ref().map(|d| d.id) {
+            if !self.refresh_notes() {
+                return false;
+            }
+            self.notes.set_note(id, &self.modal_search);
+            self.save_notes();
+        }
+        true
+    }
+
+    /// Use the notes in `path` from now on, loaded and saved there
+    pub fn set_notes(&mut self, notes: Notes, path: PathBuf) {
+        self.notes = notes;
+        self.notes_modified = modified(&path);
+        self.notes_file = Some(path);
+    }
+
+    /// Pick up changes made to notes.json since it was last loaded or saved,
+    /// such as by another running copy. Returns false if it couldn't be
+    /// read, in which case the edit must be skipped.
+    fn refresh_notes(&mut self) -> bool {
+        let Some(path) = &self.notes_file else {
+            return true;
+        };
+        let now = modified(path);
+        if now == self.notes_modified && !self.notes.read_only {
+            return true;
+        }
+        match Notes::read_from(path) {
+            Ok(notes) => {
+                self.notes = notes;
+                self.notes_modified = now;
+                true
+            }
+            Err(e) => {
+                self.error_message = Some(format!("{:#}", e));
+                false
+            }
+        }
+    }
+
+    fn save_notes(&mut self) {
+        let Some(path) = &self.notes_file else {
+            return;
+        };
+        match self.notes.save_to(path) {
+            Ok(()) => self.notes_modified = modified(path),
+            Err(e) => self.error_message = Some(format!("Couldn't save notes: {}", e)),
+        }
+    }
+
+    fn open_help(&mut self) {
+        self.show_help = true;
+        self.help_scroll = 0;
+    }
+
+    /// Sections of (keys, description) rows for what's on screen: the open
+    /// modal's keys, or the screen's followed by the global ones
+    pub fn help_sections(&self) -> Vec<(String, Vec<(String, String)>)> {
+        if let Some(modal) = self.modal {
+            let rows = self.keymap.help_rows(Context::Modal, Some(modal.actions()));
+            return vec![(modal.label().to_string(), rows)];
+        }
+        vec![
+            (
+                self.screen.label().to_string(),
+                self.keymap.help_rows(self.screen_context(), None),
+            ),
+            (
+                "Global".to_string(),
+                self.keymap.help_rows(Context::Global, None),
+            ),
+        ]
+    }
+
+    /// Movement keys scroll the help; anything else closes it
+    fn handle_help_key(&mut self, key: KeyEvent) {
+        match self.keymap.action(Context::Modal, &key) {
+            Some(
+                action @ (Action::Up
+                | Action::Down
+                | Action::PageUp
+                | Action::PageDown
+                | Action::Top
+                | Action::Bottom),
+            ) => self.scroll_help(action),
+            _ => self.show_help = false,
+        }
+    }
+
+    fn scroll_help(&mut self, action: Action) {
+        // A title and a blank line per section
+        let lines: usize = self
+            .help_sections()
+            .iter()
+            .map(|(_, rows)| rows.len() + 2)
+            .sum();
+        self.help_scroll = step_selection(self.help_scroll, lines, action);
+    }
+
+    pub fn palette_results(&self) -> Vec<PaletteEntry> {
+        palette::search(self, &self.modal_search)
+    }
+
+    fn palette_action(&mut self, action: Action) {
+        let results = self.palette_results();
+        if action != Action::Select {
+            self.modal_selected = step_selection(self.modal_selected, results.len(), action);
+            return;
+        }
+        let Some(entry) = results.into_iter().nth(self.modal_selected) else {
+            return;
+        };
+        self.modal = None;
+        self.search_mode = false;
+        self.run_command(entry.command);
+    }
+
+    fn run_command(&mut self, command: Command) {
+        match command {
+            Command::Run(action) => {
+                if !self.global_action(action) {
+                    self.screen_action(action);
                 }
             }
-            KeyCode::Down | KeyCode::Char('j') if !self.search_mode => {
-                let max = self.modal_filtered_list().len().saturating_sub(1);
-                if self.modal_selected < max {
-                    self.modal_selected += 1;
+            Command::ShowPokemon(id) => {
+                self.load_detail(id);
+                self.screen = Screen::PokemonDetail;
+            }
+            Command::SwitchTeam(index) => {
+                self.current_team = index;
+                self.team_slot_selected = 0;
+                self.screen = Screen::TeamBuilder;
+            }
+            Command::SetKeymap(preset) => {
+                self.keymap = Keymap::preset(preset);
+                self.settings.keymap = Some(preset.label().to_string());
+                self.status_message = Some(format!("Keymap: {}", preset.label()));
+            }
+            Command::SetTheme(name) => self.set_theme(name),
+            Command::SetGame(game) => {
+                if !self.refresh_collection() {
+                    return;
                 }
+                self.collection.game = game.to_string();
+                self.status_message = Some(format!("Collection: {}", game_label(game)));
+                self.save_collection();
+            }
+            Command::ToggleAccessible => {
+                self.accessible = !self.accessible;
+                self.status_message = Some(format!(
+                    "Accessibility mode {}",
+                    if self.accessible { "on" } else { "off" }
+                ));
+            }
+            Command::ToggleAnimation => {
+                self.animate_sprites = !self.animate_sprites;
+                self.load_animation();
+                self.status_message = Some(format!(
+                    "Sprite animation {}",
+                    if self.animate_sprites { "on" } else { "off" }
+                ));
             }
-            KeyCode::Char('/') if !self.search_mode => {
+        }
+    }
+
+    fn set_theme(&mut self, name: ThemeName) {
+        self.theme = Theme::named(name);
+        self.settings.theme = Some(name.name().to_string());
+        self.status_message = Some(format!("Theme: {}", name.label()));
+    }
+
+    fn type_filter_action(&mut self, action: Action) {
+        match action {
+            Action::ToggleType | Action::Select => {
+                self.toggle_type_filter(ALL_TYPES[self.modal_selected]);
+            }
+            Action::MatchMode => {
+                self.type_match = match self.type_match {
+                    TypeMatch::Any => TypeMatch::Exact,
+                    TypeMatch::Exact => TypeMatch::Any,
+                };
+                self.list_state = 0;
+            }
+            Action::ClearTypes => {
+                self.type_filter.clear();
+                self.list_state = 0;
+            }
+            Action::TypeFilter => {
+                self.modal = None;
+            }
+            _ => {
+                self.modal_selected = step_selection(self.modal_selected, ALL_TYPES.len(), action);
+            }
+        }
+    }
+
+    fn pokemon_picker_action(&mut self, action: Action) {
+        match action {
+            Action::Search => {
                 self.search_mode = true;
                 self.modal_search.clear();
             }
-            KeyCode::Enter => {
-                if self.search_mode {
-                    self.search_mode = false;
+            Action::Select => {
+                if !self.refresh_teams() {
                     return;
                 }
                 let filtered = self.modal_filtered_list();
@@ -756,56 +2697,37 @@ impl App {
                             moves: Vec::new(),
                         };
                         self.current_team_mut().members.push(member);
-                        self.team_data.save();
+                        self.save_teams();
                         self.modal = None;
                     }
                 }
             }
-            KeyCode::Backspace if self.search_mode => {
-                self.modal_search.pop();
-                self.modal_selected = 0;
-            }
-            KeyCode::Char(c) if self.search_mode => {
-                self.modal_search.push(c);
-                self.modal_selected = 0;
+            _ => {
+                let len = self.modal_filtered_list().len();
+                self.modal_selected = step_selection(self.modal_selected, len, action);
             }
-            _ => {}
         }
     }
 
     pub fn modal_filtered_list(&self) -> Vec<&PokemonSummary> {
-        let mut filtered: Vec<&PokemonSummary> = self.pokemon_list.iter().collect();
-
-        // Apply generation filter
-        if let Some(gen) = self.generation_filter {
-            filtered.retain(|p| pokemon_generation(p.id) == gen);
-        }
-
-        // Apply search query filter
-        if !self.modal_search.is_empty() {
-            let q = self.modal_search.to_lowercase();
-            filtered.retain(|p| p.name.contains(&q) || p.id.to_string().contains(&q));
-        }
+        let filtered: Vec<&PokemonSummary> = self
+            .pokemon_list
+            .iter()
+            .filter(|p| self.passes_picker_filters(p))
+            .collect();
 
-        filtered
+        // Apply search query filter, ranked by match quality
+        rank_by_query(filtered, &self.modal_search)
     }
 
-    fn handle_move_picker_key(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.modal_selected > 0 {
-                    self.modal_selected -= 1;
-                }
-            }
-            KeyCode::Down | KeyCode::Char('j') => {
-                let max = self.available_moves.len().saturating_sub(1);
-                if self.modal_selected < max {
-                    self.modal_selected += 1;
-                }
-            }
-            KeyCode::Enter => {
+    fn move_picker_action(&mut self, action: Action) {
+        match action {
+            Action::Select => {
                 let slot = self.team_slot_selected;
                 let selected = self.modal_selected;
+                if !self.refresh_teams() {
+                    return;
+                }
                 if let Some(mv) = self.available_moves.get(selected) {
                     let new_move = TeamMove {
                         name: mv.name.clone(),
@@ -818,7 +2740,7 @@ impl App {
                         if member.moves.len() < 4 {
                             member.moves.push(new_move);
                             let full = member.moves.len() >= 4;
-                            self.team_data.save();
+                            self.save_teams();
                             if full {
                                 self.modal = None;
                             }
@@ -826,7 +2748,10 @@ impl App {
                     }
                 }
             }
-            _ => {}
+            _ => {
+                let len = self.available_moves.len();
+                self.modal_selected = step_selection(self.modal_selected, len, action);
+            }
         }
     }
 }
@@ -834,6 +2759,8 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::collection::Status;
+    use crate::settings::MIN_PANE;
     use tokio::sync::mpsc;
 
     #[test]
@@ -944,9 +2871,15 @@ mod tests {
         assert!(!app.search_mode);
         assert!(app.search_query.is_empty());
         assert_eq!(app.generation_filter, None);
+        assert!(app.type_filter.is_empty());
+        assert_eq!(app.type_match, TypeMatch::Any);
+        assert_eq!(app.sort_key, SortKey::Id);
+        assert!(!app.sort_descending);
+        assert_eq!(app.summaries_loading, LoadingState::Idle);
         assert!(app.detail.is_none());
         assert_eq!(app.detail_loading, LoadingState::Idle);
         assert!(app.sprite_bytes.is_none());
+        assert_eq!(app.sprite_variant, SpriteVariant::FrontDefault);
         assert_eq!(app.detail_pokemon_id, None);
         assert_eq!(app.detail_list_index, None);
         assert!(app.type_infos.is_empty());
@@ -973,16 +2906,19 @@ mod tests {
                 id: 1,
                 name: "bulbasaur".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 25,
                 name: "pikachu".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 152,
                 name: "chikorita".to_string(),
                 types: vec![],
+                ..Default::default()
             },
         ];
 
@@ -1000,16 +2936,19 @@ mod tests {
                 id: 1,
                 name: "bulbasaur".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 25,
                 name: "pikachu".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 152,
                 name: "chikorita".to_string(),
                 types: vec![],
+                ..Default::default()
             },
         ];
 
@@ -1029,11 +2968,13 @@ mod tests {
                 id: 1,
                 name: "bulbasaur".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 25,
                 name: "pikachu".to_string(),
                 types: vec![],
+                ..Default::default()
             },
         ];
 
@@ -1053,16 +2994,19 @@ mod tests {
                 id: 1,
                 name: "bulbasaur".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 25,
                 name: "pikachu".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 152,
                 name: "chikorita".to_string(),
                 types: vec![],
+                ..Default::default()
             },
         ];
 
@@ -1082,16 +3026,19 @@ mod tests {
                 id: 1,
                 name: "bulbasaur".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 25,
                 name: "pikachu".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 152,
                 name: "chikorita".to_string(),
                 types: vec![],
+                ..Default::default()
             },
         ];
 
@@ -1103,39 +3050,1560 @@ mod tests {
     }
 
     #[test]
-    fn test_app_current_team() {
+    fn test_app_filtered_list_fuzzy_ranking() {
         let (tx, _rx) = mpsc::unbounded_channel();
-        // Use test helper to avoid reading from real cache file
-        let team_data = TeamData::default();
-        let app = App::new_with_team_data(tx, team_data);
+        let mut app = App::new(tx);
 
-        let team = app.current_team();
-        assert_eq!(team.name, "Team 1");
-        assert!(team.members.is_empty());
+        app.pokemon_list = vec![
+            PokemonSummary {
+                id: 4,
+                name: "charmander".to_string(),
+                types: vec![],
+                ..Default::default()
+            },
+            PokemonSummary {
+                id: 6,
+                name: "charizard".to_string(),
+                types: vec![],
+                ..Default::default()
+            },
+            PokemonSummary {
+                id: 122,
+                name: "mr-mime".to_string(),
+                types: vec![],
+                ..Default::default()
+            },
+        ];
+
+        app.search_query = "charzard".to_string();
+        let filtered = app.filtered_list();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "charizard");
+
+        app.search_query = "mr mime".to_string();
+        let filtered = app.filtered_list();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, 122);
+
+        // Typos still find something when nothing matches directly
+        app.search_query = "charmandr".to_string();
+        assert_eq!(app.filtered_list()[0].name, "charmander");
+        app.search_query = "charmamder".to_string();
+        assert_eq!(app.filtered_list()[0].name, "charmander");
+
+        // Better matches come first regardless of list order
+        app.search_query = "chard".to_string();
+        let filtered = app.filtered_list();
+        assert_eq!(filtered[0].name, "charizard");
     }
 
     #[test]
-    fn test_app_modal_filtered_list() {
-        let (tx, _rx) = mpsc::unbounded_channel();
-        let mut app = App::new(tx);
+    fn test_sort_key_cycle() {
+        assert_eq!(SortKey::Id.next(), SortKey::Name);
+        assert_eq!(SortKey::Type.next(), SortKey::Id);
+        assert_eq!(SortKey::Id.prev(), SortKey::Type);
+        for key in SortKey::all() {
+            assert_eq!(key.next().prev(), *key);
+        }
+        assert_eq!(SortKey::Speed.stat_index(), Some(5));
+        assert_eq!(SortKey::Total.stat_index(), None);
+    }
 
-        app.pokemon_list = vec![
+    fn sortable_list() -> Vec<PokemonSummary> {
+        vec![
             PokemonSummary {
                 id: 1,
                 name: "bulbasaur".to_string(),
-                types: vec![],
+                types: vec!["grass".to_string(), "poison".to_string()],
+                stats: vec![45, 49, 49, 65, 65, 45],
+                height: Some(7),
+                weight: Some(69),
+                species_id: None,
+                generation: None,
+            },
+            PokemonSummary {
+                id: 4,
+                name: "charmander".to_string(),
+                types: vec!["fire".to_string()],
+                stats: vec![39, 52, 43, 60, 50, 65],
+                height: Some(6),
+                weight: Some(85),
+                species_id: None,
+                generation: None,
             },
             PokemonSummary {
                 id: 25,
                 name: "pikachu".to_string(),
                 types: vec![],
+                ..Default::default()
             },
-        ];
+            PokemonSummary {
+                id: 7,
+                name: "squirtle".to_string(),
+                types: vec!["water".to_string()],
+                stats: vec![44, 48, 65, 50, 64, 43],
+                height: Some(5),
+                weight: Some(90),
+                species_id: None,
+                generation: None,
+            },
+        ]
+    }
 
-        app.modal_search = "pika".to_string();
-        let filtered = app.modal_filtered_list();
-        assert_eq!(filtered.len(), 1);
-        assert_eq!(filtered[0].name, "pikachu");
+    #[test]
+    fn test_app_filtered_list_sorting() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = sortable_list();
+
+        let ids = |app: &App| app.filtered_list().iter().map(|p| p.id).collect::<Vec<_>>();
+
+        // Default is dex order
+        assert_eq!(ids(&app), vec![1, 4, 7, 25]);
+
+        app.sort_descending = true;
+        assert_eq!(ids(&app), vec![25, 7, 4, 1]);
+
+        app.sort_key = SortKey::Speed;
+        // Not-yet-loaded stats go last in either direction
+        assert_eq!(ids(&app), vec![4, 1, 7, 25]);
+        app.sort_descending = false;
+        assert_eq!(ids(&app), vec![7, 1, 4, 25]);
+
+        app.sort_key = SortKey::Name;
+        app.sort_descending = true;
+        assert_eq!(ids(&app), vec![7, 25, 4, 1]);
+
+        app.sort_key = SortKey::Total;
+        assert_eq!(ids(&app), vec![1, 7, 4, 25]);
+
+        app.sort_key = SortKey::Weight;
+        app.sort_descending = false;
+        assert_eq!(ids(&app), vec![1, 4, 7, 25]);
+
+        app.sort_key = SortKey::Type;
+        assert_eq!(ids(&app), vec![4, 1, 7, 25]);
+    }
+
+    #[test]
+    fn test_sort_keys_cycle_from_list() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = sortable_list();
+        app.list_state = 2;
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::empty()));
+        assert_eq!(app.sort_key, SortKey::Name);
+        assert_eq!(app.list_state, 0);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::empty()));
+        assert!(app.sort_descending);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('S'), KeyModifiers::empty()));
+        assert_eq!(app.sort_key, SortKey::Id);
+        assert!(app.is_custom_sort());
+    }
+
+    #[test]
+    fn test_search_keeps_relevance_with_default_sort() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = sortable_list();
+
+        app.search_query = "char".to_string();
+        let filtered = app.filtered_list();
+        assert_eq!(filtered[0].name, "charmander");
+
+        app.search_query = "a".to_string();
+        app.sort_key = SortKey::Hp;
+        app.sort_descending = true;
+        let filtered = app.filtered_list();
+        assert_eq!(filtered[0].name, "bulbasaur");
+    }
+
+    fn typed_list() -> Vec<PokemonSummary> {
+        let mon = |id: u32, name: &str, types: &[&str]| PokemonSummary {
+            id,
+            name: name.to_string(),
+            types: types.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        };
+        vec![
+            mon(6, "charizard", &["fire", "flying"]),
+            mon(4, "charmander", &["fire"]),
+            mon(16, "pidgey", &["normal", "flying"]),
+            mon(146, "moltres", &["fire", "flying"]),
+            mon(155, "cyndaquil", &["fire"]),
+            mon(25, "pikachu", &[]),
+        ]
+    }
+
+    #[test]
+    fn test_type_filter_single_type() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = typed_list();
+
+        app.toggle_type_filter("fire");
+        let ids: Vec<u32> = app.filtered_list().iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![4, 6, 146, 155]);
+
+        // Exact with one type means mono-type
+        app.type_match = TypeMatch::Exact;
+        let ids: Vec<u32> = app.filtered_list().iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![4, 155]);
+    }
+
+    #[test]
+    fn test_type_filter_dual_type() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = typed_list();
+
+        app.toggle_type_filter("flying");
+        app.toggle_type_filter("fire");
+        assert_eq!(app.filtered_list().len(), 5);
+
+        app.type_match = TypeMatch::Exact;
+        let ids: Vec<u32> = app.filtered_list().iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![6, 146]);
+
+        // Combines with the generation and search filters
+        app.generation_filter = Some(1);
+        app.search_query = "molt".to_string();
+        let ids: Vec<u32> = app.filtered_list().iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![146]);
+    }
+
+    #[test]
+    fn test_toggle_type_filter_keeps_two() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+
+        app.toggle_type_filter("fire");
+        app.toggle_type_filter("water");
+        app.toggle_type_filter("grass");
+        assert_eq!(
+            app.type_filter,
+            vec!["water".to_string(), "grass".to_string()]
+        );
+
+        app.toggle_type_filter("water");
+        assert_eq!(app.type_filter, vec!["grass".to_string()]);
+    }
+
+    #[test]
+    fn test_type_filter_modal_keys() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = typed_list();
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::empty()));
+        assert_eq!(app.modal, Some(Modal::TypeFilter));
+
+        // normal -> fire
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::empty()));
+        assert_eq!(app.type_filter, vec!["fire".to_string()]);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('m'), KeyModifiers::empty()));
+        assert_eq!(app.type_match, TypeMatch::Exact);
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert_eq!(app.modal, None);
+        assert_eq!(app.filtered_list().len(), 2);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('T'), KeyModifiers::empty()));
+        assert!(app.type_filter.is_empty());
+    }
+
+    #[test]
+    fn test_summaries_loading_state() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+
+        app.handle_event(AppEvent::PokemonListLoaded(typed_list()));
+        assert_eq!(app.summaries_loading, LoadingState::Loading);
+        assert_eq!(app.types_known(), 5);
+
+        app.handle_event(AppEvent::PokemonSummariesUpdated(vec![PokemonSummary {
+            id: 25,
+            name: "pikachu".to_string(),
+            types: vec!["electric".to_string()],
+            ..Default::default()
+        }]));
+        assert_eq!(app.types_known(), 6);
+
+        app.handle_event(AppEvent::PokemonSummariesFinished);
+        assert_eq!(app.summaries_loading, LoadingState::Loaded);
+    }
+
+    fn list_with_forms() -> Vec<PokemonSummary> {
+        let mon = |id: u32, name: &str| PokemonSummary {
+            id,
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let mut list = vec![
+            mon(6, "charizard"),
+            mon(26, "raichu"),
+            mon(122, "mr-mime"),
+            mon(866, "mr-rime"),
+            mon(10034, "charizard-mega-x"),
+            mon(10100, "raichu-alola"),
+            mon(10168, "mr-mime-galar"),
+            mon(10999, "unknown-form"),
+        ];
+        super::infer_species_ids(&mut list);
+        list
+    }
+
+    #[test]
+    fn test_infer_species_ids() {
+        let list = list_with_forms();
+        let species: Vec<Option<u32>> = list.iter().map(|p| p.species_id).collect();
+        assert_eq!(
+            species,
+            vec![
+                Some(6),
+                Some(26),
+                Some(122),
+                Some(866),
+                Some(6),
+                Some(26),
+                Some(122),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_forms_hidden_by_default_and_grouped_when_shown() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = list_with_forms();
+
+        let ids = |app: &App| app.filtered_list().iter().map(|p| p.id).collect::<Vec<_>>();
+        assert_eq!(ids(&app), vec![6, 26, 122, 866]);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::empty()));
+        assert!(app.show_forms);
+        assert_eq!(ids(&app), vec![6, 10034, 26, 10100, 122, 10168, 866, 10999]);
+    }
+
+    #[test]
+    fn test_forms_use_species_generation() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = list_with_forms();
+        app.show_forms = true;
+
+        app.generation_filter = Some(1);
+        let ids: Vec<u32> = app.filtered_list().iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![6, 10034, 26, 10100, 122, 10168]);
+
+        // Unresolved forms are no longer lumped into Gen 9
+        app.generation_filter = Some(9);
+        assert!(app.filtered_list().is_empty());
+    }
+
+    #[test]
+    fn test_generations_from_species_data() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = list_with_forms();
+        app.show_forms = true;
+        let generations = |app: &App| -> Vec<Option<u8>> {
+            app.pokemon_list.iter().map(|p| p.generation).collect()
+        };
+
+        app.handle_event(AppEvent::GenerationsLoaded(
+            HashMap::from([(6, 1), (26, 1), (122, 1), (866, 8), (1000, 9)]),
+            Vec::new(),
+        ));
+        assert!(app.error_message.is_none());
+        let list = generations(&app);
+        assert_eq!(
+            list,
+            vec![
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(8),
+                Some(1),
+                Some(1),
+                Some(1),
+                None
+            ]
+        );
+
+        // A form picks up its generation once its species is known, and
+        // keeps it when the rest of its summary comes in
+        app.handle_event(AppEvent::PokemonSummariesUpdated(vec![PokemonSummary {
+            id: 10999,
+            name: "unknown-form".to_string(),
+            species_id: Some(1000),
+            ..Default::default()
+        }]));
+        assert_eq!(app.pokemon_list[7].generation, Some(9));
+        app.handle_event(AppEvent::PokemonSummariesUpdated(vec![PokemonSummary {
+            id: 866,
+            name: "mr-rime".to_string(),
+            types: vec!["ice".to_string(), "psychic".to_string()],
+            ..Default::default()
+        }]));
+        assert_eq!(app.pokemon_list[3].generation, Some(8));
+
+        app.generation_filter = Some(9);
+        let ids: Vec<u32> = app.filtered_list().iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![10999]);
+
+        // Generations that failed to load are reported, not taken as the end
+        app.handle_event(AppEvent::GenerationsLoaded(HashMap::new(), vec![4, 6]));
+        assert_eq!(
+            app.error_message.as_deref(),
+            Some("Couldn't load generation 4, 6; going by Pokédex number there")
+        );
+        assert_eq!(app.list_loading, LoadingState::Idle);
+    }
+
+    fn charizard_species() -> PokemonSpecies {
+        let variety =
+            |is_default: bool, name: &str, id: u32| crate::models::pokemon::SpeciesVariety {
+                is_default,
+                pokemon: crate::models::pokemon::NamedResource {
+                    name: name.to_string(),
+                    url: format!("https://pokeapi.co/api/v2/pokemon/{}/", id),
+                },
+            };
+        PokemonSpecies {
+            id: 6,
+            name: "charizard".to_string(),
+            generation: crate::models::pokemon::NamedResource {
+                name: "generation-i".to_string(),
+                url: "https://pokeapi.co/api/v2/generation/1/".to_string(),
+            },
+            varieties: vec![
+                variety(true, "charizard", 6),
+                variety(false, "charizard-mega-x", 10034),
+                variety(false, "charizard-mega-y", 10035),
+            ],
+            evolution_chain: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_click_evolution() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.screen = Screen::PokemonDetail;
+        app.detail_pokemon_id = Some(25);
+        app.detail = Some(Box::new(detail_with_sprites()));
+        app.detail_loading = LoadingState::Loaded;
+        app.detail_species = Some(PokemonSpecies {
+            id: 25,
+            name: "pikachu".to_string(),
+            ..charizard_species()
+        });
+        let chain =
+            |json: &str| -> Box<EvolutionChain> { Box::new(serde_json::from_str(json).unwrap()) };
+        let species = |name: &str, id: u32| {
+            format!(
+                r#"{{"name": "{}", "url": "https://pokeapi.co/api/v2/pokemon-species/{}/"}}"#,
+                name, id
+            )
+        };
+
+        // A chain for some other species is ignored
+        app.handle_event(AppEvent::EvolutionChainLoaded(chain(&format!(
+            r#"{{"id": 1, "chain": {{"species": {}, "evolves_to": []}}}}"#,
+            species("bulbasaur", 1)
+        ))));
+        assert!(app.detail_evolution.is_none());
+
+        app.handle_event(AppEvent::EvolutionChainLoaded(chain(&format!(
+            r#"{{"id": 10, "chain": {{"species": {}, "evolves_to": [
+                {{"species": {}, "evolves_to": [{{"species": {}}}]}}
+            ]}}}}"#,
+            species("pichu", 172),
+            species("pikachu", 25),
+            species("raichu", 26)
+        ))));
+        assert!(app.detail_evolution.is_some());
+
+        app.history.visit(app.location());
+        let (x, y) = click_point(&app, ClickTarget::Evolution(26));
+        app.handle_event(mouse(MouseEventKind::Down(MouseButton::Left), x, y));
+        assert_eq!(app.detail_pokemon_id, Some(26));
+        assert_eq!(app.screen, Screen::PokemonDetail);
+        assert!(app.history.can_go_back());
+    }
+
+    #[tokio::test]
+    async fn test_detail_form_switcher() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = list_with_forms();
+        app.screen = Screen::PokemonDetail;
+        app.detail_pokemon_id = Some(6);
+        app.detail_list_index = Some(0);
+
+        // Species for another Pokémon is ignored
+        let mut other = charizard_species();
+        other.varieties.remove(0);
+        app.handle_event(AppEvent::SpeciesLoaded(Box::new(other)));
+        assert!(app.detail_species.is_none());
+
+        app.handle_event(AppEvent::SpeciesLoaded(Box::new(charizard_species())));
+        assert!(app.detail_species.is_some());
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::empty()));
+        assert_eq!(app.detail_pokemon_id, Some(10034));
+        // Hidden form keeps the base's list position for ←→ navigation
+        assert_eq!(app.detail_list_index, Some(0));
+        assert!(app.detail_species.is_some());
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::empty()));
+        assert_eq!(app.detail_pokemon_id, Some(6));
+        app.handle_key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::empty()));
+        assert_eq!(app.detail_pokemon_id, Some(10035));
+    }
+
+    fn detail_with_sprites() -> PokemonDetail {
+        PokemonDetail {
+            id: 25,
+            name: "pikachu".to_string(),
+            height: 4,
+            weight: 60,
+            types: vec![],
+            stats: vec![],
+            abilities: vec![],
+            moves: vec![],
+            sprites: crate::models::pokemon::Sprites {
+                front_default: Some("https://example.com/25.png".to_string()),
+                front_shiny: Some("https://example.com/shiny/25.png".to_string()),
+                back_default: Some("https://example.com/back/25.png".to_string()),
+                ..Default::default()
+            },
+            species: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cycle_sprite_variant() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.screen = Screen::PokemonDetail;
+        app.detail_pokemon_id = Some(25);
+        app.handle_event(AppEvent::PokemonDetailLoaded(Box::new(
+            detail_with_sprites(),
+        )));
+        app.sprite_bytes = Some(vec![1]);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::empty()));
+        assert_eq!(app.sprite_variant, SpriteVariant::FrontShiny);
+        assert!(app.sprite_bytes.is_none());
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::empty()));
+        assert_eq!(app.sprite_variant, SpriteVariant::BackDefault);
+        app.handle_key(KeyEvent::new(KeyCode::Char('v'), KeyModifiers::empty()));
+        assert_eq!(app.sprite_variant, SpriteVariant::FrontDefault);
+        app.handle_key(KeyEvent::new(KeyCode::Char('V'), KeyModifiers::empty()));
+        assert_eq!(app.sprite_variant, SpriteVariant::BackDefault);
+
+        // Stale bytes for a different variant are dropped
+        app.handle_event(AppEvent::SpriteLoaded(
+            25,
+            SpriteVariant::FrontShiny,
+            vec![2],
+        ));
+        assert!(app.sprite_bytes.is_none());
+        app.handle_event(AppEvent::SpriteLoaded(
+            25,
+            SpriteVariant::BackDefault,
+            vec![3],
+        ));
+        assert_eq!(app.sprite_bytes, Some(vec![3]));
+    }
+
+    #[test]
+    fn test_detail_cycles_sprite_mode() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.screen = Screen::PokemonDetail;
+        app.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::empty()));
+        assert_eq!(app.sprite_mode, BlockMode::Quadrant);
+        app.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::empty()));
+        app.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::empty()));
+        app.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::empty()));
+        assert_eq!(app.sprite_mode, BlockMode::HalfBlock);
+    }
+
+    fn animation_frames() -> Vec<AnimationFrame> {
+        vec![
+            AnimationFrame {
+                png: vec![10],
+                delay: TICK_RATE,
+            },
+            AnimationFrame {
+                png: vec![20],
+                delay: TICK_RATE,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_animation_plays_on_tick() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.screen = Screen::PokemonDetail;
+        app.detail_pokemon_id = Some(25);
+        app.sprite_bytes = Some(vec![1]);
+        assert_eq!(app.current_sprite_bytes(), Some(&[1u8][..]));
+
+        app.handle_event(AppEvent::AnimationLoaded(
+            25,
+            SpriteVariant::FrontDefault,
+            animation_frames(),
+        ));
+        assert_eq!(app.current_sprite_bytes(), Some(&[10u8][..]));
+        app.handle_event(AppEvent::Tick);
+        assert_eq!(app.current_sprite_bytes(), Some(&[20u8][..]));
+
+        // Paused: ticks don't move the frame or ask for a redraw
+        app.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::empty()));
+        assert!(!app.tick());
+        assert_eq!(app.current_sprite_bytes(), Some(&[20u8][..]));
+        app.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::empty()));
+        app.handle_event(AppEvent::Tick);
+        assert_eq!(app.current_sprite_bytes(), Some(&[10u8][..]));
+
+        // Disabled: the static sprite is shown
+        app.animate_sprites = false;
+        assert_eq!(app.current_sprite_bytes(), Some(&[1u8][..]));
+        assert!(!app.tick());
+
+        // Other screens have nothing to animate
+        app.animate_sprites = true;
+        app.screen = Screen::TypeChart;
+        assert!(!app.tick());
+    }
+
+    #[test]
+    fn test_animation_for_other_sprite_is_cached_not_shown() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.detail_pokemon_id = Some(25);
+        app.handle_event(AppEvent::AnimationLoaded(
+            25,
+            SpriteVariant::BackDefault,
+            animation_frames(),
+        ));
+        assert!(app.sprite_animation.is_none());
+        assert!(app
+            .animation_cache
+            .contains_key(&(25, SpriteVariant::BackDefault)));
+
+        // Loading the detail picks the cached animation up for the matching variant
+        app.sprite_variant = SpriteVariant::BackDefault;
+        app.handle_event(AppEvent::PokemonDetailLoaded(Box::new(
+            detail_with_sprites(),
+        )));
+        assert_eq!(
+            app.sprite_animation.as_ref().map(|a| a.variant),
+            Some(SpriteVariant::BackDefault)
+        );
+    }
+
+    #[test]
+    fn test_resize_clears_sprite_cache() {
+        use crate::sprite::cache::SpriteKey;
+        use crate::sprite::renderer::RenderOptions;
+
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        let key = SpriteKey {
+            pokemon_id: 25,
+            variant: SpriteVariant::FrontDefault,
+            frame: None,
+            width: 10,
+            height: 10,
+            options: RenderOptions::default(),
+        };
+        app.sprite_cache.get_or_render(key, b"not a png");
+        assert_eq!(app.sprite_cache.len(), 1);
+        app.handle_event(AppEvent::Resize(120, 40));
+        assert!(app.sprite_cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_thumbnails_requested_once() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        assert!(app.want_thumbnail(25).is_none());
+        assert!(app.want_thumbnail(25).is_none());
+        app.fetch_wanted_thumbnails();
+        assert!(app.thumbnails_requested.contains(&25));
+        assert!(app.thumbnails_wanted.borrow().is_empty());
+
+        // Already requested: not queued again
+        assert!(app.want_thumbnail(25).is_none());
+        assert!(app.thumbnails_wanted.borrow().is_empty());
+
+        app.handle_event(AppEvent::ThumbnailLoaded(25, vec![7]));
+        assert_eq!(app.want_thumbnail(25), Some(&[7u8][..]));
+    }
+
+    #[tokio::test]
+    async fn test_export_team_card() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let team_data = TeamData {
+            teams: vec![Team {
+                name: "Rain Dance".to_string(),
+                members: vec![TeamMember {
+                    pokemon_id: 279,
+                    pokemon_name: "pelipper".to_string(),
+                    types: vec!["water".to_string(), "flying".to_string()],
+                    moves: Vec::new(),
+                }],
+            }],
+            read_only: false,
+        };
+        let mut app = App::new_with_team_data(tx, team_data);
+        // Sprite already on hand, so nothing is fetched
+        app.thumbnails.insert(279, vec![0]);
+        let dir = tempfile::tempdir().unwrap();
+        app.export_team_card_to(dir.path().to_path_buf());
+        assert!(app.status_message.is_some());
+
+        let Some(AppEvent::ExportFinished(result, 0)) = rx.recv().await else {
+            panic!("expected ExportFinished");
+        };
+        let path = result.unwrap();
+        assert_eq!(path, dir.path().join("team-rain-dance.png"));
+        assert!(image::open(&path).is_ok());
+
+        // Exporting again keeps the first card
+        app.export_team_card_to(dir.path().to_path_buf());
+        let Some(AppEvent::ExportFinished(again, 0)) = rx.recv().await else {
+            panic!("expected ExportFinished");
+        };
+        assert_eq!(again.unwrap(), dir.path().join("team-rain-dance-2.png"));
+        assert!(image::open(&path).is_ok());
+
+        app.handle_event(AppEvent::ExportFinished(Ok(path.clone()), 2));
+        assert_eq!(
+            app.status_message,
+            Some(format!(
+                "Saved {}, but 2 entries are incomplete: their details couldn't be fetched",
+                path.display()
+            ))
+        );
+        app.handle_event(AppEvent::ExportFinished(Ok(path), 0));
+        assert!(app.status_message.as_deref().unwrap().starts_with("Saved "));
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::empty()));
+        assert!(app.status_message.is_none());
+    }
+
+    #[test]
+    fn test_export_detail_card_needs_detail() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.screen = Screen::PokemonDetail;
+        app.handle_key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::empty()));
+        assert!(app.status_message.is_none());
+        app.handle_event(AppEvent::ExportFinished(Err("disk full".to_string()), 0));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Export failed: disk full")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_entries_from_detail() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.screen = Screen::PokemonDetail;
+        app.detail = Some(Box::new(detail_with_sprites()));
+        app.handle_key(KeyEvent::new(KeyCode::Char('E'), KeyModifiers::empty()));
+        assert_eq!(app.export_format, ExportFormat::Json);
+        assert_eq!(app.status_message.as_deref(), Some("Export format: JSON"));
+
+        let dir = tempfile::tempdir().unwrap();
+        app.export_entries_to(dir.path().to_path_buf());
+        let Some(AppEvent::ExportFinished(result, 0)) = rx.recv().await else {
+            panic!("expected ExportFinished");
+        };
+        let path = result.unwrap();
+        assert_eq!(path.extension().unwrap(), "json");
+        let value: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_export_entries_skips_empty_list() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.export_entries_to(std::env::temp_dir());
+        assert!(app.status_message.is_none());
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> AppEvent {
+        AppEvent::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::empty(),
+        })
+    }
+
+    /// Draw a frame and return the top-left cell of a target's clickable area
+    fn click_point(app: &App, target: ClickTarget) -> (u16, u16) {
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 30)).unwrap();
+        terminal
+            .draw(|f| {
+                crate::ui::draw(f, app);
+            })
+            .unwrap();
+        let regions = app.click_regions.borrow();
+        let (area, _) = regions
+            .iter()
+            .find(|(_, t)| *t == target)
+            .expect("target drawn");
+        (area.x, area.y)
+    }
+
+    #[tokio::test]
+    async fn test_click_selects_then_opens_list_row() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = typed_list();
+        app.list_loading = LoadingState::Loaded;
+
+        let (x, y) = click_point(&app, ClickTarget::ListRow(2));
+        let down = MouseEventKind::Down(MouseButton::Left);
+        app.handle_event(mouse(down, x, y));
+        assert_eq!(app.list_state, 2);
+        assert_eq!(app.screen, Screen::PokemonList);
+
+        app.handle_event(mouse(down, x, y));
+        assert_eq!(app.screen, Screen::PokemonDetail);
+
+        let (x, y) = click_point(&app, ClickTarget::Tab(Screen::TeamBuilder));
+        app.handle_event(mouse(down, x, y));
+        assert_eq!(app.screen, Screen::TeamBuilder);
+    }
+
+    #[tokio::test]
+    async fn test_history_back_and_forward() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = typed_list();
+        app.list_loading = LoadingState::Loaded;
+        let press =
+            |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::empty()));
+
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.detail_pokemon_id, Some(16));
+        // Stepping through Pokémon doesn't add places to go back through
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Char('3'));
+        press(&mut app, KeyCode::Right);
+        assert_eq!(app.type_chart_scroll_x, 1);
+
+        press(&mut app, KeyCode::Char('['));
+        assert_eq!(app.screen, Screen::PokemonDetail);
+        assert_eq!(app.detail_pokemon_id, Some(25));
+        app.handle_event(AppEvent::PokemonDetailLoaded(Box::new(
+            detail_with_sprites(),
+        )));
+        app.type_chart_scroll_x = 0;
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!(app.screen, Screen::TypeChart);
+        assert_eq!(app.type_chart_scroll_x, 1);
+        press(&mut app, KeyCode::Char('['));
+
+        // Esc goes back to the list, finding the row again after a re-sort
+        app.sort_descending = true;
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.screen, Screen::PokemonList);
+        assert_eq!(app.filtered_list()[app.list_state].id, 16);
+        press(&mut app, KeyCode::Char('['));
+        assert_eq!(app.status_message.as_deref(), Some("Nothing to go back to"));
+
+        press(&mut app, KeyCode::Char('H'));
+        assert_eq!(app.modal, Some(Modal::Recent));
+        assert_eq!(app.recent_rows(), vec![(25, "pikachu")]);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.screen, Screen::PokemonDetail);
+        press(&mut app, KeyCode::Char(']'));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Nothing to go forward to")
+        );
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.screen, Screen::PokemonList);
+    }
+
+    #[tokio::test]
+    async fn test_split_views() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = typed_list();
+        app.list_loading = LoadingState::Loaded;
+        let press = |app: &mut App, c| app.handle_key(KeyEvent::new(c, KeyModifiers::empty()));
+
+        // The detail beside the list follows the selection
+        press(&mut app, KeyCode::Char('|'));
+        assert_eq!(app.split(), Some(50));
+        assert!(app.shows_detail());
+        assert_eq!(app.detail_pokemon_id, Some(4));
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.detail_pokemon_id, Some(6));
+        assert_eq!(app.screen, Screen::PokemonList);
+        // A late reply for a Pokémon no longer selected is dropped
+        app.handle_event(AppEvent::PokemonDetailLoaded(Box::new(
+            detail_with_sprites(),
+        )));
+        assert!(app.detail.is_none());
+
+        press(&mut app, KeyCode::Char('>'));
+        assert_eq!(app.settings.panes.list_detail.left, 55);
+        for _ in 0..10 {
+            press(&mut app, KeyCode::Char('<'));
+        }
+        assert_eq!(app.split(), Some(MIN_PANE));
+
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('|'));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No split view for the Detail")
+        );
+        press(&mut app, KeyCode::Char('4'));
+        assert_eq!(app.split(), None);
+        press(&mut app, KeyCode::Char('|'));
+        assert_eq!(app.split(), Some(50));
+        assert_eq!(app.type_chart_loading, LoadingState::Loading);
+
+        app.accessible = true;
+        assert_eq!(app.split(), None);
+    }
+
+    #[test]
+    fn test_clicks_behind_modal_are_ignored() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = typed_list();
+        app.list_loading = LoadingState::Loaded;
+        app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::empty()));
+
+        let (x, y) = click_point(&app, ClickTarget::ModalRow(1));
+        app.handle_event(mouse(MouseEventKind::Down(MouseButton::Left), x, y));
+        // Type filter rows toggle on the first click
+        assert_eq!(app.type_filter, vec!["fire".to_string()]);
+
+        let (x, y) = click_point(&app, ClickTarget::Tab(Screen::TypeChart));
+        app.handle_event(mouse(MouseEventKind::Down(MouseButton::Left), x, y));
+        assert_eq!(app.screen, Screen::PokemonList);
+    }
+
+    #[test]
+    fn test_scroll_wheel() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = typed_list();
+
+        app.handle_event(mouse(MouseEventKind::ScrollDown, 0, 0));
+        app.handle_event(mouse(MouseEventKind::ScrollDown, 0, 0));
+        app.handle_event(mouse(MouseEventKind::ScrollUp, 0, 0));
+        assert_eq!(app.list_state, 1);
+
+        app.screen = Screen::TypeChart;
+        app.handle_event(AppEvent::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::SHIFT,
+        }));
+        assert_eq!((app.type_chart_scroll_x, app.type_chart_scroll_y), (1, 0));
+
+        app.screen = Screen::TeamBuilder;
+        app.handle_event(mouse(MouseEventKind::ScrollDown, 0, 0));
+        assert_eq!(app.team_slot_selected, 1);
+    }
+
+    #[test]
+    fn test_toggle_mouse_capture() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.handle_key(KeyEvent::new(KeyCode::Char('M'), KeyModifiers::SHIFT));
+        assert!(!app.mouse_capture);
+        app.handle_key(KeyEvent::new(KeyCode::Char('M'), KeyModifiers::SHIFT));
+        assert!(app.mouse_capture);
+    }
+
+    #[test]
+    fn test_keys_go_through_keymap() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = typed_list();
+        app.keymap = Keymap::preset(crate::keymap::Preset::Emacs);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
+        assert_eq!(app.list_state, 1);
+        // hjkl aren't movement keys in the emacs preset
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::empty()));
+        assert_eq!(app.list_state, 1);
+
+        // While searching, letters are typed and Ctrl keys still work
+        app.handle_key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::empty()));
+        app.handle_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::empty()));
+        assert_eq!(app.search_query, "q");
+        assert!(app.running);
+        app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert!(!app.running);
+    }
+
+    #[test]
+    fn test_help_overlay() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = typed_list();
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::empty()));
+        assert!(app.show_help);
+        let sections = app.help_sections();
+        assert_eq!(sections[0].0, "Pokédex");
+        assert!(sections[0]
+            .1
+            .contains(&("1-9".to_string(), "Generation 1-9".to_string())));
+        assert_eq!(sections[1].0, "Global");
+
+        // Movement scrolls the help instead of the list
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::empty()));
+        assert_eq!(app.help_scroll, 1);
+        assert_eq!(app.list_state, 0);
+        // Anything else closes it without acting
+        app.handle_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::empty()));
+        assert!(!app.show_help);
+        assert!(app.running);
+
+        // Inside a modal only its own bindings are listed
+        app.modal = Some(Modal::TypeFilter);
+        app.handle_key(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::empty()));
+        assert!(app.show_help);
+        let sections = app.help_sections();
+        assert_eq!(sections.len(), 1);
+        assert!(sections[0].1.iter().any(|(_, d)| d == "Toggle type"));
+        assert!(!sections[0].1.iter().any(|(_, d)| d == "Search"));
+    }
+
+    #[test]
+    fn test_command_palette() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = typed_list();
+
+        app.handle_key(KeyEvent::new(KeyCode::Char(':'), KeyModifiers::empty()));
+        assert_eq!(app.modal, Some(Modal::CommandPalette));
+        // Letters are typed, even ones bound to actions
+        for c in "reverse".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        assert_eq!(app.modal_search, "reverse");
+        assert!(!app.sort_descending);
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.modal, None);
+        assert!(app.sort_descending);
+
+        // Ctrl-P opens a fresh palette; Esc closes it
+        app.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        assert_eq!(app.modal, Some(Modal::CommandPalette));
+        assert!(app.modal_search.is_empty());
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert_eq!(app.modal, None);
+
+        app.team_data.teams.push(Team {
+            name: "Rain".to_string(),
+            members: Vec::new(),
+        });
+        app.handle_key(KeyEvent::new(KeyCode::Char(':'), KeyModifiers::empty()));
+        for c in "team rain".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.screen, Screen::TeamBuilder);
+        assert_eq!(app.current_team, app.team_data.teams.len() - 1);
+    }
+
+    #[test]
+    fn test_switch_theme() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        assert_eq!(app.theme.name, ThemeName::Default);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL));
+        assert_eq!(app.theme.name, ThemeName::Light);
+        assert_eq!(app.theme.text, ratatui::style::Color::Black);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char(':'), KeyModifiers::empty()));
+        for c in "16 col".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.theme.name, ThemeName::Basic);
+        assert_eq!(app.status_message.as_deref(), Some("Theme: 16 colors"));
+    }
+
+    #[test]
+    fn test_toggle_accessible_mode() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        assert_eq!(crate::ui::thumbnail_width(&app), 2);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char(':'), KeyModifiers::empty()));
+        for c in "accessib".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert!(app.accessible);
+        assert_eq!(crate::ui::thumbnail_width(&app), 0);
+        assert_eq!(
+            crate::ui::status::announcement(&app),
+            "Accessibility mode on. Pokédex: loading"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_session() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        let settings = Settings {
+            filters: Filters {
+                types: vec!["fire".into(), "flying".into(), "water".into()],
+                sort: SortKey::Speed,
+                descending: true,
+                ..Default::default()
+            },
+            sprite_mode: BlockMode::Braille,
+            ..Default::default()
+        };
+        let session = Session {
+            screen: Screen::PokemonDetail,
+            search_query: "char".to_string(),
+            generation_filter: Some(12),
+            list_state: 40,
+            pokemon_id: Some(6),
+            current_team: 99,
+            recent: vec![6, 1],
+        };
+        app.restore(settings.clone(), session.clone());
+        assert_eq!(app.screen, Screen::PokemonDetail);
+        assert_eq!(app.detail_pokemon_id, Some(6));
+        assert_eq!(app.history.recent(), [6, 1]);
+        assert_eq!(app.type_filter, vec!["fire", "flying"]);
+        assert_eq!(app.sort_key, SortKey::Speed);
+        assert!(app.sort_descending);
+        assert_eq!(app.sprite_mode, BlockMode::Braille);
+        assert_eq!(app.search_query, "char");
+        assert_eq!(app.generation_filter, None);
+        assert_eq!(app.current_team, app.team_data.teams.len() - 1);
+
+        // The saved position is clamped once the list arrives
+        app.handle_event(AppEvent::PokemonListLoaded(vec![PokemonSummary {
+            id: 6,
+            name: "charizard".to_string(),
+            types: vec!["fire".to_string(), "flying".to_string()],
+            ..Default::default()
+        }]));
+        assert_eq!(app.list_state, 0);
+        assert_eq!(app.detail_list_index, Some(0));
+        assert_eq!(app.session().pokemon_id, Some(6));
+
+        // A default screen wins over the last one
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        let settings = Settings {
+            default_screen: Some(Screen::TeamBuilder),
+            ..Default::default()
+        };
+        app.restore(settings, session);
+        assert_eq!(app.screen, Screen::TeamBuilder);
+
+        // Nothing to show on the detail screen without a Pokémon
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        let session = Session {
+            screen: Screen::PokemonDetail,
+            ..Default::default()
+        };
+        app.restore(Settings::default(), session);
+        assert_eq!(app.screen, Screen::PokemonList);
+    }
+
+    #[test]
+    fn test_favorite_and_tag_filters() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = [(1, "bulbasaur"), (6, "charizard"), (7, "squirtle")]
+            .into_iter()
+            .map(|(id, name)| PokemonSummary {
+                id,
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        app.notes.toggle_favorite(6);
+        app.notes.toggle_favorite(7);
+        app.notes.toggle_tag(7, "rain team");
+        app.notes.toggle_tag(1, "starter");
+        app.notes.toggle_tag(7, "starter");
+        let ids = |app: &App| app.filtered_list().iter().map(|p| p.id).collect::<Vec<_>>();
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::empty()));
+        assert_eq!(ids(&app), vec![6, 7]);
+
+        // Tags compose with the other filters; typing narrows the picker
+        app.handle_key(KeyEvent::new(KeyCode::Char('#'), KeyModifiers::empty()));
+        assert_eq!(app.modal, Some(Modal::TagFilter));
+        for c in "sta".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        assert_eq!(
+            app.tag_rows(Modal::TagFilter),
+            vec![("starter".to_string(), 2, false)]
+        );
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.tag_filter, vec!["starter"]);
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert_eq!(app.modal, None);
+        assert_eq!(ids(&app), vec![7]);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::empty()));
+        assert_eq!(ids(&app), vec![1, 7]);
+    }
+
+    #[test]
+    fn test_collection_reloaded_before_marking() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("collection.json");
+        app.set_collection(Collection::default(), path.clone());
+        app.cycle_collected(1);
+
+        // `pokedex collection import` adds marks while the app is open
+        let mut imported = Collection::read_from(&path).unwrap();
+        imported.set(25, Some(Status::Caught));
+        imported.save_to(&path).unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(1);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+
+        app.cycle_collected(4);
+        let saved = Collection::read_from(&path).unwrap();
+        assert_eq!(saved.status(1), Some(Status::Seen));
+        assert_eq!(saved.status(25), Some(Status::Caught));
+        assert_eq!(saved.status(4), Some(Status::Seen));
+
+        // A file that can't be read is reported and left alone
+        std::fs::write(&path, "{ not json").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later + std::time::Duration::from_secs(1))
+            .unwrap();
+        app.cycle_collected(7);
+        assert!(app.error_message.is_some());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{ not json");
+    }
+
+    #[test]
+    fn test_notes_reloaded_before_editing() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        app.set_notes(Notes::default(), path.clone());
+        app.toggle_favorite(1);
+
+        // Another copy of the app favorites something too
+        let mut other = Notes::read_from(&path).unwrap();
+        other.toggle_favorite(25);
+        other.save_to(&path).unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(1);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+
+        app.toggle_favorite(4);
+        let saved = Notes::read_from(&path).unwrap();
+        assert!([1, 4, 25].iter().all(|&id| saved.is_favorite(id)));
+    }
+
+    #[test]
+    fn test_mark_range_and_progress() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = [(1, "bulbasaur"), (25, "pikachu"), (152, "chikorita")]
+            .into_iter()
+            .map(|(id, name)| PokemonSummary {
+                id,
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect();
+
+        // A mark that doesn't parse keeps the prompt open to fix it
+        app.handle_key(KeyEvent::new(KeyCode::Char('C'), KeyModifiers::empty()));
+        assert_eq!(app.modal, Some(Modal::MarkRange));
+        for c in "1-151 got".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.modal, Some(Modal::MarkRange));
+        assert!(app
+            .status_message
+            .as_deref()
+            .unwrap()
+            .starts_with("Can't mark:"));
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+
+        app.collection.mark([1, 25], Some(Status::Seen));
+        app.collection.set(25, Some(Status::Shiny));
+        app.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::empty()));
+        assert_eq!(app.modal, Some(Modal::Progress));
+        let progress = app.collection_progress();
+        assert_eq!(
+            (
+                progress[0].total,
+                progress[0].seen,
+                progress[0].caught,
+                progress[0].shiny
+            ),
+            (2, 2, 1, 1)
+        );
+        assert_eq!((progress[1].total, progress[1].seen), (1, 0));
+
+        // Red/Blue only counts the first 151
+        app.collection.game = "red-blue".to_string();
+        let progress = app.collection_progress();
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_tag_and_note_editors() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.screen = Screen::PokemonDetail;
+        app.detail_pokemon_id = Some(25);
+        app.handle_event(AppEvent::PokemonDetailLoaded(Box::new(
+            detail_with_sprites(),
+        )));
+        app.notes.toggle_tag(1, "rain team");
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('#'), KeyModifiers::empty()));
+        assert_eq!(app.modal, Some(Modal::EditTags));
+        for c in "Rain".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        // A new tag is offered above the ones that match
+        assert_eq!(
+            app.tag_rows(Modal::EditTags),
+            vec![
+                ("rain".to_string(), 0, false),
+                ("rain team".to_string(), 1, false)
+            ]
+        );
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+
+        // Keys that are bindings elsewhere are just text in the editor
+        app.notes.set_note(25, "# Plan");
+        app.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::empty()));
+        assert_eq!(app.modal, Some(Modal::EditNote));
+        assert_eq!(app.modal_search, "# Plan");
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        for c in "- q?x".chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        app.handle_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::empty()));
+        assert_eq!(app.modal_search, "# Plan\n- q?");
+        assert!(app.running);
+        assert!(!app.show_help);
+    }
+
+    #[test]
+    fn test_toggle_row_height() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        assert_eq!(app.row_height(), 1);
+        app.handle_key(KeyEvent::new(KeyCode::Char('z'), KeyModifiers::empty()));
+        assert_eq!(app.row_height(), 3);
+        app.screen = Screen::TeamBuilder;
+        app.handle_key(KeyEvent::new(KeyCode::Char('z'), KeyModifiers::empty()));
+        assert_eq!(app.row_height(), 1);
+    }
+
+    #[test]
+    fn test_sprite_variant_falls_back_when_unavailable() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.sprite_variant = SpriteVariant::OfficialArtwork;
+        app.handle_event(AppEvent::PokemonDetailLoaded(Box::new(
+            detail_with_sprites(),
+        )));
+        assert_eq!(app.sprite_variant, SpriteVariant::FrontDefault);
+
+        app.sprite_variant = SpriteVariant::FrontShiny;
+        app.handle_event(AppEvent::PokemonDetailLoaded(Box::new(
+            detail_with_sprites(),
+        )));
+        assert_eq!(app.sprite_variant, SpriteVariant::FrontShiny);
+    }
+
+    #[test]
+    fn test_app_current_team() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        // Use test helper to avoid reading from real cache file
+        let team_data = TeamData::default();
+        let app = App::new_with_team_data(tx, team_data);
+
+        let team = app.current_team();
+        assert_eq!(team.name, "Team 1");
+        assert!(team.members.is_empty());
+    }
+
+    #[test]
+    fn test_teams_reloaded_before_saving() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("teams.json");
+        app.set_teams(TeamData::default(), path.clone());
+        app.screen = Screen::TeamBuilder;
+        app.team_action(Action::NewTeam);
+        assert_eq!(TeamData::read_from(&path).unwrap().teams.len(), 2);
+
+        // Someone else, like the API server, renames a team and adds another
+        let mut other = TeamData::read_from(&path).unwrap();
+        other.teams[0].name = "Rain".to_string();
+        other.teams.push(Team {
+            name: "Sun".to_string(),
+            members: Vec::new(),
+        });
+        other.save_to(&path).unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(1);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+
+        // An edit here lands on top of theirs instead of replacing it
+        app.team_action(Action::NewTeam);
+        let saved = TeamData::read_from(&path).unwrap();
+        let names: Vec<&str> = saved.teams.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Rain", "Team 2", "Sun", "Team 4"]);
+        assert_eq!(app.team_data.teams.len(), 4);
+    }
+
+    #[test]
+    fn test_unreadable_teams_not_saved_over() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("teams.json");
+        app.set_teams(TeamData::default(), path.clone());
+        app.screen = Screen::TeamBuilder;
+
+        // Someone else leaves a file this app can't read
+        std::fs::write(&path, "{ not json").unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(1);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+
+        // Every edit is refused and reported, and the file is left alone
+        for _ in 0..2 {
+            app.error_message = None;
+            app.team_action(Action::NewTeam);
+            assert!(app.error_message.is_some());
+            assert_eq!(app.team_data.teams.len(), 1);
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), "{ not json");
+        }
+    }
+
+    #[test]
+    fn test_newer_teams_file_not_saved_over() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("teams.json");
+        let newer = r#"{"version": 99, "teams": []}"#;
+        std::fs::write(&path, newer).unwrap();
+        let (data, _) = TeamData::load_from(&path);
+        app.set_teams(data, path.clone());
+        app.screen = Screen::TeamBuilder;
+
+        app.team_action(Action::NewTeam);
+        assert!(app.error_message.is_some());
+        assert_eq!(app.team_data.teams.len(), 1);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), newer);
+    }
+
+    #[test]
+    fn test_app_modal_filtered_list() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+
+        app.pokemon_list = vec![
+            PokemonSummary {
+                id: 1,
+                name: "bulbasaur".to_string(),
+                types: vec![],
+                ..Default::default()
+            },
+            PokemonSummary {
+                id: 25,
+                name: "pikachu".to_string(),
+                types: vec![],
+                ..Default::default()
+            },
+        ];
+
+        app.modal_search = "pika".to_string();
+        let filtered = app.modal_filtered_list();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "pikachu");
+
+        // The list's type, favorite and tag filters don't carry over
+        app.modal_search.clear();
+        app.type_filter = vec!["fire".to_string()];
+        app.favorites_only = true;
+        app.tag_filter = vec!["rain".to_string()];
+        assert!(app.filtered_list().is_empty());
+        assert_eq!(app.modal_filtered_list().len(), 2);
+        // The generation filter does
+        app.generation_filter = Some(2);
+        assert!(app.modal_filtered_list().is_empty());
     }
 
     #[test]
@@ -1151,7 +4619,9 @@ mod tests {
     fn test_modal_equality() {
         assert_eq!(Modal::PokemonPicker, Modal::PokemonPicker);
         assert_eq!(Modal::MovePicker, Modal::MovePicker);
+        assert_eq!(Modal::TypeFilter, Modal::TypeFilter);
         assert_ne!(Modal::PokemonPicker, Modal::MovePicker);
+        assert_ne!(Modal::TypeFilter, Modal::PokemonPicker);
     }
 
     #[tokio::test]
@@ -1165,16 +4635,19 @@ mod tests {
                 id: 1,
                 name: "bulbasaur".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 25,
                 name: "pikachu".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 152,
                 name: "chikorita".to_string(),
                 types: vec![],
+                ..Default::default()
             },
         ];
 
@@ -1195,7 +4668,9 @@ mod tests {
             moves: vec![],
             sprites: crate::models::pokemon::Sprites {
                 front_default: None,
+                ..Default::default()
             },
+            species: None,
         };
         app.detail = Some(Box::new(detail));
         app.detail_loading = LoadingState::Loaded;
@@ -1220,16 +4695,19 @@ mod tests {
                 id: 1,
                 name: "bulbasaur".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 25,
                 name: "pikachu".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 152,
                 name: "chikorita".to_string(),
                 types: vec![],
+                ..Default::default()
             },
         ];
 
@@ -1250,7 +4728,9 @@ mod tests {
             moves: vec![],
             sprites: crate::models::pokemon::Sprites {
                 front_default: None,
+                ..Default::default()
             },
+            species: None,
         };
         app.detail = Some(Box::new(detail));
         app.detail_loading = LoadingState::Loaded;
@@ -1275,11 +4755,13 @@ mod tests {
                 id: 1,
                 name: "bulbasaur".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 25,
                 name: "pikachu".to_string(),
                 types: vec![],
+                ..Default::default()
             },
         ];
 
@@ -1300,7 +4782,9 @@ mod tests {
             moves: vec![],
             sprites: crate::models::pokemon::Sprites {
                 front_default: None,
+                ..Default::default()
             },
+            species: None,
         };
         app.detail = Some(Box::new(detail));
         app.detail_loading = LoadingState::Loaded;
@@ -1325,11 +4809,13 @@ mod tests {
                 id: 1,
                 name: "bulbasaur".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 25,
                 name: "pikachu".to_string(),
                 types: vec![],
+                ..Default::default()
             },
         ];
 
@@ -1350,7 +4836,9 @@ mod tests {
             moves: vec![],
             sprites: crate::models::pokemon::Sprites {
                 front_default: None,
+                ..Default::default()
             },
+            species: None,
         };
         app.detail = Some(Box::new(detail));
         app.detail_loading = LoadingState::Loaded;
@@ -1375,16 +4863,19 @@ mod tests {
                 id: 1,
                 name: "bulbasaur".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 25,
                 name: "pikachu".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 152,
                 name: "chikorita".to_string(),
                 types: vec![],
+                ..Default::default()
             },
         ];
 
@@ -1408,7 +4899,9 @@ mod tests {
             moves: vec![],
             sprites: crate::models::pokemon::Sprites {
                 front_default: None,
+                ..Default::default()
             },
+            species: None,
         };
         app.detail = Some(Box::new(detail));
         app.detail_loading = LoadingState::Loaded;
@@ -1435,11 +4928,13 @@ mod tests {
                 id: 1,
                 name: "bulbasaur".to_string(),
                 types: vec![],
+                ..Default::default()
             },
             PokemonSummary {
                 id: 25,
                 name: "pikachu".to_string(),
                 types: vec![],
+                ..Default::default()
             },
         ];
 
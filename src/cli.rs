@@ -0,0 +1,911 @@
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use serde_json::json;
+
+use crate::api::client::ApiClient;
+use crate::app::{rank_by_query, summaries_from_list};
+use crate::export;
+use crate::export::table::{DexEntry, ExportFormat};
+use crate::models::collection::Collection;
+use crate::models::pokemon::{MoveDetail, PokemonDetail, PokemonSpecies, PokemonSummary};
+use crate::models::team::TeamData;
+use crate::models::type_data::{defensive_multiplier, TypeInfo, ALL_TYPES};
+use crate::search::query::SearchQuery;
+use crate::server;
+use crate::ui::capitalize;
+
+pub const USAGE: &str = "\
+Usage: pokedex [COMMAND]
+
+With no command, starts the interactive Pokédex.
+
+Commands:
+  show NAME [--json]
+      Types, size, abilities and base stats of a Pokémon (name or dex number)
+  type TYPE[/TYPE] [--json]
+      Weaknesses, resistances and immunities of a type or dual-type combination
+  move NAME [--json]
+      Type, category, power, accuracy and PP of a move
+  team list [--json]
+      Saved teams with their members and moves
+  search QUERY [--json] [--forms]
+      Filter with type:T (twice or T1/T2 for dual types), gen:N and a fuzzy name,
+      e.g. pokedex search 'type:water gen:3'
+  export [NAME...] [--format md|json|csv] [--output FILE] [--forms]
+      Write Pokédex entries (id, name, types, base stats, abilities, height, weight).
+      With no names, exports every Pokémon; alternate forms only with --forms.
+      Prints to stdout unless --output is given.
+  collection export [--format json|csv] [--output FILE]
+      Seen/caught/shiny marks for every game. Prints to stdout unless --output is given.
+  collection import FILE [--format json|csv]
+      Add marks from an export, replacing marks already there for the same Pokémon
+  serve [--port N] [--host ADDR]
+      Local HTTP/JSON API over the cache and saved teams (default 127.0.0.1:8765).
+      ADDR must be a loopback address.
+  help
+      Show this message";
+
+/// What to do after parsing the command line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Tui,
+    Help,
+    Export {
+        names: Vec<String>,
+        format: ExportFormat,
+        output: Option<PathBuf>,
+        forms: bool,
+    },
+    Show {
+        name: String,
+        json: bool,
+    },
+    Type {
+        types: Vec<String>,
+        json: bool,
+    },
+    Move {
+        name: String,
+        json: bool,
+    },
+    TeamList {
+        json: bool,
+    },
+    Search {
+        query: SearchQuery,
+        json: bool,
+        forms: bool,
+    },
+    CollectionExport {
+        format: ExportFormat,
+        output: Option<PathBuf>,
+    },
+    CollectionImport {
+        path: PathBuf,
+        format: ExportFormat,
+    },
+    Serve {
+        addr: SocketAddr,
+    },
+}
+
+/// Parse arguments, without the program name
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command> {
+    let mut args = args.into_iter();
+    let Some(command) = args.next() else {
+        return Ok(Command::Tui);
+    };
+    match command.as_str() {
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        "export" => parse_export(args),
+        "serve" => parse_serve(args),
+        "collection" => parse_collection(args),
+        "show" | "type" | "move" | "team" | "search" => parse_query(&command, args),
+        other => bail!("unknown command '{}'\n\n{}", other, USAGE),
+    }
+}
+
+fn parse_export(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let mut names = Vec::new();
+    let mut format = None;
+    let mut output: Option<PathBuf> = None;
+    let mut forms = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" | "-f" => {
+                let value = args.next().context("--format needs a value")?;
+                format = Some(
+                    ExportFormat::from_name(&value)
+                        .with_context(|| format!("unknown format '{}'", value))?,
+                );
+            }
+            "--output" | "-o" => {
+                output = Some(args.next().context("--output needs a path")?.into());
+            }
+            "--forms" => forms = true,
+            flag if flag.starts_with('-') => bail!("unknown option '{}'", flag),
+            name => names.push(name.to_lowercase()),
+        }
+    }
+    // Without --format, go by the output file's extension, else Markdown
+    let format = format
+        .or_else(|| {
+            let ext = output.as_ref()?.extension()?.to_str()?;
+            ExportFormat::from_name(ext)
+        })
+        .unwrap_or(ExportFormat::Markdown);
+    Ok(Command::Export {
+        names,
+        format,
+        output,
+        forms,
+    })
+}
+
+fn parse_collection(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let subcommand = args.next();
+    let mut format = None;
+    let mut path: Option<PathBuf> = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" | "-f" => {
+                let value = args.next().context("--format needs a value")?;
+                format = Some(match ExportFormat::from_name(&value) {
+                    Some(f @ (ExportFormat::Json | ExportFormat::Csv)) => f,
+                    _ => bail!("collections are json or csv, not '{}'", value),
+                });
+            }
+            "--output" | "-o" if subcommand.as_deref() == Some("export") => {
+                path = Some(args.next().context("--output needs a path")?.into());
+            }
+            flag if flag.starts_with('-') => bail!("unknown option '{}'", flag),
+            file if subcommand.as_deref() == Some("import") && path.is_none() => {
+                path = Some(file.into());
+            }
+            other => bail!("unexpected argument '{}'", other),
+        }
+    }
+    // Without --format, go by the file's extension, else JSON
+    let format = format
+        .or_else(|| {
+            let ext = path.as_ref()?.extension()?.to_str()?;
+            ExportFormat::from_name(ext).filter(|f| *f == ExportFormat::Csv)
+        })
+        .unwrap_or(ExportFormat::Json);
+    match subcommand.as_deref() {
+        Some("export") => Ok(Command::CollectionExport {
+            format,
+            output: path,
+        }),
+        Some("import") => Ok(Command::CollectionImport {
+            path: path.context("'collection import' needs a file")?,
+            format,
+        }),
+        Some(other) => bail!("unknown collection command '{}'", other),
+        None => bail!("'collection' needs export or import\n\n{}", USAGE),
+    }
+}
+
+fn parse_serve(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let mut addr = SocketAddr::from((Ipv4Addr::LOCALHOST, server::DEFAULT_PORT));
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--port" | "-p" => {
+                let value = args.next().context("--port needs a number")?;
+                addr.set_port(
+                    value
+                        .parse()
+                        .with_context(|| format!("bad port '{}'", value))?,
+                );
+            }
+            "--host" => {
+                let value = args.next().context("--host needs an address")?;
+                let ip: IpAddr = value
+                    .parse()
+                    .with_context(|| format!("bad address '{}'", value))?;
+                // Teams can be edited without any login, so stay off the network
+                if !ip.is_loopback() {
+                    bail!("--host must be a loopback address like 127.0.0.1 or ::1");
+                }
+                addr.set_ip(ip);
+            }
+            other => bail!("unknown option '{}'", other),
+        }
+    }
+    Ok(Command::Serve { addr })
+}
+
+/// Query commands: positional words plus `--json` (and `--forms` for search)
+fn parse_query(command: &str, args: impl Iterator<Item = String>) -> Result<Command> {
+    let mut words = Vec::new();
+    let mut json = false;
+    let mut forms = false;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--forms" if command == "search" => forms = true,
+            flag if flag.starts_with('-') => bail!("unknown option '{}'", flag),
+            _ => words.push(arg.to_lowercase()),
+        }
+    }
+    // API names use dashes: "pokedex move close combat" -> close-combat
+    let name = || -> Result<String> {
+        if words.is_empty() {
+            bail!("'{}' needs a name\n\n{}", command, USAGE);
+        }
+        Ok(words.join("-"))
+    };
+    Ok(match command {
+        "show" => Command::Show {
+            name: name()?,
+            json,
+        },
+        "move" => Command::Move {
+            name: name()?,
+            json,
+        },
+        "type" => {
+            let types: Vec<String> = words
+                .iter()
+                .flat_map(|w| w.split('/'))
+                .filter(|t| !t.is_empty())
+                .map(String::from)
+                .collect();
+            if types.is_empty() || types.len() > 2 {
+                bail!("'type' takes one or two types, like fire or fire/flying");
+            }
+            if let Some(t) = types.iter().find(|t| !ALL_TYPES.contains(&t.as_str())) {
+                bail!("unknown type '{}'", t);
+            }
+            Command::Type { types, json }
+        }
+        "team" => match words.first().map(String::as_str) {
+            None | Some("list") => Command::TeamList { json },
+            Some(other) => bail!("unknown team command '{}'", other),
+        },
+        _ => {
+            let query = SearchQuery::parse(&words.join(" ")).map_err(anyhow::Error::msg)?;
+            Command::Search { query, json, forms }
+        }
+    })
+}
+
+fn or_dash(v: Option<u32>) -> String {
+    v.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string())
+}
+
+fn format_show(detail: &PokemonDetail, species: Option<&PokemonSpecies>) -> String {
+    let entry = DexEntry::from_detail(detail);
+    let number = species.map(|s| s.id).unwrap_or(detail.id);
+    let mut out = format!("#{:03} {}\n", number, capitalize(&detail.name));
+    out.push_str(&format!("Types:      {}\n", entry.types.join("/")));
+    if let Some(gen) = species.and_then(|s| s.generation_number()) {
+        out.push_str(&format!("Generation: {}\n", gen));
+    }
+    out.push_str(&format!(
+        "Height:     {:.1} m\n",
+        detail.height as f64 / 10.0
+    ));
+    out.push_str(&format!(
+        "Weight:     {:.1} kg\n",
+        detail.weight as f64 / 10.0
+    ));
+    let abilities: Vec<String> = detail
+        .abilities
+        .iter()
+        .map(|a| {
+            if a.is_hidden {
+                format!("{} (hidden)", a.ability.name)
+            } else {
+                a.ability.name.clone()
+            }
+        })
+        .collect();
+    out.push_str(&format!("Abilities:  {}\n", abilities.join(", ")));
+    out.push_str("Base stats:\n");
+    let stats = [
+        ("HP", entry.hp),
+        ("Atk", entry.attack),
+        ("Def", entry.defense),
+        ("SpA", entry.special_attack),
+        ("SpD", entry.special_defense),
+        ("Spe", entry.speed),
+    ];
+    for (label, value) in stats {
+        let bar = "█".repeat((value.unwrap_or(0) / 10) as usize);
+        out.push_str(&format!("  {:<4}{:>4} {}\n", label, or_dash(value), bar));
+    }
+    out.push_str(&format!("  {:<4}{:>4}\n", "BST", or_dash(entry.total)));
+    out
+}
+
+/// Every attacking type grouped by its multiplier against `infos`
+fn type_matchups(infos: &[TypeInfo]) -> Vec<(&'static str, f64)> {
+    ALL_TYPES
+        .iter()
+        .map(|&attacker| (attacker, defensive_multiplier(infos, attacker)))
+        .collect()
+}
+
+fn super_effective_against(infos: &[TypeInfo]) -> Vec<String> {
+    let mut targets: Vec<String> = Vec::new();
+    for info in infos {
+        for t in &info.damage_relations.double_damage_to {
+            if !targets.contains(&t.name) {
+                targets.push(t.name.clone());
+            }
+        }
+    }
+    targets
+}
+
+fn format_type(infos: &[TypeInfo]) -> String {
+    let matchups = type_matchups(infos);
+    let list = |keep: &dyn Fn(f64) -> bool, show_factor: bool| -> String {
+        let mut selected: Vec<&(&str, f64)> = matchups.iter().filter(|(_, m)| keep(*m)).collect();
+        selected.sort_by(|a, b| b.1.total_cmp(&a.1));
+        if selected.is_empty() {
+            return "-".to_string();
+        }
+        selected
+            .iter()
+            .map(|(t, m)| {
+                if show_factor {
+                    format!("{} ×{}", t, m)
+                } else {
+                    t.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let names: Vec<&str> = infos.iter().map(|t| t.name.as_str()).collect();
+    let mut out = format!("{}\n", names.join("/"));
+    out.push_str(&format!("Weak to:     {}\n", list(&|m| m > 1.0, true)));
+    out.push_str(&format!(
+        "Resists:     {}\n",
+        list(&|m| m > 0.0 && m < 1.0, true)
+    ));
+    out.push_str(&format!("Immune to:   {}\n", list(&|m| m == 0.0, false)));
+    let targets = super_effective_against(infos);
+    let targets = if targets.is_empty() {
+        "-".to_string()
+    } else {
+        targets.join(", ")
+    };
+    out.push_str(&format!("Strong vs:   {}\n", targets));
+    out
+}
+
+pub(crate) fn type_json(infos: &[TypeInfo]) -> serde_json::Value {
+    let matchups: serde_json::Map<String, serde_json::Value> = type_matchups(infos)
+        .into_iter()
+        .map(|(t, m)| (t.to_string(), json!(m)))
+        .collect();
+    json!({
+        "types": infos.iter().map(|t| t.name.clone()).collect::<Vec<_>>(),
+        "damage_taken": matchups,
+        "super_effective_against": super_effective_against(infos),
+    })
+}
+
+fn format_move(mv: &MoveDetail) -> String {
+    let class = mv
+        .damage_class
+        .as_ref()
+        .map(|c| c.name.as_str())
+        .unwrap_or("-");
+    format!(
+        "{}\nType:      {}\nCategory:  {}\nPower:     {}\nAccuracy:  {}\nPP:        {}\n",
+        capitalize(&mv.name.replace('-', " ")),
+        mv.move_type.name,
+        class,
+        or_dash(mv.power),
+        or_dash(mv.accuracy),
+        or_dash(mv.pp),
+    )
+}
+
+pub(crate) fn move_json(mv: &MoveDetail) -> serde_json::Value {
+    json!({
+        "id": mv.id,
+        "name": mv.name,
+        "type": mv.move_type.name,
+        "category": mv.damage_class.as_ref().map(|c| c.name.clone()),
+        "power": mv.power,
+        "accuracy": mv.accuracy,
+        "pp": mv.pp,
+    })
+}
+
+fn format_teams(data: &TeamData) -> String {
+    let mut out = String::new();
+    for team in &data.teams {
+        out.push_str(&format!("{} ({}/6)\n", team.name, team.members.len()));
+        for member in &team.members {
+            let moves: Vec<&str> = member.moves.iter().map(|m| m.name.as_str()).collect();
+            out.push_str(&format!(
+                "  {:<14} {:<18} {}\n",
+                member.pokemon_name,
+                member.types.join("/"),
+                moves.join(", ")
+            ));
+        }
+    }
+    out
+}
+
+fn format_results(entries: &[DexEntry]) -> String {
+    let mut out = String::new();
+    for e in entries {
+        out.push_str(&format!(
+            "#{:04}  {:<24} {:<18} BST {}\n",
+            e.id,
+            e.name,
+            e.types.join("/"),
+            or_dash(e.total)
+        ));
+    }
+    out
+}
+
+/// List rows matching the query, in relevance order when there is a name query
+pub(crate) async fn search(
+    client: Arc<ApiClient>,
+    query: &SearchQuery,
+    forms: bool,
+) -> Result<Vec<DexEntry>> {
+    let candidates = search_candidates(&client, query, forms).await?;
+    let (mut entries, incomplete) = export::fetch_entries(client, candidates).await;
+    // A row without its detail has no types to match or show
+    if let Some(note) = export::incomplete_note(incomplete) {
+        bail!("couldn't finish the search: {}", note);
+    }
+    entries.retain(|e| query.matches_types(&e.types));
+    Ok(entries)
+}
+
+/// Pokémon that can match the query, found from the list, generation and
+/// type resources so that only these need their details fetched
+async fn search_candidates(
+    client: &ApiClient,
+    query: &SearchQuery,
+    forms: bool,
+) -> Result<Vec<PokemonSummary>> {
+    let list = client.fetch_pokemon_list().await?;
+    let mut summaries: Vec<PokemonSummary> = summaries_from_list(&list)
+        .into_iter()
+        .filter(|s| forms || !s.is_form())
+        .collect();
+    if let Some(gen) = query.generation {
+        let generation = client
+            .fetch_generation(gen)
+            .await
+            .with_context(|| format!("couldn't load generation {}", gen))?;
+        let species: HashSet<u32> = generation
+            .pokemon_species
+            .iter()
+            .filter_map(|s| s.id())
+            .collect();
+        // Forms take their species' generation; unresolved forms match none
+        summaries.retain(|s| s.known_species_id().is_some_and(|id| species.contains(&id)));
+    }
+    for name in &query.types {
+        let info = client
+            .fetch_type_info(name)
+            .await
+            .with_context(|| format!("couldn't load type '{}'", name))?;
+        let ids: HashSet<u32> = info.pokemon.iter().filter_map(|p| p.pokemon.id()).collect();
+        summaries.retain(|s| ids.contains(&s.id));
+    }
+    Ok(rank_by_query(summaries.iter().collect(), &query.text)
+        .into_iter()
+        .cloned()
+        .collect())
+}
+
+fn pretty(value: &impl serde::Serialize) -> Result<String> {
+    Ok(format!("{}\n", serde_json::to_string_pretty(value)?))
+}
+
+/// Print to stdout; a closed pipe (`pokedex export | head`) isn't an error
+fn write_stdout(text: &str) -> Result<()> {
+    use std::io::Write;
+    match std::io::stdout().lock().write_all(text.as_bytes()) {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// Run a non-interactive command
+pub async fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Tui => Ok(()),
+        Command::Help => write_stdout(&format!("{}\n", USAGE)),
+        Command::Export {
+            names,
+            format,
+            output,
+            forms,
+        } => {
+            let client = Arc::new(ApiClient::new());
+            let entries = if names.is_empty() {
+                let list = client.fetch_pokemon_list().await?;
+                let summaries = summaries_from_list(&list)
+                    .into_iter()
+                    .filter(|s| forms || !s.is_form())
+                    .collect();
+                let (entries, incomplete) = export::fetch_entries(client, summaries).await;
+                if let Some(note) = export::incomplete_note(incomplete) {
+                    eprintln!("Warning: {}", note);
+                }
+                entries
+            } else {
+                let mut entries = Vec::new();
+                for name in &names {
+                    let detail = client
+                        .fetch_pokemon_detail(name)
+                        .await
+                        .with_context(|| format!("couldn't load '{}'", name))?;
+                    entries.push(DexEntry::from_detail(&detail));
+                }
+                entries
+            };
+            let text = export::table::render(&entries, format)?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, text)
+                        .with_context(|| format!("couldn't write {}", path.display()))?;
+                    eprintln!("Wrote {} entries to {}", entries.len(), path.display());
+                }
+                None => write_stdout(&text)?,
+            }
+            Ok(())
+        }
+        Command::Show { name, json } => {
+            let client = ApiClient::new();
+            let detail = client
+                .fetch_pokemon_detail(&name)
+                .await
+                .with_context(|| format!("couldn't load Pokémon '{}'", name))?;
+            if json {
+                return write_stdout(&pretty(&DexEntry::from_detail(&detail))?);
+            }
+            let species = match detail.species.as_ref() {
+                Some(s) => client.fetch_species(&s.name).await.ok(),
+                None => None,
+            };
+            write_stdout(&format_show(&detail, species.as_ref()))
+        }
+        Command::Type { types, json } => {
+            let client = ApiClient::new();
+            let mut infos = Vec::new();
+            for t in &types {
+                infos.push(client.fetch_type_info(t).await?);
+            }
+            if json {
+                write_stdout(&pretty(&type_json(&infos))?)
+            } else {
+                write_stdout(&format_type(&infos))
+            }
+        }
+        Command::Move { name, json } => {
+            let client = ApiClient::new();
+            let mv = client
+                .fetch_move_detail(&name)
+                .await
+                .with_context(|| format!("couldn't load move '{}'", name))?;
+            if json {
+                write_stdout(&pretty(&move_json(&mv))?)
+            } else {
+                write_stdout(&format_move(&mv))
+            }
+        }
+        Command::TeamList { json } => {
+            let (data, error) = TeamData::load();
+            if let Some(error) = error {
+                bail!(error);
+            }
+            if json {
+                write_stdout(&pretty(&data.teams)?)
+            } else {
+                write_stdout(&format_teams(&data))
+            }
+        }
+        Command::CollectionExport { format, output } => {
+            let collection = Collection::read_from(&Collection::file_path())?;
+            let text = match format {
+                ExportFormat::Csv => collection.to_csv(),
+                _ => collection.to_json()?,
+            };
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, text)
+                        .with_context(|| format!("couldn't write {}", path.display()))?;
+                    eprintln!("Wrote the collection to {}", path.display());
+                }
+                None => write_stdout(&text)?,
+            }
+            Ok(())
+        }
+        Command::CollectionImport { path, format } => {
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("couldn't read {}", path.display()))?;
+            let mut collection = Collection::read_from(&Collection::file_path())?;
+            let count = match format {
+                ExportFormat::Csv => collection.import_csv(&text),
+                _ => collection.import_json(&text),
+            }
+            .with_context(|| format!("couldn't import {}", path.display()))?;
+            collection.save().context("couldn't save the collection")?;
+            eprintln!("Imported {} marks from {}", count, path.display());
+            Ok(())
+        }
+        Command::Serve { addr } => {
+            if let Err(error) = TeamData::move_from_cache_dir() {
+                eprintln!("{}", error);
+            }
+            eprintln!("{}\n", server::ROUTES);
+            server::serve(addr, server::Server::new(TeamData::file_path())).await
+        }
+        Command::Search { query, json, forms } => {
+            let entries = search(Arc::new(ApiClient::new()), &query, forms).await?;
+            if json {
+                write_stdout(&pretty(&entries)?)
+            } else {
+                write_stdout(&format_results(&entries))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_no_args_starts_tui() {
+        assert_eq!(parse(args("")).unwrap(), Command::Tui);
+        assert_eq!(parse(args("--help")).unwrap(), Command::Help);
+        assert!(parse(args("catch pikachu")).is_err());
+    }
+
+    #[test]
+    fn test_parse_export() {
+        assert_eq!(
+            parse(args("export Pikachu eevee --format csv")).unwrap(),
+            Command::Export {
+                names: vec!["pikachu".to_string(), "eevee".to_string()],
+                format: ExportFormat::Csv,
+                output: None,
+                forms: false,
+            }
+        );
+        // Format inferred from the output extension
+        let Command::Export { format, output, .. } =
+            parse(args("export -o dex.json --forms")).unwrap()
+        else {
+            panic!("expected export");
+        };
+        assert_eq!(format, ExportFormat::Json);
+        assert_eq!(output, Some(PathBuf::from("dex.json")));
+        assert!(parse(args("export --format xml")).is_err());
+        assert!(parse(args("export --bogus")).is_err());
+    }
+
+    #[test]
+    fn test_parse_query_commands() {
+        assert_eq!(
+            parse(args("show Pikachu --json")).unwrap(),
+            Command::Show {
+                name: "pikachu".to_string(),
+                json: true
+            }
+        );
+        assert_eq!(
+            parse(args("move close combat")).unwrap(),
+            Command::Move {
+                name: "close-combat".to_string(),
+                json: false
+            }
+        );
+        assert_eq!(
+            parse(args("type fire/flying")).unwrap(),
+            Command::Type {
+                types: vec!["fire".to_string(), "flying".to_string()],
+                json: false
+            }
+        );
+        assert_eq!(
+            parse(args("team")).unwrap(),
+            Command::TeamList { json: false }
+        );
+        let Command::Search { query, forms, .. } =
+            parse(args("search type:water gen:3 --forms")).unwrap()
+        else {
+            panic!("expected search");
+        };
+        assert_eq!(query.types, vec!["water"]);
+        assert_eq!(query.generation, Some(3));
+        assert!(forms);
+
+        assert!(parse(args("show")).is_err());
+        assert!(parse(args("type lava")).is_err());
+        assert!(parse(args("type fire water grass")).is_err());
+        assert!(parse(args("team delete")).is_err());
+        assert!(parse(args("show pikachu --forms")).is_err());
+        assert!(parse(args("search gen:12")).is_err());
+    }
+
+    #[test]
+    fn test_parse_serve() {
+        assert_eq!(
+            parse(args("serve")).unwrap(),
+            Command::Serve {
+                addr: "127.0.0.1:8765".parse().unwrap()
+            }
+        );
+        assert_eq!(
+            parse(args("serve --port 9000 --host ::1")).unwrap(),
+            Command::Serve {
+                addr: "[::1]:9000".parse().unwrap()
+            }
+        );
+        assert!(parse(args("serve --host 0.0.0.0")).is_err());
+        assert!(parse(args("serve --host 192.168.1.2")).is_err());
+        assert!(parse(args("serve --port http")).is_err());
+    }
+
+    #[test]
+    fn test_parse_collection() {
+        assert_eq!(
+            parse(args("collection export")).unwrap(),
+            Command::CollectionExport {
+                format: ExportFormat::Json,
+                output: None,
+            }
+        );
+        assert_eq!(
+            parse(args("collection export -o dex.csv")).unwrap(),
+            Command::CollectionExport {
+                format: ExportFormat::Csv,
+                output: Some("dex.csv".into()),
+            }
+        );
+        assert_eq!(
+            parse(args("collection import dex.txt --format csv")).unwrap(),
+            Command::CollectionImport {
+                path: "dex.txt".into(),
+                format: ExportFormat::Csv,
+            }
+        );
+        assert!(parse(args("collection import")).is_err());
+        assert!(parse(args("collection export --format markdown")).is_err());
+        assert!(parse(args("collection sync")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = ApiClient::new_with_cache_dir(dir.path().to_path_buf());
+        let cache = |url: &str, body: String| {
+            std::fs::write(dir.path().join(ApiClient::url_to_cache_key(url)), body).unwrap();
+        };
+        let resources = |kind: &str, entries: &[(&str, u32)]| -> Vec<String> {
+            entries
+                .iter()
+                .map(|(name, id)| {
+                    format!(
+                        r#"{{"name": "{}", "url": "https://pokeapi.co/api/v2/{}/{}/"}}"#,
+                        name, kind, id
+                    )
+                })
+                .collect()
+        };
+        let pokemon = [
+            ("charmander", 4),
+            ("charizard", 6),
+            ("chikorita", 152),
+            ("cyndaquil", 155),
+            ("charizard-mega-x", 10034),
+        ];
+        cache(
+            "https://pokeapi.co/api/v2/pokemon?limit=10000",
+            format!(
+                r#"{{"count": 5, "results": [{}]}}"#,
+                resources("pokemon", &pokemon).join(",")
+            ),
+        );
+        cache(
+            "https://pokeapi.co/api/v2/generation/1",
+            format!(
+                r#"{{"id": 1, "pokemon_species": [{}]}}"#,
+                resources("pokemon-species", &[("charmander", 4), ("charizard", 6)]).join(",")
+            ),
+        );
+        let fire: Vec<String> = resources(
+            "pokemon",
+            &[
+                ("charmander", 4),
+                ("charizard", 6),
+                ("cyndaquil", 155),
+                ("charizard-mega-x", 10034),
+            ],
+        )
+        .into_iter()
+        .map(|p| format!(r#"{{"slot": 1, "pokemon": {}}}"#, p))
+        .collect();
+        cache(
+            "https://pokeapi.co/api/v2/type/fire",
+            format!(
+                r#"{{"id": 10, "name": "fire", "damage_relations": {{
+                    "double_damage_to": [], "half_damage_to": [], "no_damage_to": [],
+                    "double_damage_from": [], "half_damage_from": [], "no_damage_from": []}},
+                    "pokemon": [{}]}}"#,
+                fire.join(",")
+            ),
+        );
+
+        let ids = |candidates: Vec<PokemonSummary>| -> Vec<u32> {
+            candidates.iter().map(|s| s.id).collect()
+        };
+        let query = SearchQuery::parse("type:fire").unwrap();
+        let candidates = search_candidates(&client, &query, false).await.unwrap();
+        assert_eq!(ids(candidates), vec![4, 6, 155]);
+
+        let query = SearchQuery::parse("char type:fire gen:1").unwrap();
+        let candidates = search_candidates(&client, &query, true).await.unwrap();
+        assert_eq!(ids(candidates), vec![6, 4, 10034]);
+    }
+
+    fn type_info(json: &str) -> TypeInfo {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_format_type() {
+        let ground = type_info(
+            r#"{"id": 5, "name": "ground", "damage_relations": {
+                "double_damage_to": [{"name": "fire", "url": ""}],
+                "half_damage_to": [], "no_damage_to": [],
+                "double_damage_from": [{"name": "water", "url": ""}, {"name": "grass", "url": ""}],
+                "half_damage_from": [{"name": "rock", "url": ""}],
+                "no_damage_from": [{"name": "electric", "url": ""}]}}"#,
+        );
+        let text = format_type(std::slice::from_ref(&ground));
+        assert!(text.starts_with("ground\n"));
+        assert!(text.contains("Weak to:     water ×2, grass ×2\n"));
+        assert!(text.contains("Resists:     rock ×0.5\n"));
+        assert!(text.contains("Immune to:   electric\n"));
+        assert!(text.contains("Strong vs:   fire\n"));
+        let value = type_json(&[ground]);
+        assert_eq!(value["damage_taken"]["electric"], 0.0);
+        assert_eq!(value["damage_taken"]["normal"], 1.0);
+    }
+
+    #[test]
+    fn test_format_show() {
+        let json = r#"{
+            "id": 25, "name": "pikachu", "height": 4, "weight": 60,
+            "types": [{"slot": 1, "type": {"name": "electric", "url": ""}}],
+            "stats": [{"base_stat": 35, "stat": {"name": "hp", "url": ""}}],
+            "abilities": [{"ability": {"name": "lightning-rod", "url": ""}, "is_hidden": true}],
+            "moves": [],
+            "sprites": {"front_default": null}
+        }"#;
+        let detail: PokemonDetail = serde_json::from_str(json).unwrap();
+        let text = format_show(&detail, None);
+        assert!(text.starts_with("#025 Pikachu\n"));
+        assert!(text.contains("Abilities:  lightning-rod (hidden)\n"));
+        assert!(text.contains("  HP    35 ███\n"));
+        assert!(text.contains("  Atk    -"));
+    }
+}
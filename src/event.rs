@@ -1,23 +1,41 @@
 use anyhow::Result;
-use crossterm::event::{self, Event as CrosstermEvent, KeyEvent};
-use std::time::Duration;
+use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
-use crate::models::pokemon::{MoveDetail, PokemonDetail, PokemonSummary};
+use crate::models::pokemon::{
+    EvolutionChain, MoveDetail, PokemonDetail, PokemonSpecies, PokemonSummary, SpriteVariant,
+};
 use crate::models::type_data::TypeInfo;
+use crate::sprite::animation::AnimationFrame;
+
+/// How often `AppEvent::Tick` fires (drives sprite animation)
+pub const TICK_RATE: Duration = Duration::from_millis(100);
 
 /// All events the app can receive
 #[derive(Debug)]
 pub enum AppEvent {
     Key(KeyEvent),
+    Mouse(MouseEvent),
+    Resize(u16, u16), // columns, rows
     Tick,
     // API responses
     PokemonListLoaded(Vec<PokemonSummary>),
-    PokemonTypesUpdated(Vec<(u32, Vec<String>)>), // batch of (id, types)
+    PokemonSummariesUpdated(Vec<PokemonSummary>), // batch filled in with types and stats
+    PokemonSummariesFinished,                     // background batch loader is done
+    GenerationsLoaded(HashMap<u32, u8>, Vec<u8>), // species id -> generation, generations that failed
     PokemonDetailLoaded(Box<PokemonDetail>),
-    SpriteLoaded(u32, Vec<u8>), // pokemon_id, png bytes
+    SpeciesLoaded(Box<PokemonSpecies>),
+    EvolutionChainLoaded(Box<EvolutionChain>),
+    SpriteLoaded(u32, SpriteVariant, Vec<u8>), // pokemon_id, variant, png bytes
+    AnimationLoaded(u32, SpriteVariant, Vec<AnimationFrame>),
+    ThumbnailLoaded(u32, Vec<u8>), // pokemon_id, png bytes
     TypesLoaded(Vec<TypeInfo>),
     MovesLoaded(Vec<MoveDetail>),
+    AbilityLoaded(String, Option<String>), // ability name, short effect (None on failure)
+    ExportFinished(Result<PathBuf, String>, usize), // written file or error, incomplete entries
     ApiError(String),
 }
 
@@ -31,11 +49,27 @@ impl EventHandler {
         let (tx, rx) = mpsc::unbounded_channel();
 
         let poll_tx = tx.clone();
-        // Input polling thread
-        std::thread::spawn(move || loop {
-            if event::poll(Duration::from_millis(50)).unwrap_or(false) {
-                if let Ok(CrosstermEvent::Key(key)) = event::read() {
-                    if poll_tx.send(AppEvent::Key(key)).is_err() {
+        // Input polling thread, also the tick source
+        std::thread::spawn(move || {
+            let mut last_tick = Instant::now();
+            loop {
+                let timeout = TICK_RATE.saturating_sub(last_tick.elapsed());
+                if event::poll(timeout).unwrap_or(false) {
+                    let forwarded = match event::read() {
+                        Ok(CrosstermEvent::Key(key)) => Some(AppEvent::Key(key)),
+                        Ok(CrosstermEvent::Mouse(mouse)) => Some(AppEvent::Mouse(mouse)),
+                        Ok(CrosstermEvent::Resize(w, h)) => Some(AppEvent::Resize(w, h)),
+                        _ => None,
+                    };
+                    if let Some(ev) = forwarded {
+                        if poll_tx.send(ev).is_err() {
+                            break;
+                        }
+                    }
+                }
+                if last_tick.elapsed() >= TICK_RATE {
+                    last_tick = Instant::now();
+                    if poll_tx.send(AppEvent::Tick).is_err() {
                         break;
                     }
                 }
@@ -145,6 +179,7 @@ mod tests {
             id: 1,
             name: "bulbasaur".to_string(),
             types: vec![],
+            ..Default::default()
         }];
         tx.send(AppEvent::PokemonListLoaded(summaries.clone()))
             .unwrap();
@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+
+use image::imageops::{self, FilterType};
+use image::{Rgba, RgbaImage};
+use ratatui::style::Color;
+
+use super::font::{draw_text, text_width, GLYPH_HEIGHT};
+use crate::models::pokemon::PokemonDetail;
+use crate::models::team::Team;
+use crate::ui::{capitalize, type_color};
+
+const CARD_WIDTH: u32 = 640;
+const MARGIN: u32 = 16;
+const SPRITE_BOX: u32 = 96;
+const ROW_HEIGHT: u32 = SPRITE_BOX + 16;
+
+const BACKGROUND: Rgba<u8> = Rgba([28, 28, 40, 255]);
+const PANEL: Rgba<u8> = Rgba([40, 40, 60, 255]);
+const TEXT: Rgba<u8> = Rgba([240, 240, 240, 255]);
+const MUTED: Rgba<u8> = Rgba([140, 140, 150, 255]);
+const TITLE: Rgba<u8> = Rgba([230, 60, 60, 255]);
+
+fn rgba(color: Color) -> Rgba<u8> {
+    match color {
+        Color::Rgb(r, g, b) => Rgba([r, g, b, 255]),
+        _ => MUTED,
+    }
+}
+
+fn fill_rect(img: &mut RgbaImage, x: u32, y: u32, w: u32, h: u32, color: Rgba<u8>) {
+    for py in y..(y + h).min(img.height()) {
+        for px in x..(x + w).min(img.width()) {
+            img.put_pixel(px, py, color);
+        }
+    }
+}
+
+/// Type badge like the TUI's; returns its width
+fn draw_badge(img: &mut RgbaImage, x: u32, y: u32, type_name: &str) -> u32 {
+    let label = type_name.to_uppercase();
+    let w = text_width(&label, 2) + 12;
+    fill_rect(
+        img,
+        x,
+        y,
+        w,
+        GLYPH_HEIGHT * 2 + 8,
+        rgba(type_color(type_name)),
+    );
+    draw_text(img, x + 6, y + 4, &label, 2, TEXT);
+    w
+}
+
+/// Scale a sprite up by a whole factor (crisp pixels) to fit `size`, centred in the box
+fn draw_sprite(img: &mut RgbaImage, x: u32, y: u32, size: u32, png: &[u8]) {
+    let Ok(sprite) = image::load_from_memory(png) else {
+        return;
+    };
+    let sprite = sprite.to_rgba8();
+    let (w, h) = sprite.dimensions();
+    if w == 0 || h == 0 {
+        return;
+    }
+    let scale = (size / w.max(h)).max(1);
+    let scaled = if scale > 1 {
+        imageops::resize(&sprite, w * scale, h * scale, FilterType::Nearest)
+    } else if w > size || h > size {
+        let f = size as f64 / w.max(h) as f64;
+        imageops::resize(
+            &sprite,
+            ((w as f64 * f) as u32).max(1),
+            ((h as f64 * f) as u32).max(1),
+            FilterType::Triangle,
+        )
+    } else {
+        sprite
+    };
+    let ox = x + size.saturating_sub(scaled.width()) / 2;
+    let oy = y + size.saturating_sub(scaled.height()) / 2;
+    imageops::overlay(img, &scaled, ox as i64, oy as i64);
+}
+
+/// The team name at scale 3 if it fits in `max_width`, else scale 2,
+/// shortened with "..." if it's still too long
+fn fit_title(name: &str, max_width: u32) -> (String, u32) {
+    for scale in [3, 2] {
+        if text_width(name, scale) <= max_width {
+            return (name.to_string(), scale);
+        }
+    }
+    let mut chars: Vec<char> = name.chars().collect();
+    loop {
+        let title = format!("{}...", chars.iter().collect::<String>().trim_end());
+        if chars.is_empty() || text_width(&title, 2) <= max_width {
+            return (title, 2);
+        }
+        chars.pop();
+    }
+}
+
+/// Team card: one row per member with sprite, name, types and moves.
+/// `sprites` maps Pokémon id to PNG bytes; members without one get an empty box.
+pub fn team_card(team: &Team, sprites: &HashMap<u32, Vec<u8>>) -> RgbaImage {
+    let header = MARGIN * 2 + GLYPH_HEIGHT * 3;
+    let rows = team.members.len().max(1) as u32;
+    let height = header + rows * ROW_HEIGHT + MARGIN;
+    let mut img = RgbaImage::from_pixel(CARD_WIDTH, height, BACKGROUND);
+
+    let count = format!("{}/6", team.members.len());
+    let count_x = CARD_WIDTH - MARGIN - text_width(&count, 2);
+    let (title, scale) = fit_title(&team.name, count_x - 2 * MARGIN);
+    // A smaller title sits level with the count
+    let title_y = MARGIN + (3 - scale) * GLYPH_HEIGHT / 2;
+    draw_text(&mut img, MARGIN, title_y, &title, scale, TITLE);
+    draw_text(
+        &mut img,
+        count_x,
+        MARGIN + GLYPH_HEIGHT / 2,
+        &count,
+        2,
+        MUTED,
+    );
+
+    if team.members.is_empty() {
+        draw_text(&mut img, MARGIN, header + 8, "(empty team)", 2, MUTED);
+        return img;
+    }
+
+    for (i, member) in team.members.iter().enumerate() {
+        let y = header + i as u32 * ROW_HEIGHT;
+        fill_rect(
+            &mut img,
+            MARGIN,
+            y,
+            CARD_WIDTH - 2 * MARGIN,
+            ROW_HEIGHT - 8,
+            PANEL,
+        );
+        if let Some(png) = sprites.get(&member.pokemon_id) {
+            draw_sprite(&mut img, MARGIN + 4, y + 4, SPRITE_BOX - 8, png);
+        }
+
+        let text_x = MARGIN + SPRITE_BOX + 8;
+        let name = capitalize(&member.pokemon_name.replace('-', " "));
+        draw_text(&mut img, text_x, y + 12, &name, 2, TEXT);
+        let mut badge_x = text_x;
+        for t in &member.types {
+            badge_x += draw_badge(&mut img, badge_x, y + 40, t) + 6;
+        }
+
+        let moves_x = 380;
+        if member.moves.is_empty() {
+            draw_text(&mut img, moves_x, y + 12, "No moves set", 2, MUTED);
+        }
+        for (j, mv) in member.moves.iter().take(4).enumerate() {
+            let label = mv.name.replace('-', " ");
+            let my = y + 8 + j as u32 * 22;
+            fill_rect(
+                &mut img,
+                moves_x,
+                my + 2,
+                4,
+                GLYPH_HEIGHT * 2 - 4,
+                rgba(type_color(&mv.move_type)),
+            );
+            draw_text(&mut img, moves_x + 10, my, &label, 2, TEXT);
+        }
+    }
+    img
+}
+
+const STAT_LABELS: [(&str, &str); 6] = [
+    ("hp", "HP"),
+    ("attack", "ATK"),
+    ("defense", "DEF"),
+    ("special-attack", "SPA"),
+    ("special-defense", "SPD"),
+    ("speed", "SPE"),
+];
+
+fn stat_color(value: u32) -> Rgba<u8> {
+    match value {
+        0..=49 => Rgba([240, 80, 80, 255]),
+        50..=79 => Rgba([240, 170, 60, 255]),
+        80..=109 => Rgba([220, 220, 70, 255]),
+        _ => Rgba([110, 210, 90, 255]),
+    }
+}
+
+/// Detail card: large sprite, number, name, types, size, abilities and base stats
+pub fn detail_card(detail: &PokemonDetail, sprite: Option<&[u8]>) -> RgbaImage {
+    let sprite_box = SPRITE_BOX * 2;
+    let height = MARGIN * 2 + sprite_box + 8 + STAT_LABELS.len() as u32 * 22;
+    let mut img = RgbaImage::from_pixel(CARD_WIDTH, height, BACKGROUND);
+
+    fill_rect(&mut img, MARGIN, MARGIN, sprite_box, sprite_box, PANEL);
+    if let Some(png) = sprite {
+        draw_sprite(&mut img, MARGIN, MARGIN, sprite_box, png);
+    }
+
+    let x = MARGIN * 2 + sprite_box;
+    let number = detail
+        .species
+        .as_ref()
+        .and_then(|s| s.id())
+        .unwrap_or(detail.id);
+    draw_text(&mut img, x, MARGIN, &format!("#{:03}", number), 2, MUTED);
+    let name = capitalize(&detail.name.replace('-', " "));
+    draw_text(&mut img, x, MARGIN + 20, &name, 3, TEXT);
+
+    let mut badge_x = x;
+    for slot in &detail.types {
+        badge_x += draw_badge(&mut img, badge_x, MARGIN + 50, &slot.type_info.name) + 6;
+    }
+
+    let size = format!(
+        "{:.1} m  {:.1} kg",
+        detail.height as f64 / 10.0,
+        detail.weight as f64 / 10.0
+    );
+    draw_text(&mut img, x, MARGIN + 84, &size, 2, TEXT);
+    for (i, a) in detail.abilities.iter().take(3).enumerate() {
+        let mut label = a.ability.name.replace('-', " ");
+        if a.is_hidden {
+            label.push_str(" (H)");
+        }
+        let color = if a.is_hidden { MUTED } else { TEXT };
+        draw_text(&mut img, x, MARGIN + 110 + i as u32 * 20, &label, 2, color);
+    }
+
+    let stats_y = MARGIN + sprite_box + 8;
+    let bar_x = MARGIN + 100;
+    let bar_max = CARD_WIDTH - MARGIN - bar_x;
+    for (i, (key, label)) in STAT_LABELS.iter().enumerate() {
+        let y = stats_y + i as u32 * 22;
+        let value = detail
+            .stats
+            .iter()
+            .find(|s| s.stat.name == *key)
+            .map(|s| s.base_stat)
+            .unwrap_or(0);
+        draw_text(&mut img, MARGIN, y, label, 2, MUTED);
+        draw_text(&mut img, MARGIN + 50, y, &value.to_string(), 2, TEXT);
+        fill_rect(&mut img, bar_x, y + 2, bar_max, 10, PANEL);
+        let bar = bar_max * value.min(255) / 255;
+        fill_rect(&mut img, bar_x, y + 2, bar, 10, stat_color(value));
+    }
+    img
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::team::{TeamMember, TeamMove};
+    use image::{DynamicImage, ImageFormat};
+    use std::io::Cursor;
+
+    fn sprite_png() -> Vec<u8> {
+        let img = RgbaImage::from_pixel(8, 8, Rgba([255, 0, 255, 255]));
+        let mut out = Cursor::new(Vec::new());
+        DynamicImage::ImageRgba8(img)
+            .write_to(&mut out, ImageFormat::Png)
+            .unwrap();
+        out.into_inner()
+    }
+
+    fn team(members: usize) -> Team {
+        Team {
+            name: "Rain".to_string(),
+            members: (0..members as u32)
+                .map(|i| TeamMember {
+                    pokemon_id: i + 1,
+                    pokemon_name: "pelipper".to_string(),
+                    types: vec!["water".to_string(), "flying".to_string()],
+                    moves: vec![TeamMove {
+                        name: "hurricane".to_string(),
+                        move_type: "flying".to_string(),
+                        power: Some(110),
+                    }],
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_team_card_size_grows_with_members() {
+        let sprites = HashMap::from([(1, sprite_png())]);
+        let one = team_card(&team(1), &sprites);
+        let six = team_card(&team(6), &sprites);
+        assert_eq!(one.width(), CARD_WIDTH);
+        assert_eq!(six.height() - one.height(), 5 * ROW_HEIGHT);
+        // Empty teams still produce a card
+        assert!(team_card(&team(0), &sprites).height() > 0);
+    }
+
+    #[test]
+    fn test_team_card_long_title_fits() {
+        assert_eq!(fit_title("Rain", 400), ("Rain".to_string(), 3));
+        let (title, scale) = fit_title(&"Rain Dance ".repeat(3), 400);
+        assert_eq!(scale, 2);
+        assert!(!title.ends_with("..."));
+
+        let mut long = team(1);
+        long.name =
+            "The Extremely Long Rain Dance And Harsh Sunlight Sweepers Team Version 2".to_string();
+        let img = team_card(&long, &HashMap::new());
+        let count_x = CARD_WIDTH - MARGIN - text_width("1/6", 2);
+        let title_right = img
+            .enumerate_pixels()
+            .filter(|(_, _, p)| **p == TITLE)
+            .map(|(x, _, _)| x)
+            .max()
+            .unwrap();
+        assert!(title_right < count_x - MARGIN);
+        let (title, _) = fit_title(&long.name, count_x - 2 * MARGIN);
+        assert!(title.ends_with("..."));
+    }
+
+    #[test]
+    fn test_team_card_draws_sprite() {
+        let sprites = HashMap::from([(1, sprite_png())]);
+        let img = team_card(&team(1), &sprites);
+        let magenta = Rgba([255, 0, 255, 255]);
+        assert!(img.pixels().any(|p| *p == magenta));
+        // Missing sprites are skipped, not an error
+        let img = team_card(&team(1), &HashMap::new());
+        assert!(!img.pixels().any(|p| *p == magenta));
+    }
+
+    #[test]
+    fn test_detail_card() {
+        let json = r#"{
+            "id": 25, "name": "pikachu", "height": 4, "weight": 60,
+            "types": [{"slot": 1, "type": {"name": "electric", "url": ""}}],
+            "stats": [{"base_stat": 90, "stat": {"name": "speed", "url": ""}}],
+            "abilities": [{"ability": {"name": "static", "url": ""}, "is_hidden": false}],
+            "moves": [],
+            "sprites": {"front_default": null}
+        }"#;
+        let detail: PokemonDetail = serde_json::from_str(json).unwrap();
+        let img = detail_card(&detail, Some(&sprite_png()));
+        assert_eq!(img.width(), CARD_WIDTH);
+        assert!(img.pixels().any(|p| *p == rgba(type_color("electric"))));
+        assert!(img.pixels().any(|p| *p == stat_color(90)));
+    }
+}
@@ -0,0 +1,155 @@
+pub mod card;
+pub mod font;
+pub mod table;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use image::RgbaImage;
+
+use crate::api::client::ApiClient;
+use crate::models::pokemon::PokemonSummary;
+use table::DexEntry;
+
+/// Where exported files go: Downloads, else home, else the working directory
+pub fn export_dir() -> PathBuf {
+    dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Lowercase, filename-safe version of a team or Pokémon name
+pub fn slug(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    let out = out.trim_end_matches('-').to_string();
+    if out.is_empty() {
+        "untitled".to_string()
+    } else {
+        out
+    }
+}
+
+/// `dir/stem.ext`, or `dir/stem-2.ext` and so on if that's taken, so
+/// exports never replace an earlier file
+pub fn unused_path(dir: &Path, stem: &str, ext: &str) -> PathBuf {
+    let path = dir.join(format!("{}.{}", stem, ext));
+    if !path.exists() {
+        return path;
+    }
+    (2..)
+        .map(|n| dir.join(format!("{}-{}.{}", stem, n, ext)))
+        .find(|path| !path.exists())
+        .expect("some numbered name is free")
+}
+
+/// Write a card as PNG, creating the directory if needed
+pub fn save_png(img: &RgbaImage, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    img.save_with_format(path, image::ImageFormat::Png)?;
+    Ok(())
+}
+
+/// Full entries (with abilities) for list rows, fetched in batches of 30 like the list loader.
+/// Rows whose detail can't be fetched fall back to what the summary knows; how
+/// many did comes back alongside the entries.
+pub async fn fetch_entries(
+    client: Arc<ApiClient>,
+    summaries: Vec<PokemonSummary>,
+) -> (Vec<DexEntry>, usize) {
+    let mut entries = Vec::with_capacity(summaries.len());
+    let mut incomplete = 0;
+    for chunk in summaries.chunks(30) {
+        let mut handles = Vec::new();
+        for summary in chunk {
+            let client = client.clone();
+            let id = summary.id;
+            handles.push(tokio::spawn(async move {
+                client.fetch_pokemon_detail(&id.to_string()).await
+            }));
+        }
+        for (summary, handle) in chunk.iter().zip(handles) {
+            entries.push(match handle.await {
+                Ok(Ok(detail)) => DexEntry::from_detail(&detail),
+                _ => {
+                    incomplete += 1;
+                    DexEntry::from_summary(summary)
+                }
+            });
+        }
+    }
+    (entries, incomplete)
+}
+
+/// Warning for entries `fetch_entries` couldn't complete, if there were any
+pub fn incomplete_note(incomplete: usize) -> Option<String> {
+    match incomplete {
+        0 => None,
+        1 => Some("1 entry is incomplete: its details couldn't be fetched".to_string()),
+        n => Some(format!(
+            "{} entries are incomplete: their details couldn't be fetched",
+            n
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("Team 1"), "team-1");
+        assert_eq!(slug("  Rain / Sun!! "), "rain-sun");
+        assert_eq!(slug("mr-mime"), "mr-mime");
+        assert_eq!(slug("???"), "untitled");
+    }
+
+    #[test]
+    fn test_incomplete_note() {
+        assert_eq!(incomplete_note(0), None);
+        assert!(incomplete_note(1)
+            .unwrap()
+            .starts_with("1 entry is incomplete"));
+        assert!(incomplete_note(3)
+            .unwrap()
+            .starts_with("3 entries are incomplete"));
+    }
+
+    #[test]
+    fn test_unused_path() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            unused_path(dir.path(), "team-a", "png"),
+            dir.path().join("team-a.png")
+        );
+        std::fs::write(dir.path().join("team-a.png"), "").unwrap();
+        std::fs::write(dir.path().join("team-a-2.png"), "").unwrap();
+        assert_eq!(
+            unused_path(dir.path(), "team-a", "png"),
+            dir.path().join("team-a-3.png")
+        );
+        assert_eq!(
+            unused_path(dir.path(), "team-a", "csv"),
+            dir.path().join("team-a.csv")
+        );
+    }
+
+    #[test]
+    fn test_save_png_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cards").join("a.png");
+        save_png(&RgbaImage::new(3, 2), &path).unwrap();
+        let img = image::open(&path).unwrap();
+        assert_eq!((img.width(), img.height()), (3, 2));
+    }
+}
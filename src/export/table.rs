@@ -0,0 +1,300 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::models::pokemon::{PokemonDetail, PokemonSummary};
+
+/// Text formats for Pokédex entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn all() -> &'static [ExportFormat] {
+        &[
+            ExportFormat::Markdown,
+            ExportFormat::Json,
+            ExportFormat::Csv,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Csv => "CSV",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+        }
+    }
+
+    pub fn next(&self) -> ExportFormat {
+        let all = Self::all();
+        let idx = all.iter().position(|f| f == self).unwrap_or(0);
+        all[(idx + 1) % all.len()]
+    }
+
+    /// Parse a format name or file extension ("md", "markdown", "json", "csv")
+    pub fn from_name(name: &str) -> Option<ExportFormat> {
+        match name.to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Some(ExportFormat::Markdown),
+            "json" => Some(ExportFormat::Json),
+            "csv" => Some(ExportFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+const STAT_NAMES: [&str; 6] = [
+    "hp",
+    "attack",
+    "defense",
+    "special-attack",
+    "special-defense",
+    "speed",
+];
+
+/// One exported row. Height is in metres and weight in kilograms;
+/// fields the source didn't carry are left empty.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DexEntry {
+    pub id: u32,
+    pub name: String,
+    pub types: Vec<String>,
+    pub hp: Option<u32>,
+    pub attack: Option<u32>,
+    pub defense: Option<u32>,
+    pub special_attack: Option<u32>,
+    pub special_defense: Option<u32>,
+    pub speed: Option<u32>,
+    pub total: Option<u32>,
+    pub abilities: Vec<String>,
+    pub height_m: Option<f64>,
+    pub weight_kg: Option<f64>,
+}
+
+impl DexEntry {
+    pub fn from_detail(detail: &PokemonDetail) -> Self {
+        let stat = |name: &str| {
+            detail
+                .stats
+                .iter()
+                .find(|s| s.stat.name == name)
+                .map(|s| s.base_stat)
+        };
+        let stats: Vec<Option<u32>> = STAT_NAMES.iter().map(|n| stat(n)).collect();
+        let total = stats
+            .iter()
+            .any(Option::is_some)
+            .then(|| stats.iter().flatten().sum());
+        Self {
+            id: detail.id,
+            name: detail.name.clone(),
+            types: detail
+                .types
+                .iter()
+                .map(|t| t.type_info.name.clone())
+                .collect(),
+            hp: stats[0],
+            attack: stats[1],
+            defense: stats[2],
+            special_attack: stats[3],
+            special_defense: stats[4],
+            speed: stats[5],
+            total,
+            abilities: detail
+                .abilities
+                .iter()
+                .map(|a| a.ability.name.clone())
+                .collect(),
+            height_m: Some(detail.height as f64 / 10.0),
+            weight_kg: Some(detail.weight as f64 / 10.0),
+        }
+    }
+
+    /// List rows don't carry abilities, and stats may not be loaded yet
+    pub fn from_summary(summary: &PokemonSummary) -> Self {
+        let stat = |i: usize| summary.stats.get(i).copied();
+        Self {
+            id: summary.id,
+            name: summary.name.clone(),
+            types: summary.types.clone(),
+            hp: stat(0),
+            attack: stat(1),
+            defense: stat(2),
+            special_attack: stat(3),
+            special_defense: stat(4),
+            speed: stat(5),
+            total: summary.base_stat_total(),
+            abilities: Vec::new(),
+            height_m: summary.height.map(|h| h as f64 / 10.0),
+            weight_kg: summary.weight.map(|w| w as f64 / 10.0),
+        }
+    }
+
+    fn cells(&self) -> Vec<String> {
+        let num = |v: Option<u32>| v.map(|v| v.to_string()).unwrap_or_default();
+        let dec = |v: Option<f64>| v.map(|v| format!("{:.1}", v)).unwrap_or_default();
+        vec![
+            self.id.to_string(),
+            self.name.clone(),
+            self.types.join("/"),
+            num(self.hp),
+            num(self.attack),
+            num(self.defense),
+            num(self.special_attack),
+            num(self.special_defense),
+            num(self.speed),
+            num(self.total),
+            self.abilities.join(", "),
+            dec(self.height_m),
+            dec(self.weight_kg),
+        ]
+    }
+}
+
+const HEADERS: [&str; 13] = [
+    "id",
+    "name",
+    "types",
+    "hp",
+    "attack",
+    "defense",
+    "special_attack",
+    "special_defense",
+    "speed",
+    "total",
+    "abilities",
+    "height_m",
+    "weight_kg",
+];
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Render entries in the given format, ending with a newline
+pub fn render(entries: &[DexEntry], format: ExportFormat) -> Result<String> {
+    let mut out = String::new();
+    match format {
+        ExportFormat::Json => {
+            out = serde_json::to_string_pretty(entries)?;
+            out.push('\n');
+        }
+        ExportFormat::Csv => {
+            out.push_str(&HEADERS.join(","));
+            out.push('\n');
+            for entry in entries {
+                let row: Vec<String> = entry.cells().iter().map(|c| csv_field(c)).collect();
+                out.push_str(&row.join(","));
+                out.push('\n');
+            }
+        }
+        ExportFormat::Markdown => {
+            out.push_str(&format!("| {} |\n", HEADERS.join(" | ")));
+            out.push_str(&format!("|{}\n", "---|".repeat(HEADERS.len())));
+            for entry in entries {
+                let row: Vec<String> = entry
+                    .cells()
+                    .iter()
+                    .map(|c| c.replace('|', "\\|"))
+                    .collect();
+                out.push_str(&format!("| {} |\n", row.join(" | ")));
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pikachu() -> DexEntry {
+        let json = r#"{
+            "id": 25, "name": "pikachu", "height": 4, "weight": 60,
+            "types": [{"slot": 1, "type": {"name": "electric", "url": ""}}],
+            "stats": [
+                {"base_stat": 35, "stat": {"name": "hp", "url": ""}},
+                {"base_stat": 90, "stat": {"name": "speed", "url": ""}}
+            ],
+            "abilities": [
+                {"ability": {"name": "static", "url": ""}, "is_hidden": false},
+                {"ability": {"name": "lightning-rod", "url": ""}, "is_hidden": true}
+            ],
+            "moves": [],
+            "sprites": {"front_default": null}
+        }"#;
+        DexEntry::from_detail(&serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn test_format_names_and_cycle() {
+        assert_eq!(ExportFormat::from_name("MD"), Some(ExportFormat::Markdown));
+        assert_eq!(ExportFormat::from_name("csv"), Some(ExportFormat::Csv));
+        assert_eq!(ExportFormat::from_name("xml"), None);
+        assert_eq!(ExportFormat::Csv.next(), ExportFormat::Markdown);
+    }
+
+    #[test]
+    fn test_entry_from_detail() {
+        let entry = pikachu();
+        assert_eq!(entry.hp, Some(35));
+        assert_eq!(entry.attack, None);
+        assert_eq!(entry.total, Some(125));
+        assert_eq!(entry.abilities, vec!["static", "lightning-rod"]);
+        assert_eq!(entry.height_m, Some(0.4));
+    }
+
+    #[test]
+    fn test_entry_from_summary_without_stats() {
+        let summary = PokemonSummary {
+            id: 1,
+            name: "bulbasaur".to_string(),
+            ..Default::default()
+        };
+        let entry = DexEntry::from_summary(&summary);
+        assert_eq!(entry.total, None);
+        assert_eq!(entry.cells()[3], "");
+    }
+
+    #[test]
+    fn test_render_csv_quotes_lists() {
+        let csv = render(&[pikachu()], ExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], HEADERS.join(","));
+        assert_eq!(
+            lines[1],
+            "25,pikachu,electric,35,,,,,90,125,\"static, lightning-rod\",0.4,6.0"
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_and_json() {
+        let md = render(&[pikachu()], ExportFormat::Markdown).unwrap();
+        assert_eq!(md.lines().count(), 3);
+        assert!(md
+            .lines()
+            .nth(2)
+            .unwrap()
+            .starts_with("| 25 | pikachu | electric |"));
+
+        let json = render(&[pikachu()], ExportFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[0]["name"], "pikachu");
+        assert_eq!(value[0]["types"][0], "electric");
+        assert!(value[0]["attack"].is_null());
+    }
+}
@@ -0,0 +1,162 @@
+use crate::app::Screen;
+
+/// Places kept for going back; the oldest are dropped past this
+const MAX_ENTRIES: usize = 100;
+
+/// Pokémon kept in the recently viewed list
+pub const MAX_RECENT: usize = 20;
+
+/// A place the user was, with the selection and scroll to put back on return
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Location {
+    pub screen: Screen,
+    /// Pokémon on the detail screen
+    pub pokemon_id: Option<u32>,
+    /// Selected list row, and the Pokémon in it to find again if the list changed
+    pub list_state: usize,
+    pub list_pokemon: Option<u32>,
+    pub current_team: usize,
+    pub team_slot: usize,
+    pub chart_scroll: (usize, usize),
+}
+
+impl Location {
+    /// Same screen, and for the detail screen the same Pokémon; anything else
+    /// is a change of selection within a place
+    fn same_place(&self, other: &Location) -> bool {
+        self.screen == other.screen
+            && (self.screen != Screen::PokemonDetail || self.pokemon_id == other.pokemon_id)
+    }
+}
+
+/// Back/forward stack across screens and opened Pokémon, like a browser's,
+/// plus the Pokémon viewed most recently
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    entries: Vec<Location>,
+    /// Index of the entry being shown
+    current: usize,
+    /// Pokémon ids, most recent first
+    recent: Vec<u32>,
+}
+
+impl History {
+    /// Note where the app is now: a new place is pushed, dropping anything
+    /// ahead of it, while staying put refreshes the current entry
+    pub fn visit(&mut self, location: Location) {
+        match self.entries.get_mut(self.current) {
+            Some(entry) if entry.same_place(&location) => *entry = location,
+            Some(_) => {
+                self.entries.truncate(self.current + 1);
+                self.entries.push(location);
+                if self.entries.len() > MAX_ENTRIES {
+                    self.entries.remove(0);
+                }
+                self.current = self.entries.len() - 1;
+            }
+            None => {
+                self.entries = vec![location];
+                self.current = 0;
+            }
+        }
+    }
+
+    /// Replace the current entry, for moves that shouldn't leave a trail
+    /// such as stepping through Pokémon on the detail screen
+    pub fn replace(&mut self, location: Location) {
+        match self.entries.get_mut(self.current) {
+            Some(entry) => *entry = location,
+            None => self.visit(location),
+        }
+    }
+
+    pub fn back(&mut self) -> Option<Location> {
+        self.current = self.current.checked_sub(1)?;
+        Some(self.entries[self.current])
+    }
+
+    pub fn forward(&mut self) -> Option<Location> {
+        if self.current + 1 >= self.entries.len() {
+            return None;
+        }
+        self.current += 1;
+        Some(self.entries[self.current])
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.current > 0
+    }
+
+    /// Put a Pokémon first in the recently viewed list
+    pub fn viewed(&mut self, id: u32) {
+        self.recent.retain(|&r| r != id);
+        self.recent.insert(0, id);
+        self.recent.truncate(MAX_RECENT);
+    }
+
+    pub fn recent(&self) -> &[u32] {
+        &self.recent
+    }
+
+    pub fn set_recent(&mut self, recent: Vec<u32>) {
+        self.recent = recent;
+        self.recent.truncate(MAX_RECENT);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(screen: Screen, pokemon_id: Option<u32>) -> Location {
+        Location {
+            screen,
+            pokemon_id,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_back_and_forward() {
+        let mut history = History::default();
+        assert_eq!(history.back(), None);
+        history.visit(at(Screen::PokemonList, None));
+        history.visit(Location {
+            list_state: 5,
+            ..at(Screen::PokemonList, None)
+        });
+        history.visit(at(Screen::PokemonDetail, Some(25)));
+        history.visit(at(Screen::TeamBuilder, Some(25)));
+        history.visit(at(Screen::PokemonDetail, Some(6)));
+
+        assert_eq!(history.back(), Some(at(Screen::TeamBuilder, Some(25))));
+        assert_eq!(history.back(), Some(at(Screen::PokemonDetail, Some(25))));
+        // Moving the selection updated the list entry instead of adding one
+        assert_eq!(history.back().map(|l| l.list_state), Some(5));
+        assert!(!history.can_go_back());
+        assert_eq!(history.forward(), Some(at(Screen::PokemonDetail, Some(25))));
+
+        // Going somewhere new drops what was ahead
+        history.visit(at(Screen::TypeChart, None));
+        assert_eq!(history.forward(), None);
+        assert_eq!(history.back(), Some(at(Screen::PokemonDetail, Some(25))));
+
+        history.replace(at(Screen::PokemonDetail, Some(26)));
+        assert_eq!(history.forward(), Some(at(Screen::TypeChart, None)));
+        assert_eq!(history.back(), Some(at(Screen::PokemonDetail, Some(26))));
+    }
+
+    #[test]
+    fn test_recently_viewed() {
+        let mut history = History::default();
+        for id in [1, 4, 7, 4] {
+            history.viewed(id);
+        }
+        assert_eq!(history.recent(), [4, 7, 1]);
+        for id in 100..130 {
+            history.viewed(id);
+        }
+        assert_eq!(history.recent().len(), MAX_RECENT);
+        assert_eq!(history.recent()[0], 129);
+    }
+}
@@ -0,0 +1,834 @@
+pub mod key;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+pub use key::Key;
+
+/// Where a binding applies. Screens fall back to the global bindings for keys
+/// they don't bind themselves; modals only see their own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Context {
+    Global,
+    List,
+    Detail,
+    Chart,
+    Team,
+    Modal,
+}
+
+impl Context {
+    pub fn all() -> &'static [Context] {
+        &[
+            Context::Global,
+            Context::List,
+            Context::Detail,
+            Context::Chart,
+            Context::Team,
+            Context::Modal,
+        ]
+    }
+
+    /// Name used in keymap files
+    pub fn name(&self) -> &'static str {
+        match self {
+            Context::Global => "global",
+            Context::List => "list",
+            Context::Detail => "detail",
+            Context::Chart => "chart",
+            Context::Team => "team",
+            Context::Modal => "modal",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Context> {
+        Context::all().iter().copied().find(|c| c.name() == name)
+    }
+}
+
+/// Everything a key can do. Which actions exist in which context is defined by
+/// the default preset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    NextScreen,
+    PrevScreen,
+    ShowList,
+    ShowDetail,
+    ShowChart,
+    ShowTeam,
+    ToggleMouse,
+    CycleTheme,
+    Help,
+    CommandPalette,
+    HistoryBack,
+    HistoryForward,
+    RecentlyViewed,
+    ToggleSplit,
+    GrowPane,
+    ShrinkPane,
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Select,
+    Back,
+    Search,
+    NextGeneration,
+    AllGenerations,
+    Generation(u8),
+    TypeFilter,
+    ClearTypeFilter,
+    ToggleForms,
+    NextSort,
+    PrevSort,
+    ReverseSort,
+    ToggleRowSize,
+    Export,
+    CycleExportFormat,
+    ExportCard,
+    ToggleFavorite,
+    FavoritesOnly,
+    TagFilter,
+    EditTags,
+    EditNote,
+    CycleCollected,
+    MarkRange,
+    CollectionProgress,
+    AddToTeam,
+    NextSprite,
+    PrevSprite,
+    CycleBlocks,
+    PauseAnimation,
+    NextForm,
+    PrevForm,
+    NewTeam,
+    RemoveMember,
+    ToggleType,
+    MatchMode,
+    ClearTypes,
+}
+
+impl Action {
+    /// Name used in keymap files
+    pub fn name(&self) -> String {
+        let name = match self {
+            Action::Quit => "quit",
+            Action::NextScreen => "next_screen",
+            Action::PrevScreen => "prev_screen",
+            Action::ShowList => "show_list",
+            Action::ShowDetail => "show_detail",
+            Action::ShowChart => "show_chart",
+            Action::ShowTeam => "show_team",
+            Action::ToggleMouse => "toggle_mouse",
+            Action::CycleTheme => "cycle_theme",
+            Action::Help => "help",
+            Action::CommandPalette => "command_palette",
+            Action::HistoryBack => "history_back",
+            Action::HistoryForward => "history_forward",
+            Action::RecentlyViewed => "recently_viewed",
+            Action::ToggleSplit => "toggle_split",
+            Action::GrowPane => "grow_pane",
+            Action::ShrinkPane => "shrink_pane",
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::Select => "select",
+            Action::Back => "back",
+            Action::Search => "search",
+            Action::NextGeneration => "next_generation",
+            Action::AllGenerations => "all_generations",
+            Action::Generation(n) => return format!("generation_{}", n),
+            Action::TypeFilter => "type_filter",
+            Action::ClearTypeFilter => "clear_type_filter",
+            Action::ToggleForms => "toggle_forms",
+            Action::NextSort => "next_sort",
+            Action::PrevSort => "prev_sort",
+            Action::ReverseSort => "reverse_sort",
+            Action::ToggleRowSize => "toggle_row_size",
+            Action::Export => "export",
+            Action::CycleExportFormat => "cycle_export_format",
+            Action::ExportCard => "export_card",
+            Action::ToggleFavorite => "toggle_favorite",
+            Action::FavoritesOnly => "favorites_only",
+            Action::TagFilter => "tag_filter",
+            Action::EditTags => "edit_tags",
+            Action::EditNote => "edit_note",
+            Action::CycleCollected => "cycle_collected",
+            Action::MarkRange => "mark_range",
+            Action::CollectionProgress => "collection_progress",
+            Action::AddToTeam => "add_to_team",
+            Action::NextSprite => "next_sprite",
+            Action::PrevSprite => "prev_sprite",
+            Action::CycleBlocks => "cycle_blocks",
+            Action::PauseAnimation => "pause_animation",
+            Action::NextForm => "next_form",
+            Action::PrevForm => "prev_form",
+            Action::NewTeam => "new_team",
+            Action::RemoveMember => "remove_member",
+            Action::ToggleType => "toggle_type",
+            Action::MatchMode => "match_mode",
+            Action::ClearTypes => "clear_types",
+        };
+        name.to_string()
+    }
+
+    /// Short description for help text
+    pub fn description(&self) -> String {
+        let text = match self {
+            Action::Quit => "Quit",
+            Action::NextScreen => "Next screen",
+            Action::PrevScreen => "Previous screen",
+            Action::ShowList => "Pokédex",
+            Action::ShowDetail => "Detail",
+            Action::ShowChart => "Type chart",
+            Action::ShowTeam => "Team builder",
+            Action::ToggleMouse => "Mouse capture on/off",
+            Action::CycleTheme => "Next color theme",
+            Action::Help => "Help",
+            Action::CommandPalette => "Command palette",
+            Action::HistoryBack => "Go back",
+            Action::HistoryForward => "Go forward",
+            Action::RecentlyViewed => "Recently viewed",
+            Action::ToggleSplit => "Split view",
+            Action::GrowPane => "Widen left pane",
+            Action::ShrinkPane => "Narrow left pane",
+            Action::Up => "Up",
+            Action::Down => "Down",
+            Action::Left => "Left",
+            Action::Right => "Right",
+            Action::PageUp => "Page up",
+            Action::PageDown => "Page down",
+            Action::Top => "First",
+            Action::Bottom => "Last",
+            Action::Select => "Select",
+            Action::Back => "Back",
+            Action::Search => "Search",
+            Action::NextGeneration => "Next generation",
+            Action::AllGenerations => "All generations",
+            Action::Generation(n) => return format!("Generation {}", n),
+            Action::TypeFilter => "Type filter",
+            Action::ClearTypeFilter => "Clear type filter",
+            Action::ToggleForms => "Show/hide forms",
+            Action::NextSort => "Next sort column",
+            Action::PrevSort => "Previous sort column",
+            Action::ReverseSort => "Reverse sort",
+            Action::ToggleRowSize => "Compact/expanded rows",
+            Action::Export => "Export entries",
+            Action::CycleExportFormat => "Export format",
+            Action::ExportCard => "Export PNG card",
+            Action::ToggleFavorite => "Favorite",
+            Action::FavoritesOnly => "Favorites only",
+            Action::TagFilter => "Tag filter",
+            Action::EditTags => "Tags",
+            Action::EditNote => "Notes",
+            Action::CycleCollected => "Seen/caught/shiny",
+            Action::MarkRange => "Mark a range",
+            Action::CollectionProgress => "Collection progress",
+            Action::AddToTeam => "Add to team",
+            Action::NextSprite => "Next sprite",
+            Action::PrevSprite => "Previous sprite",
+            Action::CycleBlocks => "Sprite blocks",
+            Action::PauseAnimation => "Pause animation",
+            Action::NextForm => "Next form",
+            Action::PrevForm => "Previous form",
+            Action::NewTeam => "New team",
+            Action::RemoveMember => "Remove member",
+            Action::ToggleType => "Toggle type",
+            Action::MatchMode => "Either/exact match",
+            Action::ClearTypes => "Clear types",
+        };
+        text.to_string()
+    }
+}
+
+/// Starting points for a keymap; the config file can override single actions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Preset {
+    Default,
+    Vim,
+    Emacs,
+}
+
+impl Preset {
+    pub fn all() -> &'static [Preset] {
+        &[Preset::Default, Preset::Vim, Preset::Emacs]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Preset::Default => "default",
+            Preset::Vim => "vim",
+            Preset::Emacs => "emacs",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Preset> {
+        Preset::all()
+            .iter()
+            .copied()
+            .find(|p| p.label() == name.to_lowercase())
+    }
+}
+
+/// Keymap file: a preset plus per-context overrides, e.g.
+/// `{"preset": "vim", "bindings": {"list": {"search": ["/", "ctrl-f"]}}}`
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeymapFile {
+    #[serde(default)]
+    preset: Option<String>,
+    #[serde(default)]
+    bindings: HashMap<String, HashMap<String, Vec<String>>>,
+}
+
+type Bindings = Vec<(Action, Vec<Key>)>;
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    pub preset: Preset,
+    contexts: HashMap<Context, Bindings>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap::preset(Preset::Default)
+    }
+}
+
+impl Keymap {
+    pub fn preset(preset: Preset) -> Keymap {
+        let mut keymap = Keymap {
+            preset,
+            contexts: default_bindings(),
+        };
+        match preset {
+            Preset::Default => {}
+            Preset::Vim => keymap.apply_vim(),
+            Preset::Emacs => keymap.apply_emacs(),
+        }
+        keymap
+    }
+
+    /// The action a key triggers in a context, falling back to global bindings on screens
+    pub fn action(&self, context: Context, event: &KeyEvent) -> Option<Action> {
+        let key = Key::from_event(event);
+        let find = |context: Context| {
+            self.bindings(context)
+                .iter()
+                .find(|(_, keys)| keys.contains(&key))
+                .map(|(action, _)| *action)
+        };
+        find(context).or_else(|| match context {
+            Context::Modal | Context::Global => None,
+            _ => find(Context::Global),
+        })
+    }
+
+    /// Actions of a context in display order, with their keys
+    pub fn bindings(&self, context: Context) -> &[(Action, Vec<Key>)] {
+        self.contexts
+            .get(&context)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    pub fn keys(&self, context: Context, action: Action) -> &[Key] {
+        self.bindings(context)
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// First key for an action, for hints in titles and footers
+    pub fn label(&self, context: Context, action: Action) -> String {
+        self.keys(context, action)
+            .first()
+            .map(Key::to_string)
+            .unwrap_or_else(|| "unbound".to_string())
+    }
+
+    /// (keys, description) rows for the help overlay, limited to `only` if given.
+    /// The nine generation keys share one row.
+    pub fn help_rows(&self, context: Context, only: Option<&[Action]>) -> Vec<(String, String)> {
+        let mut rows = Vec::new();
+        for (action, keys) in self.bindings(context) {
+            if keys.is_empty() || only.is_some_and(|only| !only.contains(action)) {
+                continue;
+            }
+            let row = match action {
+                Action::Generation(1) => (
+                    format!(
+                        "{}-{}",
+                        self.label(context, Action::Generation(1)),
+                        self.label(context, Action::Generation(9))
+                    ),
+                    "Generation 1-9".to_string(),
+                ),
+                Action::Generation(_) => continue,
+                _ => (
+                    keys.iter()
+                        .map(Key::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    action.description(),
+                ),
+            };
+            rows.push(row);
+        }
+        rows
+    }
+
+    /// Keys bound to more than one action in the same context, and screen
+    /// keys that hide a global binding on that screen. The list's generation
+    /// digits take over the screen jumps there on purpose, so they don't count.
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let global = self.bindings(Context::Global);
+        for &context in Context::all() {
+            let mut seen: HashMap<Key, Action> = HashMap::new();
+            for (action, keys) in self.bindings(context) {
+                for key in keys {
+                    match seen.get(key) {
+                        Some(other) if other != action => problems.push(format!(
+                            "{}: {} is bound to both {} and {}",
+                            context.name(),
+                            key,
+                            other.name(),
+                            action.name()
+                        )),
+                        _ => {
+                            seen.insert(*key, *action);
+                        }
+                    }
+                }
+            }
+            // Modals don't fall back to global keys, so there's nothing to hide
+            if matches!(context, Context::Global | Context::Modal) {
+                continue;
+            }
+            for (action, keys) in self.bindings(context) {
+                if matches!(action, Action::Generation(_)) {
+                    continue;
+                }
+                for key in keys {
+                    if let Some((hidden, _)) =
+                        global.iter().find(|(a, k)| a != action && k.contains(key))
+                    {
+                        problems.push(format!(
+                            "{}: {} is bound to {}, hiding the global {}",
+                            context.name(),
+                            key,
+                            action.name(),
+                            hidden.name()
+                        ));
+                    }
+                }
+            }
+        }
+        problems
+    }
+
+    /// Replace the keys of an action the context already has
+    fn set(&mut self, context: Context, action: Action, keys: &[Key]) {
+        if let Some(entry) = self
+            .contexts
+            .get_mut(&context)
+            .and_then(|b| b.iter_mut().find(|(a, _)| *a == action))
+        {
+            entry.1 = keys.to_vec();
+        }
+    }
+
+    /// Add keys to an action the context already has
+    fn add(&mut self, context: Context, action: Action, keys: &[Key]) {
+        let mut all = self.keys(context, action).to_vec();
+        all.extend_from_slice(keys);
+        self.set(context, action, &all);
+    }
+
+    /// hjkl everywhere, Ctrl-D/Ctrl-U paging and G for the last row
+    fn apply_vim(&mut self) {
+        for context in [Context::List, Context::Modal] {
+            self.add(context, Action::PageDown, &[Key::ctrl('d')]);
+            self.add(context, Action::PageUp, &[Key::ctrl('u')]);
+        }
+        self.set(Context::List, Action::NextGeneration, &[Key::char('g')]);
+        self.add(Context::List, Action::Bottom, &[Key::char('G')]);
+        self.add(Context::Modal, Action::Bottom, &[Key::char('G')]);
+    }
+
+    /// Ctrl-P/N/B/F movement, Ctrl-G to back out, Ctrl-S to search,
+    /// Ctrl-V/Alt-V paging and Alt-X for the palette; letter keys stay free
+    /// for commands
+    fn apply_emacs(&mut self) {
+        let moves = [
+            (Action::Up, KeyCode::Up, 'p'),
+            (Action::Down, KeyCode::Down, 'n'),
+            (Action::Left, KeyCode::Left, 'b'),
+            (Action::Right, KeyCode::Right, 'f'),
+        ];
+        for &context in Context::all() {
+            for (action, arrow, letter) in moves {
+                if !self.keys(context, action).is_empty() {
+                    self.set(context, action, &[Key::plain(arrow), Key::ctrl(letter)]);
+                }
+            }
+        }
+        for context in [Context::List, Context::Modal] {
+            self.add(context, Action::PageDown, &[Key::ctrl('v')]);
+            self.add(context, Action::PageUp, &[Key::alt('v')]);
+            self.add(context, Action::Top, &[Key::alt('<')]);
+            self.add(context, Action::Bottom, &[Key::alt('>')]);
+            self.add(context, Action::Search, &[Key::ctrl('s')]);
+        }
+        self.add(Context::Detail, Action::Back, &[Key::ctrl('g')]);
+        self.add(Context::Modal, Action::Back, &[Key::ctrl('g')]);
+        // Ctrl-P moves up, which would hide the palette on most screens
+        self.set(
+            Context::Global,
+            Action::CommandPalette,
+            &[Key::char(':'), Key::alt('x')],
+        );
+    }
+
+    /// Apply a keymap file. Problems are all collected so one message can list them.
+    fn from_file(file: &KeymapFile) -> Result<Keymap, Vec<String>> {
+        let mut problems = Vec::new();
+        let preset = match file.preset.as_deref() {
+            None => Preset::Default,
+            Some(name) => Preset::from_name(name).unwrap_or_else(|| {
+                problems.push(format!("unknown preset '{}'", name));
+                Preset::Default
+            }),
+        };
+        let mut keymap = Keymap::preset(preset);
+
+        for (context_name, actions) in &file.bindings {
+            let Some(context) = Context::from_name(context_name) else {
+                problems.push(format!("unknown context '{}'", context_name));
+                continue;
+            };
+            for (action_name, key_names) in actions {
+                let Some(action) = keymap
+                    .bindings(context)
+                    .iter()
+                    .map(|(a, _)| *a)
+                    .find(|a| a.name() == *action_name)
+                else {
+                    problems.push(format!(
+                        "{}: unknown action '{}'",
+                        context_name, action_name
+                    ));
+                    continue;
+                };
+                let mut keys = Vec::new();
+                for name in key_names {
+                    match Key::parse(name) {
+                        Ok(key) => keys.push(key),
+                        Err(e) => problems.push(format!("{}.{}: {}", context_name, action_name, e)),
+                    }
+                }
+                keymap.set(context, action, &keys);
+            }
+        }
+
+        problems.extend(keymap.conflicts());
+        if problems.is_empty() {
+            Ok(keymap)
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Keymap file location, next to other settings rather than in the cache
+    pub fn file_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("pokemon-tui")
+            .join("keymap.json")
+    }
+
+    /// Load the keymap file if there is one, on top of `preset` when settings
+    /// pick one. A broken file falls back to the preset's keys, with a message
+    /// saying what's wrong.
+    pub fn load(preset: Option<Preset>) -> (Keymap, Option<String>) {
+        Keymap::load_from(&Keymap::file_path(), preset)
+    }
+
+    pub fn load_from(path: &Path, preset: Option<Preset>) -> (Keymap, Option<String>) {
+        let fallback = Keymap::preset(preset.unwrap_or(Preset::Default));
+        let Ok(data) = std::fs::read_to_string(path) else {
+            return (fallback, None);
+        };
+        let mut file: KeymapFile = match serde_json::from_str(&data) {
+            Ok(file) => file,
+            Err(e) => {
+                let message = format!("Ignoring {}: {}", path.display(), e);
+                return (fallback, Some(message));
+            }
+        };
+        if let Some(preset) = preset {
+            file.preset = Some(preset.label().to_string());
+        }
+        match Keymap::from_file(&file) {
+            Ok(keymap) => (keymap, None),
+            Err(problems) => {
+                let message = format!(
+                    "Ignoring {}, using default keys:\n{}",
+                    path.display(),
+                    problems.join("\n")
+                );
+                (fallback, Some(message))
+            }
+        }
+    }
+}
+
+fn default_bindings() -> HashMap<Context, Bindings> {
+    use Action::*;
+
+    let c = Key::char;
+    let k = Key::plain;
+    let alt = |code| Key {
+        code,
+        modifiers: KeyModifiers::ALT,
+    };
+    let movement = |vertical_only: bool| {
+        let mut b = vec![
+            (Up, vec![k(KeyCode::Up), c('k')]),
+            (Down, vec![k(KeyCode::Down), c('j')]),
+        ];
+        if !vertical_only {
+            b.push((Left, vec![k(KeyCode::Left), c('h')]));
+            b.push((Right, vec![k(KeyCode::Right), c('l')]));
+        }
+        b
+    };
+    let paging = || {
+        vec![
+            (PageUp, vec![k(KeyCode::PageUp)]),
+            (PageDown, vec![k(KeyCode::PageDown)]),
+            (Top, vec![k(KeyCode::Home)]),
+            (Bottom, vec![k(KeyCode::End)]),
+        ]
+    };
+
+    let global = vec![
+        (Quit, vec![c('q'), Key::ctrl('c')]),
+        (NextScreen, vec![k(KeyCode::Tab)]),
+        (PrevScreen, vec![k(KeyCode::BackTab)]),
+        (ShowList, vec![c('1')]),
+        (ShowDetail, vec![c('2')]),
+        (ShowChart, vec![c('3')]),
+        (ShowTeam, vec![c('4')]),
+        (ToggleMouse, vec![c('M')]),
+        (CycleTheme, vec![Key::ctrl('t')]),
+        (Help, vec![c('?')]),
+        (CommandPalette, vec![c(':'), Key::ctrl('p')]),
+        (HistoryBack, vec![c('['), alt(KeyCode::Left)]),
+        (HistoryForward, vec![c(']'), alt(KeyCode::Right)]),
+        (RecentlyViewed, vec![c('H')]),
+        (ToggleSplit, vec![c('|')]),
+        (GrowPane, vec![c('>')]),
+        (ShrinkPane, vec![c('<')]),
+    ];
+
+    let mut list = movement(true);
+    list.extend(paging());
+    list.extend([
+        (Select, vec![k(KeyCode::Enter)]),
+        (Search, vec![c('/')]),
+        (NextGeneration, vec![c('g'), c('G')]),
+        (AllGenerations, vec![c('0')]),
+    ]);
+    list.extend((1..=9).map(|n| (Generation(n), vec![c((b'0' + n) as char)])));
+    list.extend([
+        (TypeFilter, vec![c('t')]),
+        (ClearTypeFilter, vec![c('T')]),
+        (ToggleForms, vec![c('f')]),
+        (NextSort, vec![c('s')]),
+        (PrevSort, vec![c('S')]),
+        (ReverseSort, vec![c('r')]),
+        (ToggleRowSize, vec![c('z')]),
+        (Export, vec![c('e')]),
+        (CycleExportFormat, vec![c('E')]),
+        (ToggleFavorite, vec![c('*')]),
+        (FavoritesOnly, vec![c('F')]),
+        (TagFilter, vec![c('#')]),
+        (CycleCollected, vec![c('c')]),
+        (MarkRange, vec![c('C')]),
+        (CollectionProgress, vec![c('p')]),
+    ]);
+
+    let detail = vec![
+        (Back, vec![k(KeyCode::Esc)]),
+        (Left, vec![k(KeyCode::Left), c('h')]),
+        (Right, vec![k(KeyCode::Right), c('l')]),
+        (AddToTeam, vec![c('a')]),
+        (NextSprite, vec![c('v')]),
+        (PrevSprite, vec![c('V')]),
+        (CycleBlocks, vec![c('b')]),
+        (PauseAnimation, vec![c('p')]),
+        (ExportCard, vec![c('x')]),
+        (Export, vec![c('e')]),
+        (CycleExportFormat, vec![c('E')]),
+        (NextForm, vec![c('f')]),
+        (PrevForm, vec![c('F')]),
+        (ToggleFavorite, vec![c('*')]),
+        (EditTags, vec![c('#')]),
+        (EditNote, vec![c('n')]),
+        (CycleCollected, vec![c('c')]),
+    ];
+
+    let chart = movement(false);
+
+    let mut team = movement(false);
+    team.extend([
+        (Select, vec![k(KeyCode::Enter)]),
+        (NewTeam, vec![c('n')]),
+        (RemoveMember, vec![c('d'), k(KeyCode::Delete)]),
+        (ToggleRowSize, vec![c('z')]),
+        (ExportCard, vec![c('x')]),
+    ]);
+
+    let mut modal = movement(true);
+    modal.extend(paging());
+    modal.extend([
+        (Select, vec![k(KeyCode::Enter)]),
+        (Back, vec![k(KeyCode::Esc)]),
+        (Search, vec![c('/')]),
+        (ToggleType, vec![c(' ')]),
+        (MatchMode, vec![c('m')]),
+        (ClearTypes, vec![c('c')]),
+        (TypeFilter, vec![c('t')]),
+        (Help, vec![c('?')]),
+    ]);
+
+    HashMap::from([
+        (Context::Global, global),
+        (Context::List, list),
+        (Context::Detail, detail),
+        (Context::Chart, chart),
+        (Context::Team, team),
+        (Context::Modal, modal),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::empty())
+    }
+
+    #[test]
+    fn test_presets_have_no_conflicts() {
+        for &preset in Preset::all() {
+            assert_eq!(Keymap::preset(preset).conflicts(), Vec::<String>::new());
+        }
+    }
+
+    #[test]
+    fn test_shadowed_global_keys_are_conflicts() {
+        let mut keymap = Keymap::default();
+        keymap.add(Context::Chart, Action::Up, &[Key::ctrl('p')]);
+        assert_eq!(
+            keymap.conflicts(),
+            ["chart: Ctrl+p is bound to up, hiding the global command_palette"]
+        );
+    }
+
+    #[test]
+    fn test_screen_keys_shadow_global_ones() {
+        let keymap = Keymap::default();
+        let one = press(KeyCode::Char('1'));
+        assert_eq!(
+            keymap.action(Context::List, &one),
+            Some(Action::Generation(1))
+        );
+        assert_eq!(keymap.action(Context::Detail, &one), Some(Action::ShowList));
+        assert_eq!(keymap.action(Context::Modal, &one), None);
+        assert_eq!(
+            keymap.action(Context::Detail, &press(KeyCode::Char('l'))),
+            Some(Action::Right)
+        );
+    }
+
+    #[test]
+    fn test_emacs_preset() {
+        let keymap = Keymap::preset(Preset::Emacs);
+        let ctrl_n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.action(Context::List, &ctrl_n), Some(Action::Down));
+        assert_eq!(
+            keymap.action(Context::List, &press(KeyCode::Char('j'))),
+            None
+        );
+        assert_eq!(keymap.label(Context::Team, Action::Left), "←");
+        let alt_x = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT);
+        assert_eq!(
+            keymap.action(Context::List, &alt_x),
+            Some(Action::CommandPalette)
+        );
+    }
+
+    #[test]
+    fn test_keymap_file_overrides_and_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keymap.json");
+
+        std::fs::write(
+            &path,
+            r#"{"preset": "vim", "bindings": {"list": {"search": ["ctrl-f"]}}}"#,
+        )
+        .unwrap();
+        let (keymap, error) = Keymap::load_from(&path, None);
+        assert_eq!(error, None);
+        assert_eq!(keymap.preset, Preset::Vim);
+        assert_eq!(
+            keymap.keys(Context::List, Action::Search),
+            &[Key::ctrl('f')]
+        );
+        // A preset picked in settings keeps the file's own bindings
+        let (keymap, _) = Keymap::load_from(&path, Some(Preset::Emacs));
+        assert_eq!(keymap.preset, Preset::Emacs);
+        assert_eq!(
+            keymap.keys(Context::List, Action::Search),
+            &[Key::ctrl('f')]
+        );
+
+        std::fs::write(
+            &path,
+            r#"{"bindings": {"list": {"search": ["s"], "fly": ["x"]}, "shop": {}}}"#,
+        )
+        .unwrap();
+        let (keymap, error) = Keymap::load_from(&path, None);
+        let error = error.unwrap();
+        assert!(error.contains("list: s is bound to both search and next_sort"));
+        assert!(error.contains("unknown action 'fly'"));
+        assert!(error.contains("unknown context 'shop'"));
+        // Falls back to the defaults
+        assert_eq!(
+            keymap.keys(Context::List, Action::Search),
+            &[Key::char('/')]
+        );
+
+        let (_, error) = Keymap::load_from(&dir.path().join("missing.json"), None);
+        assert_eq!(error, None);
+    }
+}
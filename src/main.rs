@@ -2,38 +2,136 @@
 
 mod api;
 mod app;
+mod cli;
 mod event;
+mod export;
+mod history;
+mod keymap;
 mod models;
+mod palette;
+mod search;
+mod server;
+mod settings;
 mod sprite;
 mod tui;
 mod ui;
 
 use anyhow::Result;
 use app::App;
-use event::EventHandler;
+use event::{AppEvent, EventHandler};
+use sprite::graphics::GraphicsRenderer;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let mut terminal = tui::init()?;
+    let command = cli::parse(std::env::args().skip(1))?;
+    if command != cli::Command::Tui {
+        return cli::run(command).await;
+    }
+
+    // Mouse reporting stops the terminal's own text selection, so it can be turned off
+    let mouse_capture = !matches!(
+        std::env::var("POKEDEX_MOUSE").as_deref(),
+        Ok("0" | "false" | "off")
+    );
+    let mut terminal = tui::init(mouse_capture)?;
 
     let events = EventHandler::new();
     let mut app = App::new(events.tx());
+    let mut graphics = GraphicsRenderer::new(sprite::graphics::detect());
+    app.graphics_protocol = graphics.protocol();
+    app.color_depth = sprite::renderer::ColorDepth::detect();
+    app.mouse_capture = mouse_capture;
+    let (settings, settings_error) = settings::Settings::load();
+    let (keymap, keymap_error) = keymap::Keymap::load(settings.preset());
+    app.keymap = keymap;
+    let (theme, theme_error) = ui::theme::Theme::load(settings.theme_name());
+    app.theme = theme;
+    let (team_data, teams_error) = models::team::TeamData::load();
+    app.set_teams(team_data, models::team::TeamData::file_path());
+    let (notes, notes_error) = models::notes::Notes::load();
+    app.set_notes(notes, models::notes::Notes::file_path());
+    let (collection, collection_error) = models::collection::Collection::load();
+    app.set_collection(collection, models::collection::Collection::file_path());
+    // Report every file that had a problem, not just the first
+    let errors: Vec<String> = [
+        teams_error,
+        notes_error,
+        collection_error,
+        settings_error,
+        keymap_error,
+        theme_error,
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    app.error_message = (!errors.is_empty()).then(|| errors.join("\n\n"));
+    // https://no-color.org: any non-empty value, unless a theme is configured
+    let no_color = std::env::var("NO_COLOR").is_ok_and(|v| !v.is_empty());
+    if no_color && settings.theme_name().is_none() && !ui::theme::Theme::file_path().exists() {
+        app.theme = ui::theme::Theme::named(ui::theme::ThemeName::NoColor);
+    }
+    app.accessible = no_color
+        || matches!(
+            std::env::var("POKEDEX_ACCESSIBLE").as_deref(),
+            Ok("1" | "true" | "on")
+        );
+    app.animate_sprites = !matches!(
+        std::env::var("POKEDEX_ANIMATE").as_deref(),
+        Ok("0" | "false" | "off")
+    );
+
+    app.restore(settings, settings::Session::load());
 
     // Kick off initial data load
     app.start_loading_list();
 
-    let result = run(&mut terminal, &mut app, events).await;
+    let result = run(&mut terminal, &mut app, &mut graphics, events).await;
+    app.save_state();
 
     tui::restore()?;
     result
 }
 
-async fn run(terminal: &mut tui::Tui, app: &mut App, mut events: EventHandler) -> Result<()> {
+async fn run(
+    terminal: &mut tui::Tui,
+    app: &mut App,
+    graphics: &mut GraphicsRenderer,
+    mut events: EventHandler,
+) -> Result<()> {
+    let mut mouse_capture = app.mouse_capture;
+    let mut redraw = true;
     while app.running {
-        terminal.draw(|f| ui::draw(f, app))?;
+        if redraw {
+            let mut sprite_area = None;
+            terminal.draw(|f| sprite_area = ui::draw(f, app))?;
+
+            app.fetch_wanted_thumbnails();
+
+            let image = sprite_area.zip(app.current_sprite_bytes());
+            if graphics.is_stale(image) {
+                // The old image isn't part of ratatui's buffer, so wipe it with a full redraw
+                graphics.reset(terminal.backend_mut())?;
+                terminal.clear()?;
+                terminal.draw(|f| {
+                    ui::draw(f, app);
+                })?;
+            }
+            graphics.present(terminal.backend_mut(), image)?;
+        }
+
+        // Ticks only matter while an animation is playing; skip redrawing when idle
+        redraw = match events.next().await? {
+            AppEvent::Tick => app.tick(),
+            event => {
+                app.handle_event(event);
+                true
+            }
+        };
 
-        let event = events.next().await?;
-        app.handle_event(event);
+        if app.mouse_capture != mouse_capture {
+            mouse_capture = app.mouse_capture;
+            tui::set_mouse_capture(terminal, mouse_capture)?;
+        }
     }
     Ok(())
 }
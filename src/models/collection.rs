@@ -0,0 +1,498 @@
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{keep_corrupt_copy, write_atomic};
+
+/// Games a collection can be kept for, as PokéAPI version group names with
+/// labels and the generation they belong to
+pub const GAMES: [(&str, &str, u8); 21] = [
+    ("red-blue", "Red/Blue", 1),
+    ("yellow", "Yellow", 1),
+    ("gold-silver", "Gold/Silver", 2),
+    ("crystal", "Crystal", 2),
+    ("ruby-sapphire", "Ruby/Sapphire", 3),
+    ("emerald", "Emerald", 3),
+    ("firered-leafgreen", "FireRed/LeafGreen", 3),
+    ("diamond-pearl", "Diamond/Pearl", 4),
+    ("platinum", "Platinum", 4),
+    ("heartgold-soulsilver", "HeartGold/SoulSilver", 4),
+    ("black-white", "Black/White", 5),
+    ("black-2-white-2", "Black 2/White 2", 5),
+    ("x-y", "X/Y", 6),
+    ("omega-ruby-alpha-sapphire", "Omega Ruby/Alpha Sapphire", 6),
+    ("sun-moon", "Sun/Moon", 7),
+    ("ultra-sun-ultra-moon", "Ultra Sun/Ultra Moon", 7),
+    ("lets-go-pikachu-lets-go-eevee", "Let's Go Pikachu/Eevee", 7),
+    ("sword-shield", "Sword/Shield", 8),
+    (
+        "brilliant-diamond-shining-pearl",
+        "Brilliant Diamond/Shining Pearl",
+        8,
+    ),
+    ("legends-arceus", "Legends: Arceus", 8),
+    ("scarlet-violet", "Scarlet/Violet", 9),
+];
+
+pub fn game_label(game: &str) -> &str {
+    GAMES
+        .iter()
+        .find(|(name, _, _)| *name == game)
+        .map_or(game, |(_, label, _)| label)
+}
+
+/// Latest generation of Pokémon a game has; all of them for unknown games
+pub fn game_generation(game: &str) -> u8 {
+    GAMES
+        .iter()
+        .find(|(name, _, _)| *name == game)
+        .map_or(9, |&(_, _, generation)| generation)
+}
+
+/// How far a Pokémon has got in a game; each step includes the ones before
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Seen,
+    Caught,
+    /// Caught as a shiny
+    Shiny,
+}
+
+impl Status {
+    pub fn all() -> &'static [Status] {
+        &[Status::Seen, Status::Caught, Status::Shiny]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Seen => "seen",
+            Status::Caught => "caught",
+            Status::Shiny => "shiny",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Status> {
+        Status::all()
+            .iter()
+            .find(|s| s.label().eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    /// Unmarked -> seen -> caught -> shiny -> unmarked
+    pub fn next(status: Option<Status>) -> Option<Status> {
+        match status {
+            None => Some(Status::Seen),
+            Some(Status::Seen) => Some(Status::Caught),
+            Some(Status::Caught) => Some(Status::Shiny),
+            Some(Status::Shiny) => None,
+        }
+    }
+}
+
+/// Seen, caught and shiny counts for one generation of the current game
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Progress {
+    pub generation: u8,
+    pub total: usize,
+    pub seen: usize,
+    pub caught: usize,
+    pub shiny: usize,
+}
+
+fn default_game() -> String {
+    GAMES[GAMES.len() - 1].0.to_string()
+}
+
+/// Seen/caught/shiny marks per game, by Pokémon id so forms count separately
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Collection {
+    /// The game being tracked in the app
+    #[serde(default = "default_game")]
+    pub game: String,
+    #[serde(default)]
+    pub games: BTreeMap<String, BTreeMap<u32, Status>>,
+    /// Stand-in for a file that couldn't be read, which mustn't be saved over
+    #[serde(skip)]
+    pub read_only: bool,
+}
+
+impl Default for Collection {
+    fn default() -> Self {
+        Self {
+            game: default_game(),
+            games: BTreeMap::new(),
+            read_only: false,
+        }
+    }
+}
+
+/// What `import`/`export` read and write: the marks without the current game
+#[derive(Serialize, Deserialize)]
+struct CollectionFile {
+    games: BTreeMap<String, BTreeMap<u32, Status>>,
+}
+
+impl Collection {
+    pub fn status(&self, id: u32) -> Option<Status> {
+        self.games.get(&self.game)?.get(&id).copied()
+    }
+
+    pub fn set(&mut self, id: u32, status: Option<Status>) {
+        set_in(&mut self.games, &self.game, id, status);
+    }
+
+    /// Move to the next status, returning it
+    pub fn cycle(&mut self, id: u32) -> Option<Status> {
+        let status = Status::next(self.status(id));
+        self.set(id, status);
+        status
+    }
+
+    /// Mark every one of `ids` the same way
+    pub fn mark(&mut self, ids: impl IntoIterator<Item = u32>, status: Option<Status>) -> usize {
+        ids.into_iter().map(|id| self.set(id, status)).count()
+    }
+
+    /// Progress per generation over `species` as (id, generation), up to the
+    /// current game's generation
+    pub fn progress(&self, species: impl IntoIterator<Item = (u32, u8)>) -> Vec<Progress> {
+        let mut progress: Vec<Progress> = (1..=game_generation(&self.game))
+            .map(|generation| Progress {
+                generation,
+                ..Default::default()
+            })
+            .collect();
+        for (id, generation) in species {
+            let Some(row) = usize::from(generation)
+                .checked_sub(1)
+                .and_then(|i| progress.get_mut(i))
+            else {
+                continue;
+            };
+            row.total += 1;
+            let status = self.status(id);
+            row.seen += usize::from(status >= Some(Status::Seen));
+            row.caught += usize::from(status >= Some(Status::Caught));
+            row.shiny += usize::from(status == Some(Status::Shiny));
+        }
+        progress
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        let file = CollectionFile {
+            games: self.games.clone(),
+        };
+        Ok(format!("{}\n", serde_json::to_string_pretty(&file)?))
+    }
+
+    /// `game,id,status` rows, a game at a time
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("game,id,status\n");
+        for (game, marks) in &self.games {
+            for (id, status) in marks {
+                out.push_str(&format!("{},{},{}\n", game, id, status.label()));
+            }
+        }
+        out
+    }
+
+    /// Add marks exported with `to_json`, replacing ones already there.
+    /// Returns how many were read.
+    pub fn import_json(&mut self, text: &str) -> Result<usize> {
+        let file: CollectionFile = serde_json::from_str(text)?;
+        let mut count = 0;
+        for (game, marks) in file.games {
+            for (id, status) in marks {
+                set_in(&mut self.games, &game, id, Some(status));
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Add `game,id,status` rows; an empty status clears the mark
+    pub fn import_csv(&mut self, text: &str) -> Result<usize> {
+        let mut marks = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (n == 0 && line.starts_with("game,")) {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [game, id, status] = fields[..] else {
+                bail!("line {}: expected game,id,status", n + 1);
+            };
+            let id: u32 = id
+                .parse()
+                .with_context(|| format!("line {}: bad id '{}'", n + 1, id))?;
+            let status = match status {
+                "" => None,
+                name => Some(
+                    Status::from_name(name)
+                        .with_context(|| format!("line {}: unknown status '{}'", n + 1, name))?,
+                ),
+            };
+            marks.push((game.to_string(), id, status));
+        }
+        // Nothing changes unless the whole file is good
+        let count = marks.len();
+        for (game, id, status) in marks {
+            set_in(&mut self.games, &game, id, status);
+        }
+        Ok(count)
+    }
+
+    pub fn file_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("pokemon-tui")
+            .join("collection.json")
+    }
+
+    pub fn load() -> (Self, Option<String>) {
+        Self::load_from(&Self::file_path())
+    }
+
+    /// The collection at `path`; a file that can't be parsed is copied aside
+    /// and reported, starting over empty. One that can't be read at all, say
+    /// for lack of permission, is left alone with a read-only stand-in.
+    pub fn load_from(path: &Path) -> (Self, Option<String>) {
+        match Self::read_from(path) {
+            Ok(collection) => (collection, None),
+            Err(e) if e.downcast_ref::<std::io::Error>().is_some() => {
+                let message = format!("{:#}\nMarks won't be saved until it can be read.", e);
+                let collection = Self {
+                    read_only: true,
+                    ..Self::default()
+                };
+                (collection, Some(message))
+            }
+            Err(e) => {
+                let message = format!(
+                    "{:#}\nStarting with an empty collection. {}",
+                    e,
+                    keep_corrupt_copy(path)
+                );
+                (Self::default(), Some(message))
+            }
+        }
+    }
+
+    pub fn read_from(path: &Path) -> Result<Self> {
+        let data = match std::fs::read_to_string(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("Couldn't read {}", path.display())),
+        };
+        serde_json::from_str(&data).with_context(|| format!("Couldn't parse {}", path.display()))
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        self.save_to(&Self::file_path())
+    }
+
+    /// Replace the file atomically; a read-only collection is never written
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if self.read_only {
+            return Err(std::io::Error::new(
+                ErrorKind::PermissionDenied,
+                format!("{} couldn't be loaded", path.display()),
+            ));
+        }
+        write_atomic(path, &serde_json::to_string_pretty(self)?)
+    }
+}
+
+fn set_in(
+    games: &mut BTreeMap<String, BTreeMap<u32, Status>>,
+    game: &str,
+    id: u32,
+    status: Option<Status>,
+) {
+    match status {
+        Some(status) => {
+            games
+                .entry(game.to_string())
+                .or_default()
+                .insert(id, status);
+        }
+        None => {
+            if let Some(marks) = games.get_mut(game) {
+                marks.remove(&id);
+                if marks.is_empty() {
+                    games.remove(game);
+                }
+            }
+        }
+    }
+}
+
+/// Parse a bulk mark like "1-151 caught", "25 shiny" or "152-251 none"
+pub fn parse_mark(text: &str) -> Result<(RangeInclusive<u32>, Option<Status>)> {
+    let mut words = text.split_whitespace();
+    let (Some(range), Some(status), None) = (words.next(), words.next(), words.next()) else {
+        bail!("type a range and a status, like 1-151 caught");
+    };
+    let number = |s: &str| -> Result<u32> {
+        s.trim()
+            .parse()
+            .with_context(|| format!("'{}' isn't a dex number", s))
+    };
+    let range = match range.split_once('-') {
+        Some((from, to)) => number(from)?..=number(to)?,
+        None => number(range)?..=number(range)?,
+    };
+    if range.is_empty() {
+        bail!("the range runs backwards");
+    }
+    let status = match status.to_lowercase().as_str() {
+        "none" | "clear" | "unseen" => None,
+        name => Some(Status::from_name(name).with_context(|| {
+            format!("unknown status '{}': use seen, caught, shiny or none", name)
+        })?),
+    };
+    Ok((range, status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_and_progress() {
+        let mut collection = Collection::default();
+        assert_eq!(collection.cycle(1), Some(Status::Seen));
+        assert_eq!(collection.cycle(1), Some(Status::Caught));
+        collection.set(4, Some(Status::Shiny));
+        collection.set(152, Some(Status::Seen));
+        collection.set(10034, Some(Status::Caught));
+
+        let species = [(1, 1), (4, 1), (7, 1), (152, 2)];
+        let progress = collection.progress(species);
+        assert_eq!(progress.len(), 9);
+        assert_eq!(
+            progress[0],
+            Progress {
+                generation: 1,
+                total: 3,
+                seen: 2,
+                caught: 2,
+                shiny: 1
+            }
+        );
+        assert_eq!(
+            (progress[1].total, progress[1].seen, progress[1].caught),
+            (1, 1, 0)
+        );
+
+        // Marks are kept per game
+        collection.game = "red-blue".to_string();
+        assert_eq!(collection.status(1), None);
+        assert_eq!(collection.mark(1..=3, Some(Status::Caught)), 3);
+        assert_eq!(collection.mark(2..=3, None), 2);
+        assert_eq!(collection.games["red-blue"].len(), 1);
+        collection.set(1, None);
+        assert!(!collection.games.contains_key("red-blue"));
+
+        // Only generations the game has are counted
+        let progress = collection.progress(species);
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].total, 3);
+        assert_eq!(game_generation("legends-arceus"), 8);
+        assert_eq!(game_generation("some-hack"), 9);
+    }
+
+    #[test]
+    fn test_parse_mark() {
+        assert_eq!(
+            parse_mark("1-151 caught").unwrap(),
+            (1..=151, Some(Status::Caught))
+        );
+        assert_eq!(
+            parse_mark(" 25  Shiny ").unwrap(),
+            (25..=25, Some(Status::Shiny))
+        );
+        assert_eq!(parse_mark("152-251 none").unwrap(), (152..=251, None));
+        assert!(parse_mark("1-151").is_err());
+        assert!(parse_mark("151-1 seen").is_err());
+        assert!(parse_mark("1-x seen").is_err());
+        assert!(parse_mark("1-3 owned")
+            .unwrap_err()
+            .to_string()
+            .contains("unknown status 'owned'"));
+    }
+
+    #[test]
+    fn test_import_export() {
+        let mut collection = Collection::default();
+        collection.set(25, Some(Status::Shiny));
+        collection.game = "red-blue".to_string();
+        collection.set(1, Some(Status::Seen));
+        collection.set(10034, Some(Status::Caught));
+
+        let csv = collection.to_csv();
+        assert_eq!(
+            csv,
+            "game,id,status\nred-blue,1,seen\nred-blue,10034,caught\nscarlet-violet,25,shiny\n"
+        );
+        let mut imported = Collection::default();
+        assert_eq!(imported.import_csv(&csv).unwrap(), 3);
+        assert_eq!(imported.games, collection.games);
+
+        let mut imported = Collection::default();
+        assert_eq!(
+            imported
+                .import_json(&collection.to_json().unwrap())
+                .unwrap(),
+            3
+        );
+        assert_eq!(imported.games, collection.games);
+
+        // Empty statuses clear; a bad row leaves everything as it was
+        assert_eq!(imported.import_csv("red-blue,1,\n").unwrap(), 1);
+        assert_eq!(imported.games["red-blue"].get(&1), None);
+        let error = imported.import_csv("x-y,1,seen\nx-y,2,lost\n").unwrap_err();
+        assert!(error.to_string().contains("line 2: unknown status 'lost'"));
+        assert!(!imported.games.contains_key("x-y"));
+    }
+
+    #[test]
+    fn test_collection_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("collection.json");
+        assert_eq!(Collection::load_from(&path), (Collection::default(), None));
+
+        let mut collection = Collection {
+            game: "x-y".to_string(),
+            ..Default::default()
+        };
+        collection.set(6, Some(Status::Caught));
+        collection.save_to(&path).unwrap();
+        assert_eq!(Collection::load_from(&path), (collection, None));
+
+        std::fs::write(&path, r#"{"games": 3}"#).unwrap();
+        let (collection, error) = Collection::load_from(&path);
+        assert_eq!(collection, Collection::default());
+        assert!(error.unwrap().contains("collection.json.corrupt"));
+    }
+
+    #[test]
+    fn test_unreadable_collection_is_read_only() {
+        let dir = tempfile::tempdir().unwrap();
+        // A directory can't be read as a file, like one without permission
+        let path = dir.path().join("collection.json");
+        std::fs::create_dir(&path).unwrap();
+
+        let (collection, error) = Collection::load_from(&path);
+        assert!(collection.read_only);
+        assert!(error.unwrap().contains("won't be saved"));
+        assert!(!dir.path().join("collection.json.corrupt").exists());
+        assert!(collection.save_to(&path).is_err());
+        assert!(path.is_dir());
+    }
+}
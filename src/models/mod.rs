@@ -1,3 +1,38 @@
+pub mod collection;
+pub mod notes;
 pub mod pokemon;
 pub mod team;
 pub mod type_data;
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// `teams.json` -> `teams.json.{suffix}`
+pub(crate) fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Write through a temporary file so a crash can't leave half a file behind
+pub(crate) fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = sibling(path, "tmp");
+    let mut out = std::fs::File::create(&tmp)?;
+    out.write_all(contents.as_bytes())?;
+    out.sync_all()?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Copy a file that couldn't be loaded to `<name>.corrupt` before it gets
+/// saved over, saying where it went
+pub(crate) fn keep_corrupt_copy(path: &Path) -> String {
+    let corrupt = sibling(path, "corrupt");
+    match std::fs::copy(path, &corrupt) {
+        Ok(_) => format!("A copy was kept at {}.", corrupt.display()),
+        Err(_) => "No copy could be kept.".to_string(),
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{keep_corrupt_copy, write_atomic};
+
+/// Favorites, tags and notes the user keeps on Pokémon, by Pokémon id
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Notes {
+    #[serde(default)]
+    pub pokemon: BTreeMap<u32, PokemonNotes>,
+    /// Stand-in for a file that couldn't be read, which mustn't be saved over
+    #[serde(skip)]
+    pub read_only: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PokemonNotes {
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub favorite: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Markdown
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+impl PokemonNotes {
+    fn is_empty(&self) -> bool {
+        !self.favorite && self.tags.is_empty() && self.note.trim().is_empty()
+    }
+}
+
+/// Tags are compared and stored trimmed and lowercase
+pub fn normalize_tag(tag: &str) -> String {
+    tag.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+impl Notes {
+    pub fn get(&self, id: u32) -> Option<&PokemonNotes> {
+        self.pokemon.get(&id)
+    }
+
+    pub fn is_favorite(&self, id: u32) -> bool {
+        self.get(id).is_some_and(|n| n.favorite)
+    }
+
+    pub fn tags(&self, id: u32) -> &[String] {
+        self.get(id).map_or(&[], |n| &n.tags)
+    }
+
+    /// Whether `id` has every one of `tags`
+    pub fn has_tags(&self, id: u32, tags: &[String]) -> bool {
+        let own = self.tags(id);
+        tags.iter().all(|t| own.contains(t))
+    }
+
+    /// Flip the favorite mark, returning the new state
+    pub fn toggle_favorite(&mut self, id: u32) -> bool {
+        self.edit(id, |n| {
+            n.favorite = !n.favorite;
+            n.favorite
+        })
+    }
+
+    /// Add the tag or take it off, returning whether it's now there
+    pub fn toggle_tag(&mut self, id: u32, tag: &str) -> bool {
+        let tag = normalize_tag(tag);
+        if tag.is_empty() {
+            return false;
+        }
+        self.edit(id, |n| match n.tags.iter().position(|t| *t == tag) {
+            Some(pos) => {
+                n.tags.remove(pos);
+                false
+            }
+            None => {
+                n.tags.push(tag);
+                n.tags.sort();
+                true
+            }
+        })
+    }
+
+    pub fn set_note(&mut self, id: u32, note: &str) {
+        self.edit(id, |n| n.note = note.trim_end().to_string());
+    }
+
+    /// Every tag in use with how many Pokémon have it, alphabetically
+    pub fn all_tags(&self) -> Vec<(String, usize)> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for tag in self.pokemon.values().flat_map(|n| &n.tags) {
+            *counts.entry(tag).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(tag, count)| (tag.to_string(), count))
+            .collect()
+    }
+
+    /// Change one Pokémon's entry, dropping it once there's nothing left in it
+    fn edit<T>(&mut self, id: u32, f: impl FnOnce(&mut PokemonNotes) -> T) -> T {
+        let entry = self.pokemon.entry(id).or_default();
+        let result = f(entry);
+        if entry.is_empty() {
+            self.pokemon.remove(&id);
+        }
+        result
+    }
+
+    pub fn file_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("pokemon-tui")
+            .join("notes.json")
+    }
+
+    pub fn load() -> (Self, Option<String>) {
+        Self::load_from(&Self::file_path())
+    }
+
+    /// Notes at `path`; a file that can't be parsed is copied aside and
+    /// reported, starting over with no notes. One that can't be read at all
+    /// is left alone with a read-only stand-in.
+    pub fn load_from(path: &Path) -> (Self, Option<String>) {
+        match Self::read_from(path) {
+            Ok(notes) => (notes, None),
+            Err(e) if e.downcast_ref::<std::io::Error>().is_some() => {
+                let message = format!("{:#}\nNotes won't be saved until it can be read.", e);
+                let notes = Self {
+                    read_only: true,
+                    ..Self::default()
+                };
+                (notes, Some(message))
+            }
+            Err(e) => {
+                let message = format!(
+                    "{:#}\nStarting without notes. {}",
+                    e,
+                    keep_corrupt_copy(path)
+                );
+                (Self::default(), Some(message))
+            }
+        }
+    }
+
+    pub fn read_from(path: &Path) -> Result<Self> {
+        let data = match std::fs::read_to_string(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("Couldn't read {}", path.display())),
+        };
+        serde_json::from_str(&data).with_context(|| format!("Couldn't parse {}", path.display()))
+    }
+
+    /// Replace the file atomically; read-only notes are never written
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if self.read_only {
+            return Err(std::io::Error::new(
+                ErrorKind::PermissionDenied,
+                format!("{} couldn't be loaded", path.display()),
+            ));
+        }
+        write_atomic(path, &serde_json::to_string_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_favorites_and_tags() {
+        let mut notes = Notes::default();
+        assert!(notes.toggle_favorite(6));
+        assert!(notes.is_favorite(6));
+        assert!(notes.toggle_tag(6, "  Rain   Team "));
+        assert!(notes.toggle_tag(6, "sweeper"));
+        assert!(notes.toggle_tag(9, "rain team"));
+        assert!(!notes.toggle_tag(9, ""));
+        assert_eq!(notes.tags(6), ["rain team", "sweeper"]);
+        assert!(notes.has_tags(6, &["sweeper".to_string()]));
+        assert!(!notes.has_tags(9, &["rain team".to_string(), "sweeper".to_string()]));
+        assert_eq!(
+            notes.all_tags(),
+            vec![("rain team".to_string(), 2), ("sweeper".to_string(), 1)]
+        );
+
+        // Entries with nothing left in them go away
+        assert!(!notes.toggle_tag(9, "Rain team"));
+        assert_eq!(notes.get(9), None);
+        notes.set_note(9, "  \n");
+        assert_eq!(notes.get(9), None);
+    }
+
+    #[test]
+    fn test_notes_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        assert_eq!(Notes::load_from(&path), (Notes::default(), None));
+
+        let mut notes = Notes::default();
+        notes.toggle_favorite(25);
+        notes.set_note(6, "# Plan\n- needs EV training\n");
+        notes.save_to(&path).unwrap();
+        let data = std::fs::read_to_string(&path).unwrap();
+        assert!(!data.contains("tags"));
+        assert_eq!(Notes::load_from(&path), (notes, None));
+
+        std::fs::write(&path, "{\"pokemon\": ").unwrap();
+        let (notes, error) = Notes::load_from(&path);
+        assert_eq!(notes, Notes::default());
+        assert!(error.unwrap().contains("notes.json.corrupt"));
+        assert!(dir.path().join("notes.json.corrupt").exists());
+    }
+
+    #[test]
+    fn test_unreadable_notes_are_read_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        std::fs::create_dir(&path).unwrap();
+
+        let (notes, error) = Notes::load_from(&path);
+        assert!(notes.read_only);
+        assert!(error.unwrap().contains("won't be saved"));
+        assert!(notes.save_to(&path).is_err());
+        assert!(path.is_dir());
+    }
+}
@@ -24,6 +24,8 @@ pub struct PokemonDetail {
     pub abilities: Vec<AbilitySlot>,
     pub moves: Vec<MoveEntry>,
     pub sprites: Sprites,
+    #[serde(default)]
+    pub species: Option<NamedResource>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -51,9 +53,207 @@ pub struct MoveEntry {
     pub move_info: NamedResource,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct Sprites {
     pub front_default: Option<String>,
+    #[serde(default)]
+    pub front_shiny: Option<String>,
+    #[serde(default)]
+    pub front_female: Option<String>,
+    #[serde(default)]
+    pub front_shiny_female: Option<String>,
+    #[serde(default)]
+    pub back_default: Option<String>,
+    #[serde(default)]
+    pub back_shiny: Option<String>,
+    #[serde(default)]
+    pub back_female: Option<String>,
+    #[serde(default)]
+    pub back_shiny_female: Option<String>,
+    #[serde(default)]
+    pub other: Option<OtherSprites>,
+    #[serde(default)]
+    pub versions: Option<VersionSprites>,
+}
+
+/// The `other` block: large official artwork and the Pokémon HOME renders
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OtherSprites {
+    #[serde(rename = "official-artwork", default)]
+    pub official_artwork: Option<ArtworkSprites>,
+    #[serde(default)]
+    pub home: Option<ArtworkSprites>,
+}
+
+/// Per-game sprite sets; only the Black/White animated GIFs are used
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct VersionSprites {
+    #[serde(rename = "generation-v", default)]
+    pub generation_v: Option<GenerationVSprites>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GenerationVSprites {
+    #[serde(rename = "black-white", default)]
+    pub black_white: Option<BlackWhiteSprites>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BlackWhiteSprites {
+    #[serde(default)]
+    pub animated: Option<AnimatedSprites>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AnimatedSprites {
+    #[serde(default)]
+    pub front_default: Option<String>,
+    #[serde(default)]
+    pub front_shiny: Option<String>,
+    #[serde(default)]
+    pub front_female: Option<String>,
+    #[serde(default)]
+    pub front_shiny_female: Option<String>,
+    #[serde(default)]
+    pub back_default: Option<String>,
+    #[serde(default)]
+    pub back_shiny: Option<String>,
+    #[serde(default)]
+    pub back_female: Option<String>,
+    #[serde(default)]
+    pub back_shiny_female: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ArtworkSprites {
+    #[serde(default)]
+    pub front_default: Option<String>,
+    #[serde(default)]
+    pub front_shiny: Option<String>,
+    #[serde(default)]
+    pub front_female: Option<String>,
+    #[serde(default)]
+    pub front_shiny_female: Option<String>,
+}
+
+/// Which sprite image the detail screen shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpriteVariant {
+    FrontDefault,
+    FrontShiny,
+    FrontFemale,
+    FrontShinyFemale,
+    BackDefault,
+    BackShiny,
+    BackFemale,
+    BackShinyFemale,
+    OfficialArtwork,
+    OfficialArtworkShiny,
+    Home,
+    HomeShiny,
+    HomeFemale,
+    HomeShinyFemale,
+}
+
+impl SpriteVariant {
+    pub fn all() -> &'static [SpriteVariant] {
+        &[
+            SpriteVariant::FrontDefault,
+            SpriteVariant::FrontShiny,
+            SpriteVariant::FrontFemale,
+            SpriteVariant::FrontShinyFemale,
+            SpriteVariant::BackDefault,
+            SpriteVariant::BackShiny,
+            SpriteVariant::BackFemale,
+            SpriteVariant::BackShinyFemale,
+            SpriteVariant::OfficialArtwork,
+            SpriteVariant::OfficialArtworkShiny,
+            SpriteVariant::Home,
+            SpriteVariant::HomeShiny,
+            SpriteVariant::HomeFemale,
+            SpriteVariant::HomeShinyFemale,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SpriteVariant::FrontDefault => "Front",
+            SpriteVariant::FrontShiny => "Shiny",
+            SpriteVariant::FrontFemale => "Female",
+            SpriteVariant::FrontShinyFemale => "Shiny Female",
+            SpriteVariant::BackDefault => "Back",
+            SpriteVariant::BackShiny => "Back Shiny",
+            SpriteVariant::BackFemale => "Back Female",
+            SpriteVariant::BackShinyFemale => "Back Shiny Female",
+            SpriteVariant::OfficialArtwork => "Artwork",
+            SpriteVariant::OfficialArtworkShiny => "Artwork Shiny",
+            SpriteVariant::Home => "HOME",
+            SpriteVariant::HomeShiny => "HOME Shiny",
+            SpriteVariant::HomeFemale => "HOME Female",
+            SpriteVariant::HomeShinyFemale => "HOME Shiny Female",
+        }
+    }
+}
+
+impl Sprites {
+    pub fn url(&self, variant: SpriteVariant) -> Option<&str> {
+        let artwork = self
+            .other
+            .as_ref()
+            .and_then(|o| o.official_artwork.as_ref());
+        let home = self.other.as_ref().and_then(|o| o.home.as_ref());
+        let url = match variant {
+            SpriteVariant::FrontDefault => &self.front_default,
+            SpriteVariant::FrontShiny => &self.front_shiny,
+            SpriteVariant::FrontFemale => &self.front_female,
+            SpriteVariant::FrontShinyFemale => &self.front_shiny_female,
+            SpriteVariant::BackDefault => &self.back_default,
+            SpriteVariant::BackShiny => &self.back_shiny,
+            SpriteVariant::BackFemale => &self.back_female,
+            SpriteVariant::BackShinyFemale => &self.back_shiny_female,
+            SpriteVariant::OfficialArtwork => return artwork?.front_default.as_deref(),
+            SpriteVariant::OfficialArtworkShiny => return artwork?.front_shiny.as_deref(),
+            SpriteVariant::Home => return home?.front_default.as_deref(),
+            SpriteVariant::HomeShiny => return home?.front_shiny.as_deref(),
+            SpriteVariant::HomeFemale => return home?.front_female.as_deref(),
+            SpriteVariant::HomeShinyFemale => return home?.front_shiny_female.as_deref(),
+        };
+        url.as_deref()
+    }
+
+    /// Animated GIF for a game-sprite variant (Gen 5 Black/White set); artwork has none
+    pub fn animated_url(&self, variant: SpriteVariant) -> Option<&str> {
+        let animated = self
+            .versions
+            .as_ref()?
+            .generation_v
+            .as_ref()?
+            .black_white
+            .as_ref()?
+            .animated
+            .as_ref()?;
+        let url = match variant {
+            SpriteVariant::FrontDefault => &animated.front_default,
+            SpriteVariant::FrontShiny => &animated.front_shiny,
+            SpriteVariant::FrontFemale => &animated.front_female,
+            SpriteVariant::FrontShinyFemale => &animated.front_shiny_female,
+            SpriteVariant::BackDefault => &animated.back_default,
+            SpriteVariant::BackShiny => &animated.back_shiny,
+            SpriteVariant::BackFemale => &animated.back_female,
+            SpriteVariant::BackShinyFemale => &animated.back_shiny_female,
+            _ => return None,
+        };
+        url.as_deref()
+    }
+
+    /// Variants this Pokémon actually has an image for, in display order
+    pub fn available(&self) -> Vec<SpriteVariant> {
+        SpriteVariant::all()
+            .iter()
+            .copied()
+            .filter(|&v| self.url(v).is_some())
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -62,12 +262,167 @@ pub struct NamedResource {
     pub url: String,
 }
 
+impl NamedResource {
+    /// Numeric id from the resource URL: ".../pokemon-species/25/" -> 25
+    pub fn id(&self) -> Option<u32> {
+        self.url
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()?
+            .parse()
+            .ok()
+    }
+}
+
+/// Pokémon ids at or above this are alternate forms (regional, Mega, Gigantamax, ...)
+pub const FORM_ID_START: u32 = 10000;
+
+/// Species data from /pokemon-species/{id}
+#[derive(Debug, Clone, Deserialize)]
+pub struct PokemonSpecies {
+    pub id: u32,
+    pub name: String,
+    pub generation: NamedResource,
+    pub varieties: Vec<SpeciesVariety>,
+    /// Where to fetch the species' evolution chain
+    #[serde(default)]
+    pub evolution_chain: Option<Resource>,
+}
+
+/// Link to an unnamed resource, such as an evolution chain
+#[derive(Debug, Clone, Deserialize)]
+pub struct Resource {
+    pub url: String,
+}
+
+/// Evolution chain from /evolution-chain/{id}
+#[derive(Debug, Clone, Deserialize)]
+pub struct EvolutionChain {
+    pub id: u32,
+    pub chain: ChainLink,
+}
+
+/// A species in an evolution chain and what it evolves into
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChainLink {
+    pub species: NamedResource,
+    #[serde(default)]
+    pub evolves_to: Vec<ChainLink>,
+}
+
+impl EvolutionChain {
+    /// Species by stage: the base first, then everything one evolution on,
+    /// and so on. Branching lines like Eevee's have several in a stage.
+    pub fn stages(&self) -> Vec<Vec<&NamedResource>> {
+        let mut stages = Vec::new();
+        let mut links = vec![&self.chain];
+        while !links.is_empty() {
+            stages.push(links.iter().map(|l| &l.species).collect());
+            links = links.iter().flat_map(|l| &l.evolves_to).collect();
+        }
+        stages
+    }
+
+    pub fn contains(&self, species_id: u32) -> bool {
+        self.stages()
+            .iter()
+            .flatten()
+            .any(|s| s.id() == Some(species_id))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpeciesVariety {
+    pub is_default: bool,
+    pub pokemon: NamedResource,
+}
+
+impl PokemonSpecies {
+    /// Generation number from the generation resource name ("generation-iv" -> 4)
+    pub fn generation_number(&self) -> Option<u8> {
+        let numeral = self.generation.name.strip_prefix("generation-")?;
+        let value = |c: char| match c {
+            'i' => Some(1),
+            'v' => Some(5),
+            'x' => Some(10),
+            _ => None,
+        };
+        let digits: Vec<u8> = numeral.chars().map(value).collect::<Option<_>>()?;
+        let mut total = 0i32;
+        for (i, &d) in digits.iter().enumerate() {
+            if digits.get(i + 1).is_some_and(|&next| next > d) {
+                total -= d as i32;
+            } else {
+                total += d as i32;
+            }
+        }
+        u8::try_from(total).ok()
+    }
+}
+
+/// Generation from /generation/{id}, with the species it introduced
+#[derive(Debug, Clone, Deserialize)]
+pub struct Generation {
+    pub id: u8,
+    pub pokemon_species: Vec<NamedResource>,
+}
+
 /// Summary used in the list screen (built from list + individual fetches)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct PokemonSummary {
     pub id: u32,
     pub name: String,
     pub types: Vec<String>,
+    /// Base stats in API order (HP, Atk, Def, Sp.Atk, Sp.Def, Speed); empty until fetched
+    pub stats: Vec<u32>,
+    pub height: Option<u32>,
+    pub weight: Option<u32>,
+    /// National dex number of the species; differs from `id` for alternate forms
+    pub species_id: Option<u32>,
+    /// Generation the species was introduced in, once species data is loaded
+    pub generation: Option<u8>,
+}
+
+impl PokemonSummary {
+    /// Fully populated summary from a detail response
+    pub fn from_detail(detail: &PokemonDetail) -> Self {
+        Self {
+            id: detail.id,
+            name: detail.name.clone(),
+            types: detail
+                .types
+                .iter()
+                .map(|t| t.type_info.name.clone())
+                .collect(),
+            stats: detail.stats.iter().map(|s| s.base_stat).collect(),
+            height: Some(detail.height),
+            weight: Some(detail.weight),
+            species_id: detail.species.as_ref().and_then(|s| s.id()),
+            generation: None,
+        }
+    }
+
+    pub fn is_form(&self) -> bool {
+        self.id >= FORM_ID_START
+    }
+
+    /// Species dex number if known; a base form's id is its species number
+    pub fn known_species_id(&self) -> Option<u32> {
+        self.species_id.or((!self.is_form()).then_some(self.id))
+    }
+
+    /// Species dex number, falling back to the Pokémon id until it is known
+    pub fn species_number(&self) -> u32 {
+        self.known_species_id().unwrap_or(self.id)
+    }
+
+    pub fn base_stat_total(&self) -> Option<u32> {
+        if self.stats.is_empty() {
+            None
+        } else {
+            Some(self.stats.iter().sum())
+        }
+    }
 }
 
 /// Move detail from /move/{id}
@@ -83,6 +438,30 @@ pub struct MoveDetail {
     pub damage_class: Option<NamedResource>,
 }
 
+/// Ability detail from /ability/{name}
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbilityDetail {
+    pub name: String,
+    #[serde(default)]
+    pub effect_entries: Vec<EffectEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EffectEntry {
+    pub short_effect: String,
+    pub language: NamedResource,
+}
+
+impl AbilityDetail {
+    /// One-sentence English description, if PokéAPI has one
+    pub fn short_effect(&self) -> Option<&str> {
+        self.effect_entries
+            .iter()
+            .find(|e| e.language.name == "en")
+            .map(|e| e.short_effect.as_str())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,12 +472,44 @@ mod tests {
             id: 25,
             name: "pikachu".to_string(),
             types: vec!["electric".to_string()],
+            ..Default::default()
         };
 
         assert_eq!(summary.id, 25);
         assert_eq!(summary.name, "pikachu");
         assert_eq!(summary.types.len(), 1);
         assert_eq!(summary.types[0], "electric");
+        assert!(summary.stats.is_empty());
+        assert_eq!(summary.base_stat_total(), None);
+    }
+
+    #[test]
+    fn test_pokemon_summary_from_detail() {
+        let json = r#"{
+            "id": 25,
+            "name": "pikachu",
+            "height": 4,
+            "weight": 60,
+            "types": [
+                {"slot": 1, "type": {"name": "electric", "url": "https://pokeapi.co/api/v2/type/13/"}}
+            ],
+            "stats": [
+                {"base_stat": 35, "stat": {"name": "hp", "url": "https://pokeapi.co/api/v2/stat/1/"}},
+                {"base_stat": 90, "stat": {"name": "speed", "url": "https://pokeapi.co/api/v2/stat/6/"}}
+            ],
+            "abilities": [],
+            "moves": [],
+            "sprites": {"front_default": null}
+        }"#;
+
+        let detail: PokemonDetail = serde_json::from_str(json).unwrap();
+        let summary = PokemonSummary::from_detail(&detail);
+        assert_eq!(summary.id, 25);
+        assert_eq!(summary.types, vec!["electric".to_string()]);
+        assert_eq!(summary.stats, vec![35, 90]);
+        assert_eq!(summary.base_stat_total(), Some(125));
+        assert_eq!(summary.height, Some(4));
+        assert_eq!(summary.weight, Some(60));
     }
 
     #[test]
@@ -151,6 +562,197 @@ mod tests {
         assert!(detail.sprites.front_default.is_some());
     }
 
+    #[test]
+    fn test_sprites_variants_deserialization() {
+        let json = r#"{
+            "front_default": "https://example.com/25.png",
+            "front_shiny": "https://example.com/shiny/25.png",
+            "front_female": "https://example.com/female/25.png",
+            "front_shiny_female": null,
+            "back_default": "https://example.com/back/25.png",
+            "back_shiny": null,
+            "other": {
+                "official-artwork": {
+                    "front_default": "https://example.com/official-artwork/25.png",
+                    "front_shiny": "https://example.com/official-artwork/shiny/25.png"
+                },
+                "home": {
+                    "front_default": "https://example.com/home/25.png",
+                    "front_female": null
+                },
+                "showdown": {"front_default": "https://example.com/showdown/25.gif"}
+            }
+        }"#;
+
+        let sprites: Sprites = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            sprites.url(SpriteVariant::FrontShiny),
+            Some("https://example.com/shiny/25.png")
+        );
+        assert_eq!(
+            sprites.url(SpriteVariant::OfficialArtwork),
+            Some("https://example.com/official-artwork/25.png")
+        );
+        assert_eq!(sprites.url(SpriteVariant::HomeFemale), None);
+        assert_eq!(
+            sprites.available(),
+            vec![
+                SpriteVariant::FrontDefault,
+                SpriteVariant::FrontShiny,
+                SpriteVariant::FrontFemale,
+                SpriteVariant::BackDefault,
+                SpriteVariant::OfficialArtwork,
+                SpriteVariant::OfficialArtworkShiny,
+                SpriteVariant::Home,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sprites_animated_url() {
+        let json = r#"{
+            "front_default": "https://example.com/25.png",
+            "versions": {
+                "generation-i": {"red-blue": {"front_default": null}},
+                "generation-v": {
+                    "black-white": {
+                        "animated": {
+                            "front_default": "https://example.com/bw/animated/25.gif",
+                            "back_default": "https://example.com/bw/animated/back/25.gif",
+                            "front_shiny": null
+                        },
+                        "front_default": "https://example.com/bw/25.png"
+                    }
+                }
+            }
+        }"#;
+        let sprites: Sprites = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            sprites.animated_url(SpriteVariant::FrontDefault),
+            Some("https://example.com/bw/animated/25.gif")
+        );
+        assert_eq!(
+            sprites.animated_url(SpriteVariant::BackDefault),
+            Some("https://example.com/bw/animated/back/25.gif")
+        );
+        assert_eq!(sprites.animated_url(SpriteVariant::FrontShiny), None);
+        assert_eq!(sprites.animated_url(SpriteVariant::OfficialArtwork), None);
+        assert_eq!(
+            Sprites::default().animated_url(SpriteVariant::FrontDefault),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sprites_minimal() {
+        let sprites: Sprites = serde_json::from_str(r#"{"front_default": null}"#).unwrap();
+        assert!(sprites.available().is_empty());
+        assert_eq!(sprites.url(SpriteVariant::Home), None);
+    }
+
+    #[test]
+    fn test_named_resource_id() {
+        let res = NamedResource {
+            name: "charizard".to_string(),
+            url: "https://pokeapi.co/api/v2/pokemon-species/6/".to_string(),
+        };
+        assert_eq!(res.id(), Some(6));
+    }
+
+    #[test]
+    fn test_form_summary_from_detail() {
+        let json = r#"{
+            "id": 10034,
+            "name": "charizard-mega-x",
+            "height": 17,
+            "weight": 1105,
+            "types": [],
+            "stats": [],
+            "abilities": [],
+            "moves": [],
+            "sprites": {"front_default": null},
+            "species": {"name": "charizard", "url": "https://pokeapi.co/api/v2/pokemon-species/6/"}
+        }"#;
+
+        let detail: PokemonDetail = serde_json::from_str(json).unwrap();
+        let summary = PokemonSummary::from_detail(&detail);
+        assert!(summary.is_form());
+        assert_eq!(summary.species_id, Some(6));
+        assert_eq!(summary.species_number(), 6);
+    }
+
+    #[test]
+    fn test_species_deserialization() {
+        let json = r#"{
+            "id": 6,
+            "name": "charizard",
+            "generation": {"name": "generation-i", "url": "https://pokeapi.co/api/v2/generation/1/"},
+            "varieties": [
+                {"is_default": true, "pokemon": {"name": "charizard", "url": "https://pokeapi.co/api/v2/pokemon/6/"}},
+                {"is_default": false, "pokemon": {"name": "charizard-mega-x", "url": "https://pokeapi.co/api/v2/pokemon/10034/"}}
+            ]
+        }"#;
+
+        let species: PokemonSpecies = serde_json::from_str(json).unwrap();
+        assert_eq!(species.id, 6);
+        assert_eq!(species.generation_number(), Some(1));
+        assert_eq!(species.varieties.len(), 2);
+        assert!(species.varieties[0].is_default);
+        assert_eq!(species.varieties[1].pokemon.id(), Some(10034));
+    }
+
+    #[test]
+    fn test_evolution_chain_stages() {
+        let link = |name: &str, id: u32, evolves_to: &str| {
+            format!(
+                r#"{{"species": {{"name": "{}", "url": "https://pokeapi.co/api/v2/pokemon-species/{}/"}}, "evolves_to": [{}]}}"#,
+                name, id, evolves_to
+            )
+        };
+        let branches = [
+            link("vaporeon", 134, ""),
+            link("jolteon", 135, ""),
+            link("flareon", 136, ""),
+        ]
+        .join(",");
+        let json = format!(
+            r#"{{"id": 67, "chain": {}}}"#,
+            link("eevee", 133, &branches)
+        );
+
+        let chain: EvolutionChain = serde_json::from_str(&json).unwrap();
+        let names: Vec<Vec<&str>> = chain
+            .stages()
+            .iter()
+            .map(|stage| stage.iter().map(|s| s.name.as_str()).collect())
+            .collect();
+        assert_eq!(
+            names,
+            vec![vec!["eevee"], vec!["vaporeon", "jolteon", "flareon"]]
+        );
+        assert!(chain.contains(135));
+        assert!(!chain.contains(25));
+    }
+
+    #[test]
+    fn test_species_generation_number() {
+        let species = |gen: &str| PokemonSpecies {
+            id: 1,
+            name: "test".to_string(),
+            generation: NamedResource {
+                name: gen.to_string(),
+                url: String::new(),
+            },
+            varieties: vec![],
+            evolution_chain: None,
+        };
+        assert_eq!(species("generation-iv").generation_number(), Some(4));
+        assert_eq!(species("generation-vii").generation_number(), Some(7));
+        assert_eq!(species("generation-ix").generation_number(), Some(9));
+        assert_eq!(species("generation-viii").generation_number(), Some(8));
+        assert_eq!(species("unknown").generation_number(), None);
+    }
+
     #[test]
     fn test_move_detail_deserialization() {
         let json = r#"{
@@ -193,4 +795,23 @@ mod tests {
         assert_eq!(move_detail.pp, None);
         assert!(move_detail.damage_class.is_none());
     }
+
+    #[test]
+    fn test_ability_short_effect() {
+        let json = r#"{
+            "name": "static",
+            "effect_entries": [
+                {"short_effect": "Peut paralyser.", "language": {"name": "fr", "url": "https://pokeapi.co/api/v2/language/5/"}},
+                {"short_effect": "Has a 30% chance of paralyzing attacking Pokémon on contact.", "language": {"name": "en", "url": "https://pokeapi.co/api/v2/language/9/"}}
+            ]
+        }"#;
+
+        let ability: AbilityDetail = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            ability.short_effect(),
+            Some("Has a 30% chance of paralyzing attacking Pokémon on contact.")
+        );
+        let bare: AbilityDetail = serde_json::from_str(r#"{"name": "x"}"#).unwrap();
+        assert_eq!(bare.short_effect(), None);
+    }
 }
@@ -1,8 +1,19 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::models::{keep_corrupt_copy, sibling, write_atomic};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TeamData {
     pub teams: Vec<Team>,
+    /// Stand-in for a file written by a newer version, which mustn't be
+    /// saved over
+    #[serde(skip)]
+    pub read_only: bool,
 }
 
 impl Default for TeamData {
@@ -12,6 +23,7 @@ impl Default for TeamData {
                 name: "Team 1".to_string(),
                 members: Vec::new(),
             }],
+            read_only: false,
         }
     }
 }
@@ -37,60 +49,211 @@ pub struct TeamMove {
     pub power: Option<u32>,
 }
 
+/// Layout version written to teams.json. Files from before it was added
+/// have no `version` and count as 1.
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// `MIGRATIONS[n - 1]` upgrades a version `n` file to `n + 1`
+const MIGRATIONS: [fn(&mut Value); 1] = [
+    // 1 -> 2 only added the version field
+    |_| {},
+];
+
+/// A teams file from a newer version, with a schema this one can't read
+#[derive(Debug)]
+pub struct NewerVersion(pub u64);
+
+impl std::fmt::Display for NewerVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "it was written by a newer version (schema {}, this one reads up to {})",
+            self.0, SCHEMA_VERSION
+        )
+    }
+}
+
+impl std::error::Error for NewerVersion {}
+
+/// How many earlier copies are kept as teams.json.1, .2, ...
+const BACKUPS: usize = 3;
+
 impl TeamData {
-    pub fn load() -> Self {
-        let path = Self::file_path();
-        if path.exists() {
-            let data = std::fs::read_to_string(&path).unwrap_or_default();
-            serde_json::from_str(&data).unwrap_or_default()
-        } else {
-            Self::default()
+    /// Teams from the data dir, moving them over from the cache dir where
+    /// older versions kept them. Problems come back as a message.
+    pub fn load() -> (Self, Option<String>) {
+        let moved = Self::move_from_cache_dir();
+        let (data, error) = Self::load_from(&Self::file_path());
+        (data, moved.err().or(error))
+    }
+
+    /// Move teams.json out of the cache dir where older versions kept it
+    pub fn move_from_cache_dir() -> std::result::Result<(), String> {
+        Self::move_legacy_file(&Self::legacy_file_path(), &Self::file_path())
+    }
+
+    /// Teams at `path`. A missing file gives the default team; one that can't
+    /// be read is copied to `teams.json.corrupt` and replaced by the default
+    /// team, with a message saying so. A file from a newer version is left
+    /// alone, with a read-only default team standing in for it.
+    pub fn load_from(path: &Path) -> (Self, Option<String>) {
+        match Self::read_from(path) {
+            Ok(data) => (data, None),
+            Err(e) if e.downcast_ref::<NewerVersion>().is_some() => {
+                let message = format!(
+                    "{:#}\nTeams can't be edited until it's updated, so the file stays as it is.",
+                    e
+                );
+                let data = Self {
+                    read_only: true,
+                    ..Self::default()
+                };
+                (data, Some(message))
+            }
+            Err(e) => {
+                let message = format!(
+                    "{:#}\nStarting with an empty team. {}",
+                    e,
+                    keep_corrupt_copy(path)
+                );
+                (Self::default(), Some(message))
+            }
         }
     }
 
-    pub fn save(&self) {
-        let path = Self::file_path();
-        if let Some(parent) = path.parent() {
-            let _ = std::fs::create_dir_all(parent);
+    /// Teams at `path`, upgraded to the current schema, or the default team if
+    /// there's no file yet
+    pub fn read_from(path: &Path) -> Result<Self> {
+        let data = match std::fs::read_to_string(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("Couldn't read {}", path.display())),
+        };
+        let mut value: Value = serde_json::from_str(&data)
+            .with_context(|| format!("Couldn't parse {}", path.display()))?;
+        migrate(&mut value).with_context(|| format!("Couldn't load {}", path.display()))?;
+        let data: TeamData = serde_json::from_value(value)
+            .with_context(|| format!("Couldn't parse {}", path.display()))?;
+        if data.teams.is_empty() {
+            bail!("{} has no teams", path.display());
         }
-        if let Ok(data) = serde_json::to_string_pretty(self) {
-            let _ = std::fs::write(&path, data);
+        Ok(data)
+    }
+
+    /// Replace the file atomically, keeping the previous versions as
+    /// numbered backups. Read-only teams are never written.
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if self.read_only {
+            return Err(std::io::Error::new(
+                ErrorKind::PermissionDenied,
+                format!("{} is from a newer version", path.display()),
+            ));
         }
+        let file = TeamFile {
+            version: SCHEMA_VERSION,
+            teams: &self.teams,
+        };
+        rotate_backups(path)?;
+        write_atomic(path, &serde_json::to_string_pretty(&file)?)
     }
 
-    fn file_path() -> std::path::PathBuf {
+    pub fn file_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("pokemon-tui")
+            .join("teams.json")
+    }
+
+    /// Where teams were kept before they moved to the data dir
+    fn legacy_file_path() -> PathBuf {
         dirs::cache_dir()
-            .unwrap_or_else(|| std::path::PathBuf::from("."))
+            .unwrap_or_else(|| PathBuf::from("."))
             .join("pokemon-tui")
             .join("teams.json")
     }
 
+    /// Move `old` to `new` unless there's already a file at `new`
+    fn move_legacy_file(old: &Path, new: &Path) -> std::result::Result<(), String> {
+        if old == new || new.exists() || !old.exists() {
+            return Ok(());
+        }
+        let moved = new
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            // The two dirs can be on different file systems
+            .and_then(|_| std::fs::rename(old, new).or_else(|_| copy_then_remove(old, new)));
+        moved.map_err(|e| {
+            format!(
+                "Couldn't move {} to {}: {}",
+                old.display(),
+                new.display(),
+                e
+            )
+        })
+    }
+
     #[cfg(test)]
-    pub(crate) fn file_path_for_testing(base: std::path::PathBuf) -> std::path::PathBuf {
+    pub(crate) fn file_path_for_testing(base: PathBuf) -> PathBuf {
         base.join("pokemon-tui").join("teams.json")
     }
 
     #[cfg(test)]
-    pub(crate) fn save_to_path(&self, base_path: std::path::PathBuf) {
-        let path = Self::file_path_for_testing(base_path);
-        if let Some(parent) = path.parent() {
-            let _ = std::fs::create_dir_all(parent);
-        }
-        if let Ok(data) = serde_json::to_string_pretty(self) {
-            let _ = std::fs::write(&path, data);
-        }
+    pub(crate) fn save_to_path(&self, base_path: PathBuf) {
+        let _ = self.save_to(&Self::file_path_for_testing(base_path));
     }
 
     #[cfg(test)]
-    pub(crate) fn load_from_path(base_path: std::path::PathBuf) -> Self {
-        let path = Self::file_path_for_testing(base_path);
-        if path.exists() {
-            let data = std::fs::read_to_string(&path).unwrap_or_default();
-            serde_json::from_str(&data).unwrap_or_default()
-        } else {
-            Self::default()
+    pub(crate) fn load_from_path(base_path: PathBuf) -> Self {
+        Self::load_from(&Self::file_path_for_testing(base_path)).0
+    }
+}
+
+/// teams.json as written, with the schema version alongside the teams
+#[derive(Serialize)]
+struct TeamFile<'a> {
+    version: u64,
+    teams: &'a [Team],
+}
+
+/// Bring a teams file up to `SCHEMA_VERSION`
+fn migrate(value: &mut Value) -> Result<()> {
+    let Some(object) = value.as_object_mut() else {
+        bail!("expected an object with a \"teams\" list");
+    };
+    let version = match object.get("version") {
+        None => 1,
+        Some(v) => v.as_u64().context("\"version\" should be a number")?,
+    };
+    if version > SCHEMA_VERSION {
+        return Err(NewerVersion(version).into());
+    }
+    for step in MIGRATIONS.iter().skip(version.saturating_sub(1) as usize) {
+        step(value);
+    }
+    if let Some(object) = value.as_object_mut() {
+        object.insert("version".to_string(), SCHEMA_VERSION.into());
+    }
+    Ok(())
+}
+
+/// Shift teams.json.1 to .2 and so on, dropping the oldest, and copy the
+/// current file to .1
+fn rotate_backups(path: &Path) -> std::io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    for n in (1..BACKUPS).rev() {
+        let from = sibling(path, &n.to_string());
+        if from.exists() {
+            std::fs::rename(&from, sibling(path, &(n + 1).to_string()))?;
         }
     }
+    std::fs::copy(path, sibling(path, "1")).map(|_| ())
+}
+
+fn copy_then_remove(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::copy(from, to)?;
+    std::fs::remove_file(from)
 }
 
 #[cfg(test)]
@@ -164,6 +327,104 @@ mod tests {
         assert_eq!(data.teams[0].name, "Team 1");
     }
 
+    #[test]
+    fn test_team_data_migrates_unversioned_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("teams.json");
+        fs::write(&path, r#"{"teams": [{"name": "Rain", "members": []}]}"#).unwrap();
+
+        let (data, error) = TeamData::load_from(&path);
+        assert_eq!(error, None);
+        assert_eq!(data.teams[0].name, "Rain");
+
+        data.save_to(&path).unwrap();
+        let saved: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["version"], SCHEMA_VERSION);
+        assert_eq!(TeamData::load_from(&path).0.teams[0].name, "Rain");
+    }
+
+    #[test]
+    fn test_team_data_corrupt_file_is_kept() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("teams.json");
+        fs::write(&path, "{\"teams\": [").unwrap();
+
+        let (data, error) = TeamData::load_from(&path);
+        assert_eq!(data.teams.len(), 1);
+        let error = error.unwrap();
+        assert!(error.contains("Couldn't parse"));
+        assert!(error.contains("teams.json.corrupt"));
+        let corrupt = temp_dir.path().join("teams.json.corrupt");
+        assert_eq!(fs::read_to_string(corrupt).unwrap(), "{\"teams\": [");
+
+        // Files from a newer version aren't guessed at either
+        fs::write(&path, r#"{"version": 99, "teams": []}"#).unwrap();
+        let error = TeamData::read_from(&path).unwrap_err();
+        assert!(format!("{:#}", error).contains("newer version (schema 99"));
+    }
+
+    #[test]
+    fn test_team_data_newer_file_is_read_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("teams.json");
+        let newer = r#"{"version": 99, "teams": [], "leagues": []}"#;
+        fs::write(&path, newer).unwrap();
+
+        let (data, error) = TeamData::load_from(&path);
+        assert!(data.read_only);
+        assert!(error.unwrap().contains("newer version (schema 99"));
+        assert!(!temp_dir.path().join("teams.json.corrupt").exists());
+
+        // Saving is refused rather than replacing the newer file
+        assert!(data.save_to(&path).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), newer);
+        assert!(!temp_dir.path().join("teams.json.1").exists());
+    }
+
+    #[test]
+    fn test_team_data_save_keeps_backups() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("teams.json");
+        let mut data = TeamData::default();
+        for n in 1..=5 {
+            data.teams[0].name = format!("Team {}", n);
+            data.save_to(&path).unwrap();
+        }
+        let name = |file: &str| {
+            TeamData::read_from(&temp_dir.path().join(file))
+                .unwrap()
+                .teams[0]
+                .name
+                .clone()
+        };
+        assert_eq!(name("teams.json"), "Team 5");
+        assert_eq!(name("teams.json.1"), "Team 4");
+        assert_eq!(name("teams.json.3"), "Team 2");
+        assert!(!temp_dir.path().join("teams.json.4").exists());
+        assert!(!temp_dir.path().join("teams.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_team_data_moves_legacy_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let old = temp_dir.path().join("cache").join("teams.json");
+        let new = temp_dir.path().join("data").join("teams.json");
+        let mut data = TeamData::default();
+        data.teams[0].name = "Old".to_string();
+        data.save_to(&old).unwrap();
+
+        TeamData::move_legacy_file(&old, &new).unwrap();
+        assert!(!old.exists());
+        assert_eq!(TeamData::load_from(&new).0.teams[0].name, "Old");
+
+        // An existing file in the new place wins
+        data.teams[0].name = "Older".to_string();
+        data.save_to(&old).unwrap();
+        TeamData::move_legacy_file(&old, &new).unwrap();
+        assert!(old.exists());
+        assert_eq!(TeamData::load_from(&new).0.teams[0].name, "Old");
+    }
+
     #[test]
     fn test_team_serialization() {
         let team = Team {
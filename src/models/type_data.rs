@@ -2,11 +2,25 @@ use serde::Deserialize;
 
 use super::pokemon::NamedResource;
 
+/// All 18 types in the canonical type chart order
+pub const ALL_TYPES: [&str; 18] = [
+    "normal", "fire", "water", "electric", "grass", "ice", "fighting", "poison", "ground",
+    "flying", "psychic", "bug", "rock", "ghost", "dragon", "dark", "steel", "fairy",
+];
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct TypeInfo {
     pub id: u32,
     pub name: String,
     pub damage_relations: DamageRelations,
+    /// Pokémon with this type, forms included
+    #[serde(default)]
+    pub pokemon: Vec<TypePokemon>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TypePokemon {
+    pub pokemon: NamedResource,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -19,6 +33,28 @@ pub struct DamageRelations {
     pub no_damage_from: Vec<NamedResource>,
 }
 
+impl TypeInfo {
+    /// Damage multiplier this type takes from an attacking type
+    pub fn damage_from(&self, attacker: &str) -> f64 {
+        let has = |list: &[NamedResource]| list.iter().any(|t| t.name == attacker);
+        let r = &self.damage_relations;
+        if has(&r.no_damage_from) {
+            0.0
+        } else if has(&r.double_damage_from) {
+            2.0
+        } else if has(&r.half_damage_from) {
+            0.5
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Combined multiplier against a Pokémon with all of `defenders`' types
+pub fn defensive_multiplier(defenders: &[TypeInfo], attacker: &str) -> f64 {
+    defenders.iter().map(|t| t.damage_from(attacker)).product()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,4 +115,42 @@ mod tests {
         assert_eq!(type_info.name, "normal");
         assert!(type_info.damage_relations.double_damage_to.is_empty());
     }
+
+    #[test]
+    fn test_defensive_multiplier_combines_types() {
+        let r = |names: &[&str]| -> Vec<NamedResource> {
+            names
+                .iter()
+                .map(|n| NamedResource {
+                    name: n.to_string(),
+                    url: String::new(),
+                })
+                .collect()
+        };
+        let info = |name: &str, double: &[&str], half: &[&str], none: &[&str]| TypeInfo {
+            id: 0,
+            name: name.to_string(),
+            damage_relations: DamageRelations {
+                double_damage_to: Vec::new(),
+                half_damage_to: Vec::new(),
+                no_damage_to: Vec::new(),
+                double_damage_from: r(double),
+                half_damage_from: r(half),
+                no_damage_from: r(none),
+            },
+            pokemon: Vec::new(),
+        };
+        let fire = info(
+            "fire",
+            &["water", "rock", "ground"],
+            &["grass", "fire"],
+            &[],
+        );
+        let flying = info("flying", &["rock", "electric"], &["grass"], &["ground"]);
+        let both = [fire, flying];
+        assert_eq!(defensive_multiplier(&both, "rock"), 4.0);
+        assert_eq!(defensive_multiplier(&both, "grass"), 0.25);
+        assert_eq!(defensive_multiplier(&both, "ground"), 0.0);
+        assert_eq!(defensive_multiplier(&both, "normal"), 1.0);
+    }
 }
@@ -0,0 +1,174 @@
+use crate::app::App;
+use crate::keymap::{Action, Preset};
+use crate::models::collection::GAMES;
+use crate::search::fuzzy::fuzzy_match;
+use crate::ui::capitalize;
+use crate::ui::theme::ThemeName;
+
+/// Results shown at once; the Pokédex alone has over a thousand entries
+const MAX_RESULTS: usize = 100;
+
+/// What a palette entry does when picked
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Run(Action),
+    ShowPokemon(u32),
+    SwitchTeam(usize),
+    SetKeymap(Preset),
+    SetTheme(ThemeName),
+    SetGame(&'static str),
+    ToggleAnimation,
+    ToggleAccessible,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteEntry {
+    pub label: String,
+    /// Key hint for actions, empty for everything else
+    pub keys: String,
+    pub command: Command,
+}
+
+/// Moving a cursor or confirming only makes sense on a key
+fn is_navigation(action: Action) -> bool {
+    matches!(
+        action,
+        Action::Up
+            | Action::Down
+            | Action::Left
+            | Action::Right
+            | Action::PageUp
+            | Action::PageDown
+            | Action::Top
+            | Action::Bottom
+            | Action::Select
+            | Action::CommandPalette
+    )
+}
+
+/// Commands for the current screen, global actions, teams and settings
+fn commands(app: &App) -> Vec<PaletteEntry> {
+    let mut entries = Vec::new();
+    for context in [app.screen_context(), crate::keymap::Context::Global] {
+        for (action, keys) in app.keymap.bindings(context) {
+            if is_navigation(*action) {
+                continue;
+            }
+            entries.push(PaletteEntry {
+                label: action.description(),
+                keys: keys.first().map(|k| k.to_string()).unwrap_or_default(),
+                command: Command::Run(*action),
+            });
+        }
+    }
+    for (i, team) in app.team_data.teams.iter().enumerate() {
+        entries.push(PaletteEntry {
+            label: format!("Switch to team: {}", team.name),
+            keys: String::new(),
+            command: Command::SwitchTeam(i),
+        });
+    }
+    for &preset in Preset::all() {
+        entries.push(PaletteEntry {
+            label: format!("Keymap: {}", preset.label()),
+            keys: String::new(),
+            command: Command::SetKeymap(preset),
+        });
+    }
+    for &name in ThemeName::all() {
+        entries.push(PaletteEntry {
+            label: format!("Theme: {}", name.label()),
+            keys: String::new(),
+            command: Command::SetTheme(name),
+        });
+    }
+    for &(game, label, _) in &GAMES {
+        entries.push(PaletteEntry {
+            label: format!("Game: {}", label),
+            keys: String::new(),
+            command: Command::SetGame(game),
+        });
+    }
+    entries.push(PaletteEntry {
+        label: format!(
+            "Sprite animation: {}",
+            if app.animate_sprites { "off" } else { "on" }
+        ),
+        keys: String::new(),
+        command: Command::ToggleAnimation,
+    });
+    entries.push(PaletteEntry {
+        label: format!(
+            "Accessibility mode: {}",
+            if app.accessible { "off" } else { "on" }
+        ),
+        keys: String::new(),
+        command: Command::ToggleAccessible,
+    });
+    entries
+}
+
+/// Entries matching `query`, best first. Pokémon only appear once something is typed.
+pub fn search(app: &App, query: &str) -> Vec<PaletteEntry> {
+    let mut entries = commands(app);
+    if query.trim().is_empty() {
+        return entries;
+    }
+    entries.extend(app.pokemon_list.iter().map(|p| PaletteEntry {
+        label: format!("Show {} #{}", capitalize(&p.name.replace('-', " ")), p.id),
+        keys: String::new(),
+        command: Command::ShowPokemon(p.id),
+    }));
+
+    let mut scored: Vec<(i32, bool, PaletteEntry)> = entries
+        .into_iter()
+        .filter_map(|e| {
+            let m = fuzzy_match(query, &e.label)?;
+            Some((m.score, m.is_typo(), e))
+        })
+        .collect();
+    if scored.iter().any(|&(_, typo, _)| !typo) {
+        scored.retain(|&(_, typo, _)| !typo);
+    }
+    scored.sort_by_key(|&(score, _, _)| std::cmp::Reverse(score));
+    scored
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, _, e)| e)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::pokemon::PokemonSummary;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_search_commands_and_pokemon() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.pokemon_list = vec![PokemonSummary {
+            id: 25,
+            name: "pikachu".to_string(),
+            ..Default::default()
+        }];
+
+        let all = search(&app, "");
+        assert!(all
+            .iter()
+            .any(|e| e.command == Command::Run(Action::NextSort)));
+        assert!(!all.iter().any(|e| e.command == Command::Run(Action::Down)));
+        assert!(!all
+            .iter()
+            .any(|e| matches!(e.command, Command::ShowPokemon(_))));
+
+        let results = search(&app, "pika");
+        assert_eq!(results[0].command, Command::ShowPokemon(25));
+        assert_eq!(results[0].label, "Show Pikachu #25");
+
+        let results = search(&app, "reverse sort");
+        assert_eq!(results[0].command, Command::Run(Action::ReverseSort));
+        assert_eq!(results[0].keys, "r");
+    }
+}
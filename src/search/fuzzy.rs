@@ -0,0 +1,292 @@
+/// Result of matching a query against a candidate name
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    /// Higher is better; typo matches always rank below subsequence matches
+    pub score: i32,
+    /// Char indices into the candidate that matched the query (for highlighting)
+    pub indices: Vec<usize>,
+}
+
+impl FuzzyMatch {
+    /// Whether this only matched through the edit-distance fallback
+    pub fn is_typo(&self) -> bool {
+        self.score <= TYPO_BASE
+    }
+}
+
+const SCORE_MATCH: i32 = 16;
+const BONUS_CONSECUTIVE: i32 = 12;
+const BONUS_WORD_START: i32 = 10;
+const BONUS_PREFIX: i32 = 8;
+const PENALTY_GAP: i32 = 1;
+const MAX_LEADING_PENALTY: i32 = 5;
+const TYPO_BASE: i32 = -1000;
+const PENALTY_TYPO: i32 = 100;
+
+fn is_separator(c: char) -> bool {
+    matches!(c, '-' | ' ' | '_' | '.' | '\'')
+}
+
+/// Lowercase and map every separator to '-', one char per input char so
+/// indices into the result line up with the original string
+pub fn normalize(s: &str) -> Vec<char> {
+    s.chars()
+        .map(|c| {
+            if is_separator(c) {
+                '-'
+            } else {
+                c.to_lowercase().next().unwrap_or(c)
+            }
+        })
+        .collect()
+}
+
+/// Match `query` against `candidate`. Separators are ignored in the query, so
+/// "mr mime", "mr-mime" and "mrmime" all find `mr-mime`. Falls back to a small
+/// edit distance against the start of the name so typos like "bulbosaur" still hit.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let q: Vec<char> = normalize(query).into_iter().filter(|&c| c != '-').collect();
+    if q.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            indices: Vec::new(),
+        });
+    }
+    let c = normalize(candidate);
+    subsequence_match(&q, &c).or_else(|| typo_match(&q, &c))
+}
+
+/// Best-scoring alignment of `q` as a subsequence of `c`
+fn subsequence_match(q: &[char], c: &[char]) -> Option<FuzzyMatch> {
+    let (n, m) = (q.len(), c.len());
+    if n > m {
+        return None;
+    }
+
+    let char_bonus = |j: usize| -> i32 {
+        let mut bonus = SCORE_MATCH;
+        if j == 0 || c[j - 1] == '-' {
+            bonus += BONUS_WORD_START;
+        }
+        if j == 0 {
+            bonus += BONUS_PREFIX;
+        }
+        bonus
+    };
+
+    // best[i][j]: best score with q[i] matched at c[j]; prev[i][j]: where q[i-1] matched
+    let mut best = vec![vec![None::<i32>; m]; n];
+    let mut prev = vec![vec![0usize; m]; n];
+
+    for j in 0..m {
+        if c[j] == q[0] {
+            let leading = (j as i32 * PENALTY_GAP).min(MAX_LEADING_PENALTY);
+            best[0][j] = Some(char_bonus(j) - leading);
+        }
+    }
+    for i in 1..n {
+        for j in i..m {
+            if c[j] != q[i] {
+                continue;
+            }
+            let mut top: Option<(i32, usize)> = None;
+            for (k, prev_best) in best[i - 1].iter().enumerate().take(j).skip(i - 1) {
+                if let Some(s) = *prev_best {
+                    let s = if k + 1 == j {
+                        s + BONUS_CONSECUTIVE
+                    } else {
+                        s - (j - k - 1) as i32 * PENALTY_GAP
+                    };
+                    if top.is_none_or(|(t, _)| s > t) {
+                        top = Some((s, k));
+                    }
+                }
+            }
+            if let Some((s, k)) = top {
+                best[i][j] = Some(s + char_bonus(j));
+                prev[i][j] = k;
+            }
+        }
+    }
+
+    let (mut score, end) = (0..m)
+        .filter_map(|j| best[n - 1][j].map(|s| (s, j)))
+        .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))?;
+
+    let mut indices = vec![0; n];
+    let mut j = end;
+    for i in (0..n).rev() {
+        indices[i] = j;
+        if i > 0 {
+            j = prev[i][j];
+        }
+    }
+    // Prefer shorter names when everything else is equal
+    score -= (m - end - 1) as i32 * PENALTY_GAP;
+    Some(FuzzyMatch { score, indices })
+}
+
+/// Edit-distance match of `q` against a prefix of `c` (separators ignored)
+fn typo_match(q: &[char], c: &[char]) -> Option<FuzzyMatch> {
+    let allowed = match q.len() {
+        0..=3 => return None,
+        4..=7 => 1,
+        _ => 2,
+    };
+
+    let (stripped, positions): (Vec<char>, Vec<usize>) = c
+        .iter()
+        .enumerate()
+        .filter(|(_, &ch)| ch != '-')
+        .map(|(i, &ch)| (ch, i))
+        .unzip();
+
+    let lo = q.len().saturating_sub(allowed).max(1);
+    let hi = (q.len() + allowed).min(stripped.len());
+    let (dist, len) = (lo..=hi)
+        .map(|len| (edit_distance(q, &stripped[..len]), len))
+        .min_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))?;
+    if dist > allowed {
+        return None;
+    }
+
+    let indices = common_subsequence(q, &stripped[..len])
+        .into_iter()
+        .map(|i| positions[i])
+        .collect();
+    let score =
+        TYPO_BASE - dist as i32 * PENALTY_TYPO - (stripped.len() - len) as i32 * PENALTY_GAP;
+    Some(FuzzyMatch { score, indices })
+}
+
+/// Optimal string alignment distance (Levenshtein plus adjacent transpositions)
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let (n, m) = (a.len(), b.len());
+    let mut d = vec![vec![0usize; m + 1]; n + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[n][m]
+}
+
+/// Indices into `b` of a longest common subsequence with `a`
+fn common_subsequence(a: &[char], b: &[char]) -> Vec<usize> {
+    let (n, m) = (a.len(), b.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j, mut out) = (0, 0, Vec::new());
+    while i < n && j < m {
+        if a[i] == b[j] {
+            out.push(j);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(q: &str, c: &str) -> i32 {
+        fuzzy_match(q, c).map(|m| m.score).unwrap_or(i32::MIN)
+    }
+
+    #[test]
+    fn test_normalize_maps_separators() {
+        assert_eq!(
+            normalize("Mr. Mime"),
+            vec!['m', 'r', '-', '-', 'm', 'i', 'm', 'e']
+        );
+        assert_eq!(normalize("ho_oh").len(), 5);
+    }
+
+    #[test]
+    fn test_empty_query_matches_everything() {
+        let m = fuzzy_match("", "pikachu").unwrap();
+        assert_eq!(m.score, 0);
+        assert!(m.indices.is_empty());
+    }
+
+    #[test]
+    fn test_subsequence_with_missing_letter() {
+        let m = fuzzy_match("charzard", "charizard").unwrap();
+        assert_eq!(m.indices, vec![0, 1, 2, 3, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_space_and_hyphen_are_equivalent() {
+        assert!(fuzzy_match("mr mime", "mr-mime").is_some());
+        assert!(fuzzy_match("mrmime", "mr-mime").is_some());
+        assert!(fuzzy_match("mr. mime", "mr-mime").is_some());
+        assert_eq!(
+            fuzzy_match("mr mime", "mr-mime").unwrap().indices,
+            vec![0, 1, 3, 4, 5, 6]
+        );
+    }
+
+    #[test]
+    fn test_typo_fallback() {
+        let m = fuzzy_match("bulbosaur", "bulbasaur").unwrap();
+        assert!(m.is_typo());
+        assert!(!fuzzy_match("bulb", "bulbasaur").unwrap().is_typo());
+        assert!(fuzzy_match("pikachi", "pikachu").is_some());
+        // Short queries need an exact subsequence
+        assert!(fuzzy_match("pkz", "pikachu").is_none());
+    }
+
+    #[test]
+    fn test_no_match() {
+        assert!(fuzzy_match("xyz", "bulbasaur").is_none());
+        assert!(fuzzy_match("pikachuuuuuuu", "pika").is_none());
+    }
+
+    #[test]
+    fn test_ranking_prefers_prefix_and_contiguous() {
+        assert!(score("pika", "pikachu") > score("pika", "pichu-spiky-eared"));
+        assert!(score("mew", "mew") > score("mew", "mewtwo"));
+        assert!(score("char", "charmander") > score("char", "pancham-hair"));
+        // Any subsequence hit outranks a typo hit
+        assert!(score("saur", "venusaur") > score("bulbosaur", "bulbasaur"));
+    }
+
+    #[test]
+    fn test_word_start_bonus() {
+        assert!(score("mime", "mr-mime") > score("mime", "mimikyu-disguised-meme"));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(edit_distance(&chars("abc"), &chars("abc")), 0);
+        assert_eq!(edit_distance(&chars("abc"), &chars("acb")), 1);
+        assert_eq!(edit_distance(&chars("kitten"), &chars("sitting")), 3);
+    }
+}
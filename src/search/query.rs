@@ -0,0 +1,82 @@
+use crate::models::type_data::ALL_TYPES;
+
+/// Structured search like `type:water gen:3 saur`: `key:value` terms filter,
+/// everything else is the fuzzy name (or dex number) query
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchQuery {
+    pub text: String,
+    /// Every listed type must be present
+    pub types: Vec<String>,
+    pub generation: Option<u8>,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Result<SearchQuery, String> {
+        let mut query = SearchQuery::default();
+        let mut words = Vec::new();
+        for term in input.split_whitespace() {
+            let Some((key, value)) = term.split_once(':') else {
+                words.push(term);
+                continue;
+            };
+            let value = value.to_lowercase();
+            match key.to_lowercase().as_str() {
+                "type" | "t" => {
+                    for t in value.split('/').filter(|t| !t.is_empty()) {
+                        if !ALL_TYPES.contains(&t) {
+                            return Err(format!("unknown type '{}'", t));
+                        }
+                        if !query.types.iter().any(|q| q == t) {
+                            query.types.push(t.to_string());
+                        }
+                    }
+                    if query.types.len() > 2 {
+                        return Err("at most two types".to_string());
+                    }
+                }
+                "gen" | "generation" | "g" => {
+                    let gen = value
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|g| (1..=9).contains(g))
+                        .ok_or_else(|| format!("generation must be 1-9, got '{}'", value))?;
+                    query.generation = Some(gen);
+                }
+                other => return Err(format!("unknown filter '{}:'", other)),
+            }
+        }
+        query.text = words.join(" ");
+        Ok(query)
+    }
+
+    pub fn matches_types(&self, types: &[String]) -> bool {
+        self.types.iter().all(|t| types.contains(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filters_and_text() {
+        let q = SearchQuery::parse("type:water gen:3 mud").unwrap();
+        assert_eq!(q.types, vec!["water"]);
+        assert_eq!(q.generation, Some(3));
+        assert_eq!(q.text, "mud");
+
+        let q = SearchQuery::parse("Type:Water/Ground").unwrap();
+        assert_eq!(q.types, vec!["water", "ground"]);
+        assert!(q.matches_types(&["water".to_string(), "ground".to_string()]));
+        assert!(!q.matches_types(&["water".to_string()]));
+        assert_eq!(SearchQuery::parse("").unwrap(), SearchQuery::default());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(SearchQuery::parse("type:lava").is_err());
+        assert!(SearchQuery::parse("gen:10").is_err());
+        assert!(SearchQuery::parse("type:fire type:water type:grass").is_err());
+        assert!(SearchQuery::parse("color:red").is_err());
+    }
+}
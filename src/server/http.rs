@@ -0,0 +1,235 @@
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+/// Header section and body limits; this only serves small JSON documents
+const MAX_HEADER_LINES: usize = 100;
+const MAX_LINE: u64 = 8 * 1024;
+const MAX_BODY: usize = 1 << 20;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    /// Path segments, percent-decoded: "/teams/0" -> ["teams", "0"]
+    pub segments: Vec<String>,
+    pub query: Vec<(String, String)>,
+    /// Header names are lowercased
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    pub fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    pub fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Decode %XX escapes, and '+' as space in query strings
+pub fn percent_decode(s: &str, plus_as_space: bool) -> String {
+    let hex = |b: u8| (b as char).to_digit(16);
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes.get(i + 1..i + 3) {
+            Some(&[hi, lo]) if bytes[i] == b'%' => hex(hi).zip(hex(lo)),
+            _ => None,
+        };
+        if let Some((hi, lo)) = escaped {
+            out.push((hi * 16 + lo) as u8);
+            i += 3;
+            continue;
+        }
+        out.push(match bytes[i] {
+            b'+' if plus_as_space => b' ',
+            b => b,
+        });
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn parse_target(target: &str) -> (Vec<String>, Vec<(String, String)>) {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let segments = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| percent_decode(s, false))
+        .collect();
+    let query = query
+        .split('&')
+        .filter(|s| !s.is_empty())
+        .map(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(k, true), percent_decode(v, true))
+        })
+        .collect();
+    (segments, query)
+}
+
+/// Read one line into `line`, failing if it's longer than `MAX_LINE`
+/// rather than buffering whatever the client sends
+async fn read_limited_line<R: tokio::io::AsyncBufRead + Unpin>(
+    reader: &mut R,
+    line: &mut String,
+) -> Result<usize> {
+    line.clear();
+    let read = reader.take(MAX_LINE).read_line(line).await?;
+    if read as u64 == MAX_LINE && !line.ends_with('\n') {
+        bail!("request line or header too long");
+    }
+    Ok(read)
+}
+
+/// Read one request: request line, headers, and a Content-Length body
+pub async fn read_request<R: tokio::io::AsyncRead + Unpin>(stream: R) -> Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    read_limited_line(&mut reader, &mut line).await?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        bail!("malformed request line");
+    };
+    let method = method.to_string();
+    let (segments, query) = parse_target(target);
+
+    let mut content_length = 0;
+    let mut headers = Vec::new();
+    for _ in 0..MAX_HEADER_LINES {
+        if read_limited_line(&mut reader, &mut line).await? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let (name, value) = (name.trim().to_ascii_lowercase(), value.trim());
+            if name == "content-length" {
+                content_length = value.parse().context("bad Content-Length")?;
+            }
+            headers.push((name, value.to_string()));
+        }
+    }
+    if content_length > MAX_BODY {
+        bail!("request body too large");
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+    Ok(Request {
+        method,
+        segments,
+        query,
+        headers,
+        body,
+    })
+}
+
+pub async fn write_response<W: AsyncWrite + Unpin>(
+    stream: &mut W,
+    response: &Response,
+) -> Result<()> {
+    let body = serde_json::to_string_pretty(&response.body)?;
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(
+            percent_decode("type%3Awater+gen%3A3", true),
+            "type:water gen:3"
+        );
+        assert_eq!(percent_decode("a+b", false), "a+b");
+        assert_eq!(percent_decode("100%", true), "100%");
+        assert_eq!(percent_decode("%zz", true), "%zz");
+    }
+
+    #[tokio::test]
+    async fn test_read_request_with_body() {
+        let raw = b"POST /teams?x=1&q=gen%3A3 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 4\r\n\r\n{}\r\n";
+        let req = read_request(&raw[..]).await.unwrap();
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.segments, vec!["teams"]);
+        assert_eq!(req.param("q"), Some("gen:3"));
+        assert_eq!(req.header("Host"), Some("localhost"));
+        assert_eq!(req.header("content-length"), Some("4"));
+        assert_eq!(req.body, b"{}\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_read_request_line_limit() {
+        let long = "a".repeat(MAX_LINE as usize * 2);
+        let raw = format!("GET /{} HTTP/1.1\r\n\r\n", long);
+        assert!(read_request(raw.as_bytes()).await.is_err());
+        let raw = format!("GET / HTTP/1.1\r\nX-Long: {}\r\n\r\n", long);
+        assert!(read_request(raw.as_bytes()).await.is_err());
+        // Lines up to the limit are fine
+        let raw = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(1000));
+        assert!(read_request(raw.as_bytes()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_write_response() {
+        let mut out = Vec::new();
+        write_response(&mut out, &Response::error(404, "nope"))
+            .await
+            .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(text.ends_with("\"error\": \"nope\"\n}"));
+    }
+}
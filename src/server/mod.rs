@@ -0,0 +1,454 @@
+pub mod http;
+
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+
+use crate::api::client::ApiClient;
+use crate::app::summaries_from_list;
+use crate::cli::{move_json, search, type_json};
+use crate::export::table::DexEntry;
+use crate::models::team::{Team, TeamData, TeamMember};
+use crate::models::type_data::ALL_TYPES;
+use crate::search::query::SearchQuery;
+use http::{read_request, write_response, Request, Response};
+
+pub const DEFAULT_PORT: u16 = 8765;
+
+/// How long a client gets to send its whole request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub const ROUTES: &str = "\
+GET    /pokemon                 all Pokémon ids and names (?forms=true to include forms)
+GET    /pokemon?q=QUERY         search, e.g. ?q=type:water+gen:3
+GET    /pokemon/NAME_OR_ID      entry with types, base stats, abilities, size
+GET    /types/TYPE[/TYPE]       damage taken from every type
+GET    /moves/NAME              type, category, power, accuracy, PP
+GET    /teams                   saved teams
+POST   /teams                   create a team from {\"name\", \"members\"} (application/json)
+GET    /teams/INDEX             one team
+PUT    /teams/INDEX             replace a team (application/json)
+DELETE /teams/INDEX             delete a team";
+
+/// Body for creating or replacing a team
+#[derive(Debug, Deserialize)]
+struct TeamBody {
+    name: String,
+    #[serde(default)]
+    members: Vec<TeamMember>,
+}
+
+/// Request handling shared by every connection
+pub struct Server {
+    client: Arc<ApiClient>,
+    teams_path: PathBuf,
+    /// Serializes read-modify-write of the teams file
+    teams_lock: Mutex<()>,
+}
+
+impl Server {
+    pub fn new(teams_path: PathBuf) -> Self {
+        Self {
+            client: Arc::new(ApiClient::new()),
+            teams_path,
+            teams_lock: Mutex::new(()),
+        }
+    }
+
+    pub async fn handle(&self, req: &Request) -> Response {
+        let segments: Vec<&str> = req.segments.iter().map(String::as_str).collect();
+        let method = req.method.as_str();
+        // A page that rebinds its own hostname to 127.0.0.1 still sends that hostname
+        if !req.header("host").is_none_or(is_loopback_host) {
+            return Response::error(403, "only requests to localhost are served");
+        }
+        if method != "GET" {
+            if let Err(response) = check_write_request(req) {
+                return response;
+            }
+        }
+        match (segments.as_slice(), method) {
+            (["pokemon"], "GET") => self.list_pokemon(req).await,
+            (["pokemon", name], "GET") => self.pokemon(name).await,
+            (["types", types @ ..], "GET") if matches!(types.len(), 1 | 2) => {
+                self.types(types).await
+            }
+            (["moves", name], "GET") => self.move_detail(name).await,
+            (["teams"], "GET") => {
+                self.with_teams(|data| Response::ok(json!(data.teams)))
+                    .await
+            }
+            (["teams"], "POST") => self.create_team(req).await,
+            (["teams", index], "GET" | "PUT" | "DELETE") => {
+                let Ok(index) = index.parse::<usize>() else {
+                    return Response::error(404, "team index must be a number");
+                };
+                self.team(method, index, req).await
+            }
+            (["pokemon" | "types" | "moves" | "teams", ..], _) if method != "GET" => {
+                Response::error(405, format!("{} not allowed here", method))
+            }
+            _ => Response::error(404, "no such route"),
+        }
+    }
+
+    async fn list_pokemon(&self, req: &Request) -> Response {
+        let forms = req.param("forms") == Some("true");
+        if let Some(q) = req.param("q") {
+            let query = match SearchQuery::parse(q) {
+                Ok(query) => query,
+                Err(e) => return Response::error(400, e),
+            };
+            return match search(self.client.clone(), &query, forms).await {
+                Ok(entries) => Response::ok(json!(entries)),
+                Err(e) => Response::error(500, e.to_string()),
+            };
+        }
+        match self.client.fetch_pokemon_list().await {
+            Ok(list) => {
+                let rows: Vec<Value> = summaries_from_list(&list)
+                    .into_iter()
+                    .filter(|s| forms || !s.is_form())
+                    .map(|s| json!({ "id": s.id, "name": s.name }))
+                    .collect();
+                Response::ok(json!(rows))
+            }
+            Err(e) => Response::error(500, e.to_string()),
+        }
+    }
+
+    async fn pokemon(&self, name: &str) -> Response {
+        match self.client.fetch_pokemon_detail(&name.to_lowercase()).await {
+            Ok(detail) => Response::ok(json!(DexEntry::from_detail(&detail))),
+            Err(e) => Response::error(404, format!("couldn't load Pokémon '{}': {}", name, e)),
+        }
+    }
+
+    async fn types(&self, types: &[&str]) -> Response {
+        let mut infos = Vec::new();
+        for t in types {
+            let t = t.to_lowercase();
+            if !ALL_TYPES.contains(&t.as_str()) {
+                return Response::error(404, format!("unknown type '{}'", t));
+            }
+            match self.client.fetch_type_info(&t).await {
+                Ok(info) => infos.push(info),
+                Err(e) => return Response::error(500, e.to_string()),
+            }
+        }
+        Response::ok(type_json(&infos))
+    }
+
+    async fn move_detail(&self, name: &str) -> Response {
+        match self.client.fetch_move_detail(&name.to_lowercase()).await {
+            Ok(mv) => Response::ok(move_json(&mv)),
+            Err(e) => Response::error(404, format!("couldn't load move '{}': {}", name, e)),
+        }
+    }
+
+    /// Load the teams file fresh for each request, so edits made by the TUI are seen,
+    /// and save it if `f` changed anything and succeeded
+    async fn with_teams<F>(&self, f: F) -> Response
+    where
+        F: FnOnce(&mut TeamData) -> Response,
+    {
+        let _guard = self.teams_lock.lock().await;
+        // Leave a broken file alone rather than saving over it
+        let mut data = match TeamData::read_from(&self.teams_path) {
+            Ok(data) => data,
+            Err(e) => return Response::error(500, format!("{:#}", e)),
+        };
+        let before = serde_json::to_value(&data.teams).ok();
+        let response = f(&mut data);
+        if response.status < 300 && serde_json::to_value(&data.teams).ok() != before {
+            if let Err(e) = data.save_to(&self.teams_path) {
+                return Response::error(500, format!("couldn't save teams: {}", e));
+            }
+        }
+        response
+    }
+
+    async fn create_team(&self, req: &Request) -> Response {
+        let body = match parse_team(req) {
+            Ok(team) => team,
+            Err(response) => return response,
+        };
+        self.with_teams(|data| {
+            data.teams.push(body);
+            let index = data.teams.len() - 1;
+            Response {
+                status: 201,
+                body: json!({ "index": index, "team": data.teams[index] }),
+            }
+        })
+        .await
+    }
+
+    async fn team(&self, method: &str, index: usize, req: &Request) -> Response {
+        let body = if method == "PUT" {
+            match parse_team(req) {
+                Ok(team) => Some(team),
+                Err(response) => return response,
+            }
+        } else {
+            None
+        };
+        self.with_teams(|data| {
+            if index >= data.teams.len() {
+                return Response::error(404, format!("no team {}", index));
+            }
+            match method {
+                "PUT" => data.teams[index] = body.expect("PUT has a body"),
+                "DELETE" => {
+                    if data.teams.len() == 1 {
+                        return Response::error(409, "can't delete the last team");
+                    }
+                    return Response::ok(json!(data.teams.remove(index)));
+                }
+                _ => {}
+            }
+            Response::ok(json!(data.teams[index]))
+        })
+        .await
+    }
+}
+
+/// Whether a Host header names this machine: `localhost` or a loopback
+/// address, with or without a port
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Only take edits from local tools, not web pages: a browser sends an
+/// `Origin` header with cross-site requests
+fn check_write_request(req: &Request) -> Result<(), Response> {
+    if req.header("origin").is_some() {
+        return Err(Response::error(
+            403,
+            "requests from web pages aren't allowed",
+        ));
+    }
+    Ok(())
+}
+
+/// A team from a POST or PUT body. Requiring JSON means a browser can't send
+/// one to another origin without a preflight this server never answers.
+fn parse_team(req: &Request) -> Result<Team, Response> {
+    let json = req
+        .header("content-type")
+        .and_then(|t| t.split(';').next())
+        .is_some_and(|t| t.trim().eq_ignore_ascii_case("application/json"));
+    if !json {
+        return Err(Response::error(
+            415,
+            "Content-Type must be application/json",
+        ));
+    }
+    let body: TeamBody = serde_json::from_slice(&req.body)
+        .map_err(|e| Response::error(400, format!("invalid team: {}", e)))?;
+    if body.name.trim().is_empty() {
+        return Err(Response::error(400, "team name can't be empty"));
+    }
+    if body.members.len() > 6 {
+        return Err(Response::error(400, "a team has at most 6 members"));
+    }
+    Ok(Team {
+        name: body.name,
+        members: body.members,
+    })
+}
+
+/// Accept connections until the process is stopped
+pub async fn serve(addr: SocketAddr, server: Server) -> Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    eprintln!("Serving the Pokédex on http://{}", listener.local_addr()?);
+    let server = Arc::new(server);
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let server = server.clone();
+        tokio::spawn(async move {
+            let (reader, mut writer) = stream.split();
+            let response = match tokio::time::timeout(READ_TIMEOUT, read_request(reader)).await {
+                Ok(Ok(req)) => server.handle(&req).await,
+                Ok(Err(e)) => Response::error(400, e.to_string()),
+                Err(_) => Response::error(408, "timed out reading the request"),
+            };
+            let _ = write_response(&mut writer, &response).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str, body: &str) -> Request {
+        Request {
+            method: method.to_string(),
+            segments: path
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect(),
+            query: Vec::new(),
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_teams_crud() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("teams.json");
+        let server = Server::new(path.clone());
+
+        let res = server.handle(&request("GET", "/teams", "")).await;
+        assert_eq!(res.body[0]["name"], "Team 1");
+
+        let member =
+            r#"{"pokemon_id": 25, "pokemon_name": "pikachu", "types": ["electric"], "moves": []}"#;
+        let body = format!(r#"{{"name": "Sparks", "members": [{}]}}"#, member);
+        let res = server.handle(&request("POST", "/teams", &body)).await;
+        assert_eq!(res.status, 201);
+        assert_eq!(res.body["index"], 1);
+        assert_eq!(
+            TeamData::load_from(&path).0.teams[1].members[0].pokemon_id,
+            25
+        );
+
+        let res = server
+            .handle(&request("PUT", "/teams/1", r#"{"name": "Renamed"}"#))
+            .await;
+        assert_eq!(res.body["name"], "Renamed");
+        assert!(TeamData::load_from(&path).0.teams[1].members.is_empty());
+
+        let res = server.handle(&request("DELETE", "/teams/1", "")).await;
+        assert_eq!(res.status, 200);
+        assert_eq!(TeamData::load_from(&path).0.teams.len(), 1);
+        let res = server.handle(&request("DELETE", "/teams/0", "")).await;
+        assert_eq!(res.status, 409);
+    }
+
+    #[tokio::test]
+    async fn test_team_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = Server::new(dir.path().join("teams.json"));
+        let status = |res: Response| res.status;
+        assert_eq!(
+            status(server.handle(&request("GET", "/teams/5", "")).await),
+            404
+        );
+        assert_eq!(
+            status(server.handle(&request("GET", "/teams/x", "")).await),
+            404
+        );
+        assert_eq!(
+            status(server.handle(&request("POST", "/teams", "not json")).await),
+            400
+        );
+        assert_eq!(
+            status(
+                server
+                    .handle(&request("POST", "/teams", r#"{"name": " "}"#))
+                    .await
+            ),
+            400
+        );
+        assert_eq!(
+            status(server.handle(&request("POST", "/pokemon", "")).await),
+            405
+        );
+        assert_eq!(
+            status(server.handle(&request("GET", "/nowhere", "")).await),
+            404
+        );
+        assert_eq!(
+            status(server.handle(&request("GET", "/types/lava", "")).await),
+            404
+        );
+        // Failed requests don't create the file
+        assert!(!dir.path().join("teams.json").exists());
+
+        // A broken teams file is reported and left as it is
+        std::fs::write(dir.path().join("teams.json"), "{oops").unwrap();
+        let res = server
+            .handle(&request("POST", "/teams", r#"{"name": "New"}"#))
+            .await;
+        assert_eq!(res.status, 500);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("teams.json")).unwrap(),
+            "{oops"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_edits_only_from_local_json_clients() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = Server::new(dir.path().join("teams.json"));
+        let body = r#"{"name": "New"}"#;
+
+        // A web page's form or fetch without a preflight sends text/plain
+        let mut req = request("POST", "/teams", body);
+        req.headers = vec![("content-type".to_string(), "text/plain".to_string())];
+        assert_eq!(server.handle(&req).await.status, 415);
+        req.headers.clear();
+        assert_eq!(server.handle(&req).await.status, 415);
+
+        // Anything a browser sends on a page's behalf carries an Origin
+        let mut req = request("DELETE", "/teams/0", "");
+        req.headers
+            .push(("origin".to_string(), "https://example.com".to_string()));
+        assert_eq!(server.handle(&req).await.status, 403);
+        assert!(!dir.path().join("teams.json").exists());
+
+        // Reads stay open, and JSON requests from local tools still work
+        let mut req = request("GET", "/teams", "");
+        req.headers.clear();
+        assert_eq!(server.handle(&req).await.status, 200);
+        let mut req = request("POST", "/teams", r#"{"name": "New", "members": []}"#);
+        req.headers[0].1 = "application/json; charset=utf-8".to_string();
+        assert_eq!(server.handle(&req).await.status, 201);
+
+        // DELETE has no body, so needs no Content-Type
+        let mut req = request("DELETE", "/teams/1", "");
+        req.headers.clear();
+        assert_eq!(server.handle(&req).await.status, 200);
+        assert_eq!(
+            TeamData::load_from(&dir.path().join("teams.json"))
+                .0
+                .teams
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_only_localhost_names_served() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = Server::new(dir.path().join("teams.json"));
+        for (host, status) in [
+            ("localhost:8765", 200),
+            ("LOCALHOST", 200),
+            ("127.0.0.1:8765", 200),
+            ("[::1]:8765", 200),
+            ("evil.example:8765", 403),
+            ("192.168.1.2", 403),
+        ] {
+            let mut req = request("GET", "/teams", "");
+            req.headers.push(("host".to_string(), host.to_string()));
+            assert_eq!(server.handle(&req).await.status, status, "{}", host);
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::{Screen, SortKey, TypeMatch};
+use crate::keymap::Preset;
+use crate::models::{keep_corrupt_copy, write_atomic};
+use crate::sprite::renderer::BlockMode;
+use crate::ui::theme::ThemeName;
+
+/// Preferences kept in `settings.json`. Filters, sprite mode and panes are
+/// written back on exit; theme and keymap only once picked in the app.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// Theme name, e.g. "light"; unset leaves it to theme.json
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Keymap preset, e.g. "vim"; unset leaves it to keymap.json
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keymap: Option<String>,
+    /// Screen to start on; unset returns to where the last session ended
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_screen: Option<Screen>,
+    pub filters: Filters,
+    pub sprite_mode: BlockMode,
+    pub panes: Panes,
+}
+
+/// List filters and ordering that stay until changed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Filters {
+    pub types: Vec<String>,
+    pub type_match: TypeMatch,
+    pub show_forms: bool,
+    pub sort: SortKey,
+    pub descending: bool,
+    pub favorites_only: bool,
+    /// Tags a Pokémon must all have to be listed
+    pub tags: Vec<String>,
+}
+
+/// Narrowest a pane of a split view can be, in percent of the width
+pub const MIN_PANE: u16 = 20;
+
+/// Split views: a second screen drawn beside the one being used
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Panes {
+    /// The selected Pokémon's detail beside the list
+    pub list_detail: Split,
+    /// The type chart beside the team builder
+    pub team_chart: Split,
+}
+
+impl Panes {
+    /// The split a screen can show, if it has one
+    pub fn get(&self, screen: Screen) -> Option<&Split> {
+        match screen {
+            Screen::PokemonList => Some(&self.list_detail),
+            Screen::TeamBuilder => Some(&self.team_chart),
+            Screen::PokemonDetail | Screen::TypeChart => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, screen: Screen) -> Option<&mut Split> {
+        match screen {
+            Screen::PokemonList => Some(&mut self.list_detail),
+            Screen::TeamBuilder => Some(&mut self.team_chart),
+            Screen::PokemonDetail | Screen::TypeChart => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Split {
+    pub enabled: bool,
+    /// Width of the left pane in percent
+    pub left: u16,
+}
+
+impl Default for Split {
+    fn default() -> Self {
+        Split {
+            enabled: false,
+            left: 50,
+        }
+    }
+}
+
+impl Split {
+    /// The left pane's width, kept to a size both panes are usable at
+    pub fn left_percent(&self) -> u16 {
+        self.left.clamp(MIN_PANE, 100 - MIN_PANE)
+    }
+
+    /// Widen (or with a negative step, narrow) the left pane
+    pub fn resize(&mut self, step: i16) {
+        let left = self.left_percent() as i16 + step;
+        self.left = (left.max(0) as u16).clamp(MIN_PANE, 100 - MIN_PANE);
+    }
+}
+
+/// Where the last run left off, kept in `session.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub screen: Screen,
+    pub search_query: String,
+    pub generation_filter: Option<u8>,
+    pub list_state: usize,
+    /// Last Pokémon opened on the detail screen
+    pub pokemon_id: Option<u32>,
+    pub current_team: usize,
+    /// Recently viewed Pokémon ids, most recent first
+    pub recent: Vec<u32>,
+}
+
+fn config_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("pokemon-tui")
+}
+
+fn write_json<T: Serialize>(value: &T, path: &Path) -> std::io::Result<()> {
+    write_atomic(path, &serde_json::to_string_pretty(value)?)
+}
+
+impl Settings {
+    pub fn file_path() -> PathBuf {
+        config_dir().join("settings.json")
+    }
+
+    /// Load the settings file if there is one. A broken file falls back to the
+    /// defaults, with a message saying what's wrong; it's copied aside first
+    /// since saving on exit replaces it. Unknown theme and keymap names are
+    /// kept, so they're written back as they were, but not used.
+    pub fn load() -> (Settings, Option<String>) {
+        Settings::load_from(&Settings::file_path())
+    }
+
+    pub fn load_from(path: &Path) -> (Settings, Option<String>) {
+        let Ok(data) = std::fs::read_to_string(path) else {
+            return (Settings::default(), None);
+        };
+        let settings: Settings = match serde_json::from_str(&data) {
+            Ok(settings) => settings,
+            Err(e) => {
+                let message = format!(
+                    "Ignoring {}: {}\n{}",
+                    path.display(),
+                    e,
+                    keep_corrupt_copy(path)
+                );
+                return (Settings::default(), Some(message));
+            }
+        };
+        let mut problems = Vec::new();
+        if let Some(theme) = settings
+            .theme
+            .as_ref()
+            .filter(|_| settings.theme_name().is_none())
+        {
+            problems.push(format!("unknown theme '{}'", theme));
+        }
+        if let Some(keymap) = settings
+            .keymap
+            .as_ref()
+            .filter(|_| settings.preset().is_none())
+        {
+            problems.push(format!("unknown keymap preset '{}'", keymap));
+        }
+        let message = (!problems.is_empty())
+            .then(|| format!("In {}:\n{}", path.display(), problems.join("\n")));
+        (settings, message)
+    }
+
+    pub fn theme_name(&self) -> Option<ThemeName> {
+        self.theme.as_deref().and_then(ThemeName::from_name)
+    }
+
+    pub fn preset(&self) -> Option<Preset> {
+        self.keymap.as_deref().and_then(Preset::from_name)
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        self.save_to(&Settings::file_path())
+    }
+
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        write_json(self, path)
+    }
+}
+
+impl Session {
+    pub fn file_path() -> PathBuf {
+        config_dir().join("session.json")
+    }
+
+    /// The last session; a missing or unreadable file starts fresh
+    pub fn load() -> Session {
+        Session::load_from(&Session::file_path())
+    }
+
+    pub fn load_from(path: &Path) -> Session {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        self.save_to(&Session::file_path())
+    }
+
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        write_json(self, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pokemon-tui").join("settings.json");
+
+        let (settings, error) = Settings::load_from(&path);
+        assert_eq!(settings, Settings::default());
+        assert_eq!(error, None);
+
+        let settings = Settings {
+            theme: Some("light".to_string()),
+            default_screen: Some(Screen::TypeChart),
+            filters: Filters {
+                types: vec!["fire".to_string()],
+                sort: SortKey::Speed,
+                descending: true,
+                ..Default::default()
+            },
+            sprite_mode: BlockMode::Braille,
+            ..Default::default()
+        };
+        settings.save_to(&path).unwrap();
+        let data = std::fs::read_to_string(&path).unwrap();
+        assert!(data.contains(r#""default_screen": "type_chart""#));
+        assert!(data.contains(r#""sprite_mode": "braille""#));
+        assert!(!data.contains("keymap"));
+        assert_eq!(Settings::load_from(&path), (settings, None));
+
+        // Partial files fill in the rest; mistakes are reported
+        std::fs::write(&path, r#"{"keymap": "vim"}"#).unwrap();
+        let (settings, _) = Settings::load_from(&path);
+        assert_eq!(settings.keymap.as_deref(), Some("vim"));
+        assert_eq!(settings.filters.sort, SortKey::Id);
+        std::fs::write(&path, r#"{"theme": "neon", "keymap": "vim"}"#).unwrap();
+        let (settings, error) = Settings::load_from(&path);
+        assert_eq!(settings.theme_name(), None);
+        assert_eq!(settings.preset(), Some(Preset::Vim));
+        assert!(error.unwrap().contains("unknown theme 'neon'"));
+        // Written back as it was rather than dropped
+        settings.save_to(&path).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("neon"));
+        std::fs::write(&path, r#"{"theme": "light", "colour": "red"}"#).unwrap();
+        let (settings, error) = Settings::load_from(&path);
+        assert_eq!(settings, Settings::default());
+        let error = error.unwrap();
+        assert!(error.contains("unknown field `colour`"));
+        assert!(error.contains("settings.json.corrupt"));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("pokemon-tui").join("settings.json.corrupt"))
+                .unwrap(),
+            r#"{"theme": "light", "colour": "red"}"#
+        );
+    }
+
+    #[test]
+    fn test_split_panes() {
+        let settings: Settings =
+            serde_json::from_str(r#"{"panes": {"team_chart": {"enabled": true}}}"#).unwrap();
+        let split = settings.panes.get(Screen::TeamBuilder).unwrap();
+        assert!(split.enabled);
+        assert_eq!(split.left_percent(), 50);
+        assert!(!settings.panes.get(Screen::PokemonList).unwrap().enabled);
+        assert_eq!(settings.panes.get(Screen::TypeChart), None);
+
+        let mut split = Split {
+            enabled: true,
+            left: 95,
+        };
+        assert_eq!(split.left_percent(), 80);
+        split.resize(-5);
+        assert_eq!(split.left, 75);
+        for _ in 0..20 {
+            split.resize(-5);
+        }
+        assert_eq!(split.left, MIN_PANE);
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        assert_eq!(Session::load_from(&path), Session::default());
+
+        let session = Session {
+            screen: Screen::PokemonDetail,
+            search_query: "char".to_string(),
+            generation_filter: Some(1),
+            list_state: 2,
+            pokemon_id: Some(6),
+            current_team: 1,
+            recent: vec![6, 25],
+        };
+        session.save_to(&path).unwrap();
+        assert_eq!(Session::load_from(&path), session);
+
+        std::fs::write(&path, "{not json").unwrap();
+        assert_eq!(Session::load_from(&path), Session::default());
+    }
+}
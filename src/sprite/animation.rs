@@ -0,0 +1,157 @@
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use image::codecs::gif::GifDecoder;
+use image::{AnimationDecoder, DynamicImage, ImageFormat};
+
+use crate::models::pokemon::SpriteVariant;
+
+/// GIFs commonly ask for 0–10ms delays, which browsers treat as 100ms
+const MIN_FRAME_DELAY: Duration = Duration::from_millis(20);
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// One decoded frame, re-encoded as PNG so it draws like a static sprite
+#[derive(Debug, Clone)]
+pub struct AnimationFrame {
+    pub png: Vec<u8>,
+    pub delay: Duration,
+}
+
+/// Decode every frame of a GIF. Returns None for anything that isn't an animated GIF.
+pub fn decode_gif(bytes: &[u8]) -> Option<Vec<AnimationFrame>> {
+    let decoder = GifDecoder::new(Cursor::new(bytes)).ok()?;
+    let frames = decoder.into_frames().collect_frames().ok()?;
+    if frames.len() < 2 {
+        return None;
+    }
+    frames
+        .into_iter()
+        .map(|frame| {
+            let (num, den) = frame.delay().numer_denom_ms();
+            let ms = num.checked_div(den).unwrap_or(0);
+            let delay = if ms < MIN_FRAME_DELAY.as_millis() as u32 {
+                DEFAULT_FRAME_DELAY
+            } else {
+                Duration::from_millis(ms as u64)
+            };
+            let mut png = Cursor::new(Vec::new());
+            DynamicImage::ImageRgba8(frame.into_buffer())
+                .write_to(&mut png, ImageFormat::Png)
+                .ok()?;
+            Some(AnimationFrame {
+                png: png.into_inner(),
+                delay,
+            })
+        })
+        .collect()
+}
+
+/// Playback state for the sprite on the detail screen
+#[derive(Debug, Clone)]
+pub struct SpriteAnimation {
+    pub pokemon_id: u32,
+    pub variant: SpriteVariant,
+    frames: Arc<Vec<AnimationFrame>>,
+    current: usize,
+    elapsed: Duration,
+}
+
+impl SpriteAnimation {
+    pub fn new(pokemon_id: u32, variant: SpriteVariant, frames: Arc<Vec<AnimationFrame>>) -> Self {
+        Self {
+            pokemon_id,
+            variant,
+            frames,
+            current: 0,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    pub fn frame(&self) -> &[u8] {
+        &self.frames[self.current].png
+    }
+
+    pub fn frame_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Move time forward; returns true when the visible frame changed
+    pub fn advance(&mut self, dt: Duration) -> bool {
+        let start = self.current;
+        self.elapsed += dt;
+        while self.elapsed >= self.frames[self.current].delay {
+            self.elapsed -= self.frames[self.current].delay;
+            self.current = (self.current + 1) % self.frames.len();
+        }
+        self.current != start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, Rgba, RgbaImage};
+
+    fn gif(delays_ms: &[u32]) -> Vec<u8> {
+        let mut out = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut out);
+            for (i, &ms) in delays_ms.iter().enumerate() {
+                let img = RgbaImage::from_pixel(4, 4, Rgba([i as u8 * 60, 0, 0, 255]));
+                let frame = Frame::from_parts(img, 0, 0, Delay::from_numer_denom_ms(ms, 1));
+                encoder.encode_frame(frame).unwrap();
+            }
+        }
+        out
+    }
+
+    fn frames(delays_ms: &[u64]) -> Arc<Vec<AnimationFrame>> {
+        Arc::new(
+            delays_ms
+                .iter()
+                .map(|&ms| AnimationFrame {
+                    png: vec![ms as u8],
+                    delay: Duration::from_millis(ms),
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_decode_gif_frames() {
+        let frames = decode_gif(&gif(&[100, 200, 0])).unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].delay, Duration::from_millis(100));
+        assert_eq!(frames[1].delay, Duration::from_millis(200));
+        // Zero delay is treated like a browser would
+        assert_eq!(frames[2].delay, DEFAULT_FRAME_DELAY);
+        let img = image::load_from_memory(&frames[1].png).unwrap();
+        assert_eq!(img.to_rgba8().get_pixel(0, 0)[0], 60);
+    }
+
+    #[test]
+    fn test_decode_rejects_still_images() {
+        assert!(decode_gif(&gif(&[100])).is_none());
+        assert!(decode_gif(b"not a gif").is_none());
+    }
+
+    #[test]
+    fn test_advance_steps_through_frames() {
+        let mut anim = SpriteAnimation::new(25, SpriteVariant::FrontDefault, frames(&[100, 50]));
+        assert!(!anim.advance(Duration::from_millis(60)));
+        assert_eq!(anim.frame_index(), 0);
+        assert!(anim.advance(Duration::from_millis(60)));
+        assert_eq!(anim.frame_index(), 1);
+        assert_eq!(anim.frame(), &[50]);
+        // 20ms into frame 1 plus 40 wraps back to frame 0
+        assert!(anim.advance(Duration::from_millis(40)));
+        assert_eq!(anim.frame_index(), 0);
+        assert_eq!(anim.frame_count(), 2);
+    }
+}
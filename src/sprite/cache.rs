@@ -0,0 +1,112 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::models::pokemon::SpriteVariant;
+use crate::sprite::renderer::{RenderOptions, SpriteWidget};
+
+/// Rendered sprites kept before the cache starts over
+const MAX_ENTRIES: usize = 512;
+
+/// Everything that changes the cells a sprite renders to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpriteKey {
+    pub pokemon_id: u32,
+    pub variant: SpriteVariant,
+    /// Animation frame, None for a still image
+    pub frame: Option<usize>,
+    pub width: u16,
+    pub height: u16,
+    pub options: RenderOptions,
+}
+
+/// Computed sprite cells, so redraws don't decode and resample the PNG each time.
+/// Drawing only has `&App`, hence the interior mutability.
+#[derive(Default)]
+pub struct SpriteCache {
+    entries: RefCell<HashMap<SpriteKey, Option<Rc<SpriteWidget>>>>,
+}
+
+impl SpriteCache {
+    /// Cached cells for `key`, rendering `bytes` on a miss. Undecodable images are
+    /// remembered as None so they aren't retried every frame.
+    pub fn get_or_render(&self, key: SpriteKey, bytes: &[u8]) -> Option<Rc<SpriteWidget>> {
+        if let Some(hit) = self.entries.borrow().get(&key) {
+            return hit.clone();
+        }
+        let widget = SpriteWidget::from_png_bytes_with(bytes, key.width, key.height, key.options)
+            .map(Rc::new);
+        let mut entries = self.entries.borrow_mut();
+        if entries.len() >= MAX_ENTRIES {
+            entries.clear();
+        }
+        entries.insert(key, widget.clone());
+        widget
+    }
+
+    /// Drop everything, e.g. when the terminal is resized
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+    use std::io::Cursor;
+
+    fn png() -> Vec<u8> {
+        let img = RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]));
+        let mut out = Cursor::new(Vec::new());
+        DynamicImage::ImageRgba8(img)
+            .write_to(&mut out, ImageFormat::Png)
+            .unwrap();
+        out.into_inner()
+    }
+
+    fn key(width: u16) -> SpriteKey {
+        SpriteKey {
+            pokemon_id: 25,
+            variant: SpriteVariant::FrontDefault,
+            frame: None,
+            width,
+            height: 10,
+            options: RenderOptions::default(),
+        }
+    }
+
+    #[test]
+    fn test_cache_reuses_rendered_cells() {
+        let cache = SpriteCache::default();
+        let first = cache.get_or_render(key(10), &png()).unwrap();
+        // Bytes aren't looked at again on a hit
+        let second = cache.get_or_render(key(10), b"garbage").unwrap();
+        assert!(Rc::ptr_eq(&first, &second));
+        assert_eq!(cache.len(), 1);
+
+        // A different size is a different entry
+        cache.get_or_render(key(12), &png()).unwrap();
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_cache_remembers_failures_and_clears() {
+        let cache = SpriteCache::default();
+        assert!(cache.get_or_render(key(10), b"not a png").is_none());
+        assert!(cache.get_or_render(key(10), &png()).is_none());
+        assert_eq!(cache.len(), 1);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(cache.get_or_render(key(10), &png()).is_some());
+    }
+}
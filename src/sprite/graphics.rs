@@ -0,0 +1,582 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Cursor, Write};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use crossterm::{cursor::MoveTo, queue};
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView, ImageFormat, RgbaImage};
+use ratatui::layout::Rect;
+
+/// How sprites are drawn on the detail screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Sixel,
+    Iterm2,
+    HalfBlocks,
+}
+
+impl GraphicsProtocol {
+    pub fn all() -> &'static [GraphicsProtocol] {
+        &[
+            GraphicsProtocol::Kitty,
+            GraphicsProtocol::Sixel,
+            GraphicsProtocol::Iterm2,
+            GraphicsProtocol::HalfBlocks,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GraphicsProtocol::Kitty => "Kitty",
+            GraphicsProtocol::Sixel => "Sixel",
+            GraphicsProtocol::Iterm2 => "iTerm2",
+            GraphicsProtocol::HalfBlocks => "Half-blocks",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "kitty" => Some(GraphicsProtocol::Kitty),
+            "sixel" => Some(GraphicsProtocol::Sixel),
+            "iterm" | "iterm2" => Some(GraphicsProtocol::Iterm2),
+            "halfblocks" | "half-blocks" | "blocks" | "none" => Some(GraphicsProtocol::HalfBlocks),
+            _ => None,
+        }
+    }
+}
+
+/// Pick the best protocol for the current terminal. `POKEDEX_GRAPHICS` overrides detection.
+pub fn detect() -> GraphicsProtocol {
+    detect_from_env(|key| std::env::var(key).ok())
+}
+
+fn detect_from_env(env: impl Fn(&str) -> Option<String>) -> GraphicsProtocol {
+    if let Some(p) = env("POKEDEX_GRAPHICS").and_then(|v| GraphicsProtocol::from_name(&v)) {
+        return p;
+    }
+    // Multiplexers swallow image escapes unless passthrough is configured
+    if env("TMUX").is_some() || env("STY").is_some() {
+        return GraphicsProtocol::HalfBlocks;
+    }
+
+    let term = env("TERM").unwrap_or_default().to_lowercase();
+    let program = env("TERM_PROGRAM").unwrap_or_default().to_lowercase();
+
+    if env("KITTY_WINDOW_ID").is_some()
+        || term.contains("kitty")
+        || term.contains("ghostty")
+        || program == "ghostty"
+    {
+        return GraphicsProtocol::Kitty;
+    }
+    if program == "iterm.app"
+        || program == "wezterm"
+        || env("LC_TERMINAL").is_some_and(|t| t == "iTerm2")
+        || env("WEZTERM_EXECUTABLE").is_some()
+    {
+        return GraphicsProtocol::Iterm2;
+    }
+    if term.contains("sixel")
+        || term.starts_with("foot")
+        || term.starts_with("mlterm")
+        || term.starts_with("yaft")
+        || program == "mintty"
+    {
+        return GraphicsProtocol::Sixel;
+    }
+    GraphicsProtocol::HalfBlocks
+}
+
+/// Pixel size of one terminal cell, from the terminal's reported window size
+pub fn cell_size() -> (u16, u16) {
+    match crossterm::terminal::window_size() {
+        Ok(ws) if ws.width > 0 && ws.height > 0 && ws.columns > 0 && ws.rows > 0 => {
+            ((ws.width / ws.columns).max(1), (ws.height / ws.rows).max(1))
+        }
+        // Common default when the terminal doesn't report pixels
+        _ => (10, 20),
+    }
+}
+
+/// Scale `img` to fit `cols`×`rows` cells, keeping pixel art crisp by using
+/// whole-number nearest-neighbour scaling when enlarging
+fn fit_image(img: &DynamicImage, cols: u16, rows: u16, cell: (u16, u16)) -> Option<RgbaImage> {
+    let (w, h) = img.dimensions();
+    if w == 0 || h == 0 || cols == 0 || rows == 0 {
+        return None;
+    }
+    let box_w = cols as f64 * cell.0 as f64;
+    let box_h = rows as f64 * cell.1 as f64;
+    let mut scale = (box_w / w as f64).min(box_h / h as f64);
+    if scale >= 1.0 {
+        scale = scale.floor();
+    }
+    let out_w = ((w as f64 * scale) as u32).max(1);
+    let out_h = ((h as f64 * scale) as u32).max(1);
+    Some(image::imageops::resize(
+        img,
+        out_w,
+        out_h,
+        FilterType::Nearest,
+    ))
+}
+
+/// Cells covered by an image of this pixel size
+fn cells_for(img: &RgbaImage, cell: (u16, u16)) -> (u16, u16) {
+    (
+        img.width().div_ceil(cell.0 as u32) as u16,
+        img.height().div_ceil(cell.1 as u32) as u16,
+    )
+}
+
+fn png_bytes(img: &RgbaImage) -> Option<Vec<u8>> {
+    let mut out = Cursor::new(Vec::new());
+    img.write_to(&mut out, ImageFormat::Png).ok()?;
+    Some(out.into_inner())
+}
+
+/// Escape sequence that draws `img` at the cursor, fitted to `cols`×`rows` cells
+pub fn encode(
+    protocol: GraphicsProtocol,
+    img: &DynamicImage,
+    cols: u16,
+    rows: u16,
+    cell: (u16, u16),
+) -> Option<String> {
+    let fitted = fit_image(img, cols, rows, cell)?;
+    match protocol {
+        GraphicsProtocol::Kitty => {
+            let (c, r) = cells_for(&fitted, cell);
+            Some(kitty(&png_bytes(&fitted)?, c, r))
+        }
+        GraphicsProtocol::Iterm2 => {
+            let (c, r) = cells_for(&fitted, cell);
+            Some(iterm2(&png_bytes(&fitted)?, c, r))
+        }
+        GraphicsProtocol::Sixel => Some(sixel(&fitted)),
+        GraphicsProtocol::HalfBlocks => None,
+    }
+}
+
+const KITTY_CHUNK: usize = 4096;
+
+fn kitty(png: &[u8], cols: u16, rows: u16) -> String {
+    let data = STANDARD.encode(png);
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK).collect();
+    let mut out = String::with_capacity(data.len() + chunks.len() * 16 + 64);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        let payload = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            // a=T transmit and display, f=100 PNG, C=1 leave the cursor alone, q=2 no replies
+            out.push_str(&format!(
+                "\x1b_Ga=T,f=100,c={},r={},C=1,q=2,m={};{}\x1b\\",
+                cols, rows, more, payload
+            ));
+        } else {
+            out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, payload));
+        }
+    }
+    out
+}
+
+/// Removes every image the Kitty protocol has placed
+pub const KITTY_DELETE_ALL: &str = "\x1b_Ga=d,d=A,q=2\x1b\\";
+
+fn iterm2(png: &[u8], cols: u16, rows: u16) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+        png.len(),
+        cols,
+        rows,
+        STANDARD.encode(png)
+    )
+}
+
+fn sixel(img: &RgbaImage) -> String {
+    let (w, h) = img.dimensions();
+
+    // Sprites rarely use more than a few dozen colors; beyond 256 fall back to a 6×7×6 cube
+    let mut palette: Vec<(u8, u8, u8)> = Vec::new();
+    let mut index: HashMap<(u8, u8, u8), usize> = HashMap::new();
+    let unique: std::collections::HashSet<(u8, u8, u8)> = img
+        .pixels()
+        .filter(|p| p[3] >= 128)
+        .map(|p| (p[0], p[1], p[2]))
+        .collect();
+    let quantize = unique.len() > 256;
+    let key = |p: &image::Rgba<u8>| -> (u8, u8, u8) {
+        if quantize {
+            let q = |v: u8, levels: u32| -> u8 {
+                let step = (v as u32 * (levels - 1) + 127) / 255;
+                (step * 255 / (levels - 1)) as u8
+            };
+            (q(p[0], 6), q(p[1], 7), q(p[2], 6))
+        } else {
+            (p[0], p[1], p[2])
+        }
+    };
+
+    // Color index per pixel, None for transparent
+    let pixels: Vec<Option<usize>> = img
+        .pixels()
+        .map(|p| {
+            if p[3] < 128 {
+                return None;
+            }
+            let k = key(p);
+            Some(*index.entry(k).or_insert_with(|| {
+                palette.push(k);
+                palette.len() - 1
+            }))
+        })
+        .collect();
+
+    // P2=1: unset pixels stay transparent
+    let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", w, h);
+    for (i, (r, g, b)) in palette.iter().enumerate() {
+        let pct = |v: u8| v as u32 * 100 / 255;
+        out.push_str(&format!("#{};2;{};{};{}", i, pct(*r), pct(*g), pct(*b)));
+    }
+
+    for band in (0..h).step_by(6) {
+        let mut used: Vec<usize> = (band..(band + 6).min(h))
+            .flat_map(|y| (0..w).map(move |x| (y * w + x) as usize))
+            .filter_map(|i| pixels[i])
+            .collect();
+        used.sort_unstable();
+        used.dedup();
+
+        for (n, &color) in used.iter().enumerate() {
+            if n > 0 {
+                out.push('$');
+            }
+            out.push_str(&format!("#{}", color));
+            let mut run_char = '\0';
+            let mut run_len = 0;
+            for x in 0..w {
+                let mut bits = 0u8;
+                for dy in 0..6 {
+                    let y = band + dy;
+                    if y < h && pixels[(y * w + x) as usize] == Some(color) {
+                        bits |= 1 << dy;
+                    }
+                }
+                let ch = (b'?' + bits) as char;
+                if ch == run_char {
+                    run_len += 1;
+                } else {
+                    push_run(&mut out, run_char, run_len);
+                    run_char = ch;
+                    run_len = 1;
+                }
+            }
+            push_run(&mut out, run_char, run_len);
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_run(out: &mut String, ch: char, len: usize) {
+    match len {
+        0 => {}
+        1..=3 => out.extend(std::iter::repeat_n(ch, len)),
+        _ => out.push_str(&format!("!{}{}", len, ch)),
+    }
+}
+
+/// Draws sprites with a terminal graphics protocol on top of the ratatui buffer.
+/// The UI leaves the sprite cells untouched (skipped); this writes the image there
+/// after each frame and tells the caller when the screen must be cleared first.
+pub struct GraphicsRenderer {
+    protocol: GraphicsProtocol,
+    cell: (u16, u16),
+    /// Area and content hash of the image currently on screen
+    shown: Option<(Rect, u64)>,
+    /// Escape sequences by area and content hash, so animation loops encode each frame once
+    encoded: HashMap<(Rect, u64), String>,
+}
+
+/// Encoded images kept before the cache starts over
+const MAX_ENCODED: usize = 64;
+
+impl GraphicsRenderer {
+    pub fn new(protocol: GraphicsProtocol) -> Self {
+        Self {
+            protocol,
+            cell: cell_size(),
+            shown: None,
+            encoded: HashMap::new(),
+        }
+    }
+
+    pub fn protocol(&self) -> GraphicsProtocol {
+        self.protocol
+    }
+
+    fn key(image: Option<(Rect, &[u8])>) -> Option<(Rect, u64)> {
+        image.map(|(area, bytes)| {
+            let mut hasher = DefaultHasher::new();
+            bytes.hash(&mut hasher);
+            (area, hasher.finish())
+        })
+    }
+
+    /// Whether the screen must be cleared before presenting `image`: skipped cells keep
+    /// whatever the terminal showed before, so moving or removing the image starts from
+    /// blank. A new image in the same area (e.g. the next animation frame) is handled by
+    /// `present`.
+    pub fn is_stale(&self, image: Option<(Rect, &[u8])>) -> bool {
+        self.shown.map(|(area, _)| area) != image.map(|(area, _)| area)
+    }
+
+    /// Forget the image on screen, e.g. after a terminal clear
+    pub fn reset<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        if self.protocol == GraphicsProtocol::Kitty && self.shown.is_some() {
+            out.write_all(KITTY_DELETE_ALL.as_bytes())?;
+            out.flush()?;
+        }
+        self.shown = None;
+        Ok(())
+    }
+
+    /// Draw `image` (PNG bytes in an area) if it isn't already on screen
+    pub fn present<W: Write>(
+        &mut self,
+        out: &mut W,
+        image: Option<(Rect, &[u8])>,
+    ) -> io::Result<()> {
+        let key = Self::key(image);
+        if key == self.shown {
+            return Ok(());
+        }
+        let (Some((area, bytes)), Some((_, hash))) = (image, key) else {
+            self.shown = None;
+            return Ok(());
+        };
+
+        if !self.encoded.contains_key(&(area, hash)) {
+            let sequence = image::load_from_memory(bytes)
+                .ok()
+                .and_then(|img| encode(self.protocol, &img, area.width, area.height, self.cell));
+            let Some(sequence) = sequence else {
+                // Remember the failure so the screen isn't cleared again every frame
+                self.shown = key;
+                return Ok(());
+            };
+            if self.encoded.len() >= MAX_ENCODED {
+                self.encoded.clear();
+            }
+            self.encoded.insert((area, hash), sequence);
+        }
+
+        // Replacing an image in place: wipe the old one first, transparent pixels would show it
+        if self.shown.is_some() {
+            if self.protocol == GraphicsProtocol::Kitty {
+                out.write_all(KITTY_DELETE_ALL.as_bytes())?;
+            } else {
+                let blank = " ".repeat(area.width as usize);
+                for y in area.top()..area.bottom() {
+                    queue!(out, MoveTo(area.x, y))?;
+                    out.write_all(blank.as_bytes())?;
+                }
+            }
+        }
+        queue!(out, MoveTo(area.x, area.y))?;
+        out.write_all(self.encoded[&(area, hash)].as_bytes())?;
+        out.flush()?;
+        self.shown = key;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn env_of(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    }
+
+    fn sprite(w: u32, h: u32) -> DynamicImage {
+        let mut img = RgbaImage::new(w, h);
+        for y in 0..h {
+            for x in 0..w {
+                let px = if x == 0 {
+                    Rgba([0, 0, 0, 0])
+                } else if (x + y) % 2 == 0 {
+                    Rgba([255, 0, 0, 255])
+                } else {
+                    Rgba([0, 0, 255, 255])
+                };
+                img.put_pixel(x, y, px);
+            }
+        }
+        DynamicImage::ImageRgba8(img)
+    }
+
+    #[test]
+    fn test_detect_from_env() {
+        assert_eq!(
+            detect_from_env(env_of(&[("KITTY_WINDOW_ID", "1")])),
+            GraphicsProtocol::Kitty
+        );
+        assert_eq!(
+            detect_from_env(env_of(&[("TERM_PROGRAM", "iTerm.app")])),
+            GraphicsProtocol::Iterm2
+        );
+        assert_eq!(
+            detect_from_env(env_of(&[("TERM", "foot")])),
+            GraphicsProtocol::Sixel
+        );
+        assert_eq!(
+            detect_from_env(env_of(&[("TERM", "xterm-256color")])),
+            GraphicsProtocol::HalfBlocks
+        );
+        assert_eq!(
+            detect_from_env(env_of(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux")])),
+            GraphicsProtocol::HalfBlocks
+        );
+    }
+
+    #[test]
+    fn test_detect_override() {
+        assert_eq!(
+            detect_from_env(env_of(&[
+                ("POKEDEX_GRAPHICS", "sixel"),
+                ("KITTY_WINDOW_ID", "1")
+            ])),
+            GraphicsProtocol::Sixel
+        );
+        assert_eq!(
+            detect_from_env(env_of(&[
+                ("POKEDEX_GRAPHICS", "none"),
+                ("KITTY_WINDOW_ID", "1")
+            ])),
+            GraphicsProtocol::HalfBlocks
+        );
+        // Unknown values fall through to detection
+        assert_eq!(
+            detect_from_env(env_of(&[("POKEDEX_GRAPHICS", "bogus"), ("TERM", "foot")])),
+            GraphicsProtocol::Sixel
+        );
+    }
+
+    #[test]
+    fn test_fit_image_uses_whole_number_upscale() {
+        let img = sprite(10, 10);
+        // 8×4 cells of 10×20 px = 80×80 box → 8× exactly
+        let fitted = fit_image(&img, 8, 4, (10, 20)).unwrap();
+        assert_eq!(fitted.dimensions(), (80, 80));
+        // 7×4 cells = 70×80 box → 7×
+        let fitted = fit_image(&img, 7, 4, (10, 20)).unwrap();
+        assert_eq!(fitted.dimensions(), (70, 70));
+        assert_eq!(cells_for(&fitted, (10, 20)), (7, 4));
+        assert!(fit_image(&img, 0, 4, (10, 20)).is_none());
+    }
+
+    #[test]
+    fn test_kitty_chunks_payload() {
+        let png = vec![0u8; 6000];
+        let seq = kitty(&png, 4, 2);
+        assert!(seq.starts_with("\x1b_Ga=T,f=100,c=4,r=2,C=1,q=2,m=1;"));
+        // 8000 base64 chars → two chunks, the last with m=0
+        assert_eq!(seq.matches("\x1b_G").count(), 2);
+        assert!(seq.contains("\x1b_Gm=0;"));
+        assert!(seq.ends_with("\x1b\\"));
+    }
+
+    #[test]
+    fn test_iterm2_sequence() {
+        let seq = iterm2(b"png", 3, 2);
+        assert_eq!(
+            seq,
+            "\x1b]1337;File=inline=1;size=3;width=3;height=2;preserveAspectRatio=1:cG5n\x07"
+        );
+    }
+
+    #[test]
+    fn test_sixel_sequence() {
+        let img = sprite(4, 7).to_rgba8();
+        let seq = sixel(&img);
+        assert!(seq.starts_with("\x1bP0;1;0q\"1;1;4;7"));
+        assert!(seq.ends_with("-\x1b\\"));
+        // Two colors in the palette (in first-seen order), two bands of six rows
+        assert!(seq.contains("#0;2;0;0;100"));
+        assert!(seq.contains("#1;2;100;0;0"));
+        assert_eq!(seq.matches('-').count(), 2);
+    }
+
+    #[test]
+    fn test_push_run_compresses() {
+        let mut out = String::new();
+        push_run(&mut out, '~', 2);
+        push_run(&mut out, '?', 10);
+        assert_eq!(out, "~~!10?");
+    }
+
+    #[test]
+    fn test_renderer_tracks_shown_image() {
+        let mut png = Cursor::new(Vec::new());
+        sprite(4, 4).write_to(&mut png, ImageFormat::Png).unwrap();
+        let png = png.into_inner();
+        let area = Rect::new(2, 3, 4, 2);
+
+        let mut renderer = GraphicsRenderer {
+            protocol: GraphicsProtocol::Kitty,
+            cell: (10, 20),
+            shown: None,
+            encoded: HashMap::new(),
+        };
+        assert!(!renderer.is_stale(None));
+        assert!(renderer.is_stale(Some((area, &png))));
+
+        let mut out = Vec::new();
+        renderer.present(&mut out, Some((area, &png))).unwrap();
+        let written = String::from_utf8(out).unwrap();
+        assert!(written.starts_with("\x1b[4;3H\x1b_Ga=T"));
+
+        // Same image again: nothing to do
+        assert!(!renderer.is_stale(Some((area, &png))));
+        let mut out = Vec::new();
+        renderer.present(&mut out, Some((area, &png))).unwrap();
+        assert!(out.is_empty());
+
+        assert!(renderer.is_stale(None));
+        assert!(renderer.is_stale(Some((Rect::new(0, 0, 4, 2), &png))));
+
+        // A different image in the same area replaces the old one without a clear
+        let mut other = Cursor::new(Vec::new());
+        sprite(4, 5).write_to(&mut other, ImageFormat::Png).unwrap();
+        let other = other.into_inner();
+        assert!(!renderer.is_stale(Some((area, &other))));
+        let mut out = Vec::new();
+        renderer.present(&mut out, Some((area, &other))).unwrap();
+        let written = String::from_utf8(out).unwrap();
+        assert!(written.starts_with(KITTY_DELETE_ALL));
+        assert!(written.contains("\x1b[4;3H\x1b_Ga=T"));
+
+        let mut out = Vec::new();
+        renderer.reset(&mut out).unwrap();
+        assert_eq!(out, KITTY_DELETE_ALL.as_bytes());
+        assert!(!renderer.is_stale(None));
+    }
+
+    #[test]
+    fn test_protocol_names() {
+        for p in GraphicsProtocol::all() {
+            assert_eq!(GraphicsProtocol::from_name(p.label()), Some(*p));
+        }
+    }
+}
@@ -1 +1,4 @@
+pub mod animation;
+pub mod cache;
+pub mod graphics;
 pub mod renderer;
@@ -1,79 +1,459 @@
-use image::{DynamicImage, GenericImageView};
+use image::{DynamicImage, RgbaImage};
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
 use ratatui::widgets::Widget;
+use serde::{Deserialize, Serialize};
 
-/// A rendered sprite: rows of (fg_color, bg_color, char) cells using half-block unicode
+/// How many image pixels each terminal cell carries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockMode {
+    /// ▀▄ — 1×2 pixels, two colors per cell
+    #[default]
+    HalfBlock,
+    /// ▘▚▙ — 2×2 pixels, two colors per cell
+    Quadrant,
+    /// 🬗 — 2×3 pixels, two colors per cell
+    Sextant,
+    /// ⣿ — 2×4 dots, one color per cell
+    Braille,
+}
+
+impl BlockMode {
+    pub fn all() -> &'static [BlockMode] {
+        &[
+            BlockMode::HalfBlock,
+            BlockMode::Quadrant,
+            BlockMode::Sextant,
+            BlockMode::Braille,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BlockMode::HalfBlock => "Half-block",
+            BlockMode::Quadrant => "Quadrant",
+            BlockMode::Sextant => "Sextant",
+            BlockMode::Braille => "Braille",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let all = Self::all();
+        let i = all.iter().position(|m| m == self).unwrap_or(0);
+        all[(i + 1) % all.len()]
+    }
+
+    /// Pixels per cell (columns, rows)
+    fn pixels(&self) -> (u32, u32) {
+        match self {
+            BlockMode::HalfBlock => (1, 2),
+            BlockMode::Quadrant => (2, 2),
+            BlockMode::Sextant => (2, 3),
+            BlockMode::Braille => (2, 4),
+        }
+    }
+}
+
+/// Colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorDepth {
+    #[default]
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+impl ColorDepth {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColorDepth::TrueColor => "24-bit",
+            ColorDepth::Ansi256 => "256 colors",
+            ColorDepth::Ansi16 => "16 colors",
+        }
+    }
+
+    /// Guess from the environment; `POKEDEX_COLORS` (truecolor/256/16) overrides
+    pub fn detect() -> Self {
+        Self::detect_from_env(|key| std::env::var(key).ok())
+    }
+
+    fn detect_from_env(env: impl Fn(&str) -> Option<String>) -> Self {
+        match env("POKEDEX_COLORS").as_deref().map(str::trim) {
+            Some("truecolor" | "24bit") => return ColorDepth::TrueColor,
+            Some("256") => return ColorDepth::Ansi256,
+            Some("16") => return ColorDepth::Ansi16,
+            _ => {}
+        }
+        if matches!(env("COLORTERM").as_deref(), Some("truecolor" | "24bit")) {
+            return ColorDepth::TrueColor;
+        }
+        match env("TERM") {
+            Some(term) if term.contains("256") => ColorDepth::Ansi256,
+            Some(term) if term.contains("direct") => ColorDepth::TrueColor,
+            Some(term) if !term.is_empty() => ColorDepth::Ansi16,
+            // No TERM at all (e.g. Windows Terminal) means a modern console
+            _ => ColorDepth::TrueColor,
+        }
+    }
+}
+
+/// How a sprite is turned into cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RenderOptions {
+    pub mode: BlockMode,
+    pub depth: ColorDepth,
+}
+
+type Rgb = (u8, u8, u8);
+
+/// A rendered sprite: rows of (char, fg, bg) cells using block or braille characters
 pub struct SpriteWidget {
-    /// Each row is a Vec of (char, fg, bg) — one terminal row = 2 pixel rows via ▄/▀
     cells: Vec<Vec<(char, Color, Color)>>,
 }
 
 impl SpriteWidget {
     pub fn from_png_bytes(bytes: &[u8], max_width: u16, max_height: u16) -> Option<Self> {
+        Self::from_png_bytes_with(bytes, max_width, max_height, RenderOptions::default())
+    }
+
+    pub fn from_png_bytes_with(
+        bytes: &[u8],
+        max_width: u16,
+        max_height: u16,
+        options: RenderOptions,
+    ) -> Option<Self> {
         let img = image::load_from_memory(bytes).ok()?;
-        Some(Self::from_image(&img, max_width, max_height))
+        Some(Self::from_image_with(&img, max_width, max_height, options))
     }
 
     pub fn from_image(img: &DynamicImage, max_width: u16, max_height: u16) -> Self {
-        let rgba = img.to_rgba8();
-        let (w, h) = rgba.dimensions();
-
-        // Scale to fit — each terminal cell is 1 char wide, 2 pixels tall (half-block)
-        let scale_x = (w as f64) / (max_width as f64);
-        let scale_y = (h as f64) / ((max_height * 2) as f64);
-        let scale = scale_x.max(scale_y).max(1.0);
-
-        let out_w = ((w as f64) / scale).ceil() as u32;
-        let out_h = ((h as f64) / scale).ceil() as u32;
-        // Make sure height is even for half-block pairing
-        // Using manual modulo check instead of is_multiple_of() for stable Rust compatibility
-        #[allow(clippy::manual_is_multiple_of)]
-        let out_h = if out_h % 2 != 0 { out_h + 1 } else { out_h };
-
-        let mut cells = Vec::new();
-
-        for row in (0..out_h).step_by(2) {
-            let mut line = Vec::new();
-            for col in 0..out_w {
-                let top = sample_pixel(img, col, row, scale);
-                let bot = sample_pixel(img, col, row + 1, scale);
-
-                let (ch, fg, bg) = match (top, bot) {
-                    (None, None) => (' ', Color::Reset, Color::Reset),
-                    (Some(tc), None) => ('▀', to_color(tc), Color::Reset),
-                    (None, Some(bc)) => ('▄', to_color(bc), Color::Reset),
-                    (Some(tc), Some(bc)) => ('▀', to_color(tc), to_color(bc)),
-                };
-                line.push((ch, fg, bg));
-            }
-            cells.push(line);
+        Self::from_image_with(img, max_width, max_height, RenderOptions::default())
+    }
+
+    pub fn from_image_with(
+        img: &DynamicImage,
+        max_width: u16,
+        max_height: u16,
+        options: RenderOptions,
+    ) -> Self {
+        let img = trim_transparent(img);
+        let (w, h) = img.dimensions();
+        let (px_w, px_h) = options.mode.pixels();
+        if w == 0 || h == 0 || max_width == 0 || max_height == 0 {
+            return SpriteWidget { cells: Vec::new() };
+        }
+
+        // Cells are roughly twice as tall as wide. `k` is cell columns per image pixel;
+        // never sample finer than the source pixels.
+        let k = (max_width as f64 / w as f64)
+            .min(2.0 * max_height as f64 / h as f64)
+            .min(1.0 / px_w as f64)
+            .min(2.0 / px_h as f64);
+        let grid_w = ((w as f64 * k * px_w as f64).round() as u32).max(1);
+        let grid_h = ((h as f64 * k * px_h as f64 / 2.0).round() as u32).max(1);
+
+        let mut grid: Vec<Option<Rgb>> = (0..grid_h)
+            .flat_map(|gy| (0..grid_w).map(move |gx| (gx, gy)))
+            .map(|(gx, gy)| {
+                let x0 = gx as f64 * w as f64 / grid_w as f64;
+                let x1 = (gx + 1) as f64 * w as f64 / grid_w as f64;
+                let y0 = gy as f64 * h as f64 / grid_h as f64;
+                let y1 = (gy + 1) as f64 * h as f64 / grid_h as f64;
+                sample_area(&img, x0, y0, x1, y1)
+            })
+            .collect();
+        if options.depth != ColorDepth::TrueColor {
+            dither(&mut grid, grid_w, options.depth);
         }
 
+        let cols = grid_w.div_ceil(px_w);
+        let rows = grid_h.div_ceil(px_h);
+        let cells = (0..rows)
+            .map(|row| {
+                (0..cols)
+                    .map(|col| {
+                        let block: Vec<Option<Rgb>> = (0..px_h)
+                            .flat_map(|dy| (0..px_w).map(move |dx| (dx, dy)))
+                            .map(|(dx, dy)| {
+                                let (x, y) = (col * px_w + dx, row * px_h + dy);
+                                if x < grid_w && y < grid_h {
+                                    grid[(y * grid_w + x) as usize]
+                                } else {
+                                    None
+                                }
+                            })
+                            .collect();
+                        build_cell(options.mode, &block, options.depth)
+                    })
+                    .collect()
+            })
+            .collect();
+
         SpriteWidget { cells }
     }
 }
 
-fn sample_pixel(img: &DynamicImage, col: u32, row: u32, scale: f64) -> Option<(u8, u8, u8)> {
-    let sx = ((col as f64) * scale) as u32;
-    let sy = ((row as f64) * scale) as u32;
+/// Crop the transparent border PokéAPI sprites carry
+fn trim_transparent(img: &DynamicImage) -> RgbaImage {
+    let rgba = img.to_rgba8();
+    let (w, h) = rgba.dimensions();
+    let (mut x0, mut y0, mut x1, mut y1) = (w, h, 0, 0);
+    for (x, y, p) in rgba.enumerate_pixels() {
+        if p[3] > 0 {
+            x0 = x0.min(x);
+            y0 = y0.min(y);
+            x1 = x1.max(x + 1);
+            y1 = y1.max(y + 1);
+        }
+    }
+    if x0 >= x1 || y0 >= y1 {
+        return rgba;
+    }
+    image::imageops::crop_imm(&rgba, x0, y0, x1 - x0, y1 - y0).to_image()
+}
+
+/// Alpha-weighted average color over a source box; None when mostly transparent
+fn sample_area(img: &RgbaImage, x0: f64, y0: f64, x1: f64, y1: f64) -> Option<Rgb> {
     let (w, h) = img.dimensions();
-    if sx >= w || sy >= h {
+    let sx0 = (x0.floor() as u32).min(w);
+    let sy0 = (y0.floor() as u32).min(h);
+    let sx1 = (x1.ceil() as u32).max(sx0 + 1).min(w);
+    let sy1 = (y1.ceil() as u32).max(sy0 + 1).min(h);
+    if sx0 >= sx1 || sy0 >= sy1 {
         return None;
     }
-    let pixel = img.get_pixel(sx, sy);
-    if pixel[3] < 128 {
-        None // transparent
-    } else {
-        Some((pixel[0], pixel[1], pixel[2]))
+
+    let (mut r, mut g, mut b, mut a) = (0u64, 0u64, 0u64, 0u64);
+    for y in sy0..sy1 {
+        for x in sx0..sx1 {
+            let p = img.get_pixel(x, y);
+            let alpha = p[3] as u64;
+            r += p[0] as u64 * alpha;
+            g += p[1] as u64 * alpha;
+            b += p[2] as u64 * alpha;
+            a += alpha;
+        }
     }
+    let count = ((sx1 - sx0) * (sy1 - sy0)) as u64;
+    // Keep the pixel when it's at least half covered
+    if a == 0 || a * 2 < 255 * count {
+        return None;
+    }
+    Some(((r / a) as u8, (g / a) as u8, (b / a) as u8))
 }
 
-fn to_color(rgb: (u8, u8, u8)) -> Color {
+fn to_color(rgb: Rgb) -> Color {
     Color::Rgb(rgb.0, rgb.1, rgb.2)
 }
 
+/// Standard xterm values for the 16 ANSI colors
+const ANSI16: [Rgb; 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: Rgb, b: Rgb) -> i32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2);
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// Nearest palette entry as (terminal color, its RGB value)
+fn quantize(rgb: Rgb, depth: ColorDepth) -> (Color, Rgb) {
+    match depth {
+        ColorDepth::TrueColor => (to_color(rgb), rgb),
+        ColorDepth::Ansi256 => {
+            let level = |v: u8| {
+                (0..6)
+                    .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - v as i32).abs())
+                    .unwrap_or(0)
+            };
+            let (r, g, b) = (level(rgb.0), level(rgb.1), level(rgb.2));
+            let cube = (CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+            let cube_index = 16 + 36 * r + 6 * g + b;
+
+            let avg = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+            let step = (avg.saturating_sub(8) / 10).min(23) as u8;
+            let gray_value = 8 + step * 10;
+            let gray = (gray_value, gray_value, gray_value);
+
+            if distance(rgb, gray) < distance(rgb, cube) {
+                (Color::Indexed(232 + step), gray)
+            } else {
+                (Color::Indexed(cube_index as u8), cube)
+            }
+        }
+        ColorDepth::Ansi16 => {
+            let i = (0..16)
+                .min_by_key(|&i| distance(rgb, ANSI16[i]))
+                .unwrap_or(0);
+            (Color::Indexed(i as u8), ANSI16[i])
+        }
+    }
+}
+
+/// Floyd–Steinberg error diffusion onto the palette; transparent pixels take no error
+fn dither(grid: &mut [Option<Rgb>], width: u32, depth: ColorDepth) {
+    let width = width as usize;
+    let mut error = vec![[0i32; 3]; grid.len()];
+    for i in 0..grid.len() {
+        let Some(rgb) = grid[i] else { continue };
+        let clamp = |v: u8, e: i32| (v as i32 + e).clamp(0, 255) as u8;
+        let wanted = (
+            clamp(rgb.0, error[i][0]),
+            clamp(rgb.1, error[i][1]),
+            clamp(rgb.2, error[i][2]),
+        );
+        let (_, got) = quantize(wanted, depth);
+        grid[i] = Some(got);
+
+        let diff = [
+            wanted.0 as i32 - got.0 as i32,
+            wanted.1 as i32 - got.1 as i32,
+            wanted.2 as i32 - got.2 as i32,
+        ];
+        let (x, y) = (i % width, i / width);
+        let mut spread = |dx: isize, dy: usize, weight: i32| {
+            let nx = x as isize + dx;
+            if nx < 0 || nx as usize >= width {
+                return;
+            }
+            let j = (y + dy) * width + nx as usize;
+            if j < grid.len() && grid[j].is_some() {
+                for c in 0..3 {
+                    error[j][c] += diff[c] * weight / 16;
+                }
+            }
+        };
+        spread(1, 0, 7);
+        spread(-1, 1, 3);
+        spread(0, 1, 5);
+        spread(1, 1, 1);
+    }
+}
+
+fn average(colors: &[Rgb]) -> Rgb {
+    let n = colors.len().max(1) as u32;
+    let sum = colors.iter().fold((0u32, 0u32, 0u32), |acc, c| {
+        (acc.0 + c.0 as u32, acc.1 + c.1 as u32, acc.2 + c.2 as u32)
+    });
+    ((sum.0 / n) as u8, (sum.1 / n) as u8, (sum.2 / n) as u8)
+}
+
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// Sextant glyph for a 6-bit mask (bit 0 top-left … bit 5 bottom-right, row-major)
+fn sextant_char(mask: u32) -> char {
+    match mask {
+        0 => ' ',
+        21 => '▌',
+        42 => '▐',
+        63 => '█',
+        // U+1FB00.. skips the four patterns that already exist above
+        m => {
+            let offset = m - 1 - u32::from(m > 21) - u32::from(m > 42);
+            char::from_u32(0x1FB00 + offset).unwrap_or('█')
+        }
+    }
+}
+
+/// Braille dot bits for (column, row) within the 2×4 cell
+const BRAILLE_DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// Turn one cell's pixels (row-major) into a glyph with foreground/background colors
+fn build_cell(mode: BlockMode, block: &[Option<Rgb>], depth: ColorDepth) -> (char, Color, Color) {
+    let color = |rgb: Rgb| quantize(rgb, depth).0;
+    let opaque: Vec<Rgb> = block.iter().flatten().copied().collect();
+    if opaque.is_empty() {
+        return (' ', Color::Reset, Color::Reset);
+    }
+
+    if mode == BlockMode::Braille {
+        let mut bits = 0;
+        for (i, px) in block.iter().enumerate() {
+            if px.is_some() {
+                bits |= BRAILLE_DOTS[i % 2][i / 2];
+            }
+        }
+        let ch = char::from_u32(0x2800 + bits).unwrap_or(' ');
+        return (ch, color(average(&opaque)), Color::Reset);
+    }
+
+    // Split into two color groups: transparent vs opaque when the cell has gaps,
+    // otherwise around the two most different pixels
+    let mask: Vec<bool> = if opaque.len() < block.len() {
+        block.iter().map(|p| p.is_some()).collect()
+    } else {
+        let mut seeds = (opaque[0], opaque[0]);
+        let mut best = -1;
+        for (i, &a) in opaque.iter().enumerate() {
+            for &b in &opaque[i + 1..] {
+                if distance(a, b) > best {
+                    best = distance(a, b);
+                    seeds = (a, b);
+                }
+            }
+        }
+        opaque
+            .iter()
+            .map(|&p| distance(p, seeds.0) <= distance(p, seeds.1))
+            .collect()
+    };
+    let (fg_px, bg_px): (Vec<Rgb>, Vec<Rgb>) = block
+        .iter()
+        .zip(&mask)
+        .filter_map(|(p, &m)| p.map(|p| (p, m)))
+        .fold((Vec::new(), Vec::new()), |(mut fg, mut bg), (p, m)| {
+            if m {
+                fg.push(p);
+            } else {
+                bg.push(p);
+            }
+            (fg, bg)
+        });
+    let fg = color(average(&fg_px));
+    let bg = if bg_px.is_empty() {
+        Color::Reset
+    } else {
+        color(average(&bg_px))
+    };
+
+    let bits = mask
+        .iter()
+        .enumerate()
+        .filter(|(_, &m)| m)
+        .fold(0u32, |acc, (i, _)| acc | 1 << i);
+    let ch = match mode {
+        BlockMode::HalfBlock => match bits {
+            0b01 => '▀',
+            0b10 => '▄',
+            _ => '█',
+        },
+        BlockMode::Quadrant => QUADRANTS[bits as usize],
+        _ => sextant_char(bits),
+    };
+    (ch, fg, bg)
+}
+
 impl Widget for &SpriteWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
         for (row_idx, row) in self.cells.iter().enumerate() {
@@ -84,11 +464,18 @@ impl Widget for &SpriteWidget {
                 if col_idx as u16 >= area.width {
                     break;
                 }
+                // Transparent parts keep whatever is underneath (e.g. a row highlight)
+                if ch == ' ' && bg == Color::Reset {
+                    continue;
+                }
                 let x = area.x + col_idx as u16;
                 let y = area.y + row_idx as u16;
-                buf[(x, y)]
-                    .set_char(ch)
-                    .set_style(Style::default().fg(fg).bg(bg));
+                let style = if bg == Color::Reset {
+                    Style::default().fg(fg)
+                } else {
+                    Style::default().fg(fg).bg(bg)
+                };
+                buf[(x, y)].set_char(ch).set_style(style);
             }
         }
     }
@@ -170,23 +557,23 @@ mod tests {
     }
 
     #[test]
-    fn test_sample_pixel_within_bounds() {
-        let img = create_test_image(10, 10);
-        let pixel = super::sample_pixel(&img, 5, 5, 1.0);
+    fn test_sample_area_within_bounds() {
+        let img = create_test_image(10, 10).to_rgba8();
+        let pixel = super::sample_area(&img, 5.0, 5.0, 6.0, 6.0);
         assert!(pixel.is_some());
     }
 
     #[test]
-    fn test_sample_pixel_out_of_bounds() {
-        let img = create_test_image(10, 10);
-        let pixel = super::sample_pixel(&img, 20, 20, 1.0);
+    fn test_sample_area_out_of_bounds() {
+        let img = create_test_image(10, 10).to_rgba8();
+        let pixel = super::sample_area(&img, 20.0, 20.0, 21.0, 21.0);
         assert!(pixel.is_none());
     }
 
     #[test]
-    fn test_sample_pixel_transparent() {
-        let img = create_transparent_image(10, 10);
-        let pixel = super::sample_pixel(&img, 5, 5, 1.0);
+    fn test_sample_area_transparent() {
+        let img = create_transparent_image(10, 10).to_rgba8();
+        let pixel = super::sample_area(&img, 5.0, 5.0, 6.0, 6.0);
         assert!(pixel.is_none()); // Transparent pixels return None
     }
 
@@ -201,4 +588,187 @@ mod tests {
         // Verify the cell contains actual data (not empty)
         assert!(!widget.cells[0].is_empty());
     }
+
+    fn padded_image() -> DynamicImage {
+        // 2×2 opaque red square inside a 10×10 transparent canvas
+        let mut img = RgbaImage::new(10, 10);
+        for y in 4..6 {
+            for x in 3..5 {
+                img.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+            }
+        }
+        DynamicImage::ImageRgba8(img)
+    }
+
+    #[test]
+    fn test_trim_transparent() {
+        let trimmed = trim_transparent(&padded_image());
+        assert_eq!(trimmed.dimensions(), (2, 2));
+        // Fully transparent images are left alone
+        assert_eq!(
+            trim_transparent(&create_transparent_image(3, 3)).dimensions(),
+            (3, 3)
+        );
+    }
+
+    #[test]
+    fn test_trimmed_sprite_fills_area() {
+        let widget = SpriteWidget::from_image(&padded_image(), 10, 10);
+        assert_eq!(widget.cells.len(), 1);
+        assert_eq!(widget.cells[0].len(), 2);
+        assert_eq!(widget.cells[0][0].0, '█');
+    }
+
+    #[test]
+    fn test_sample_area_averages() {
+        let mut img = RgbaImage::new(2, 1);
+        img.put_pixel(0, 0, Rgba([200, 0, 0, 255]));
+        img.put_pixel(1, 0, Rgba([0, 0, 100, 255]));
+        assert_eq!(sample_area(&img, 0.0, 0.0, 2.0, 1.0), Some((100, 0, 50)));
+
+        // Half covered is kept, mostly transparent is dropped
+        img.put_pixel(1, 0, Rgba([0, 0, 0, 0]));
+        assert_eq!(sample_area(&img, 0.0, 0.0, 2.0, 1.0), Some((200, 0, 0)));
+        let mut faint = RgbaImage::new(2, 1);
+        faint.put_pixel(0, 0, Rgba([200, 0, 0, 100]));
+        assert_eq!(sample_area(&faint, 0.0, 0.0, 2.0, 1.0), None);
+    }
+
+    #[test]
+    fn test_quadrant_cells() {
+        // Quadrant pixels are twice as tall as wide, so each covers two source rows
+        let mut img = RgbaImage::new(2, 4);
+        for y in 0..4 {
+            for x in 0..2 {
+                let red = (x == 0) == (y < 2);
+                let px = if red {
+                    Rgba([255, 0, 0, 255])
+                } else {
+                    Rgba([0, 0, 255, 255])
+                };
+                img.put_pixel(x, y, px);
+            }
+        }
+        let widget = SpriteWidget::from_image_with(
+            &DynamicImage::ImageRgba8(img),
+            10,
+            10,
+            RenderOptions {
+                mode: BlockMode::Quadrant,
+                ..Default::default()
+            },
+        );
+        assert_eq!(widget.cells.len(), 1);
+        assert_eq!(widget.cells[0].len(), 1);
+        let (ch, fg, bg) = widget.cells[0][0];
+        assert_eq!(ch, '▚');
+        assert_eq!(fg, Color::Rgb(255, 0, 0));
+        assert_eq!(bg, Color::Rgb(0, 0, 255));
+    }
+
+    #[test]
+    fn test_braille_uses_one_color() {
+        let widget = SpriteWidget::from_image_with(
+            &create_test_image(2, 4),
+            10,
+            10,
+            RenderOptions {
+                mode: BlockMode::Braille,
+                ..Default::default()
+            },
+        );
+        assert_eq!(widget.cells.len(), 1);
+        let (ch, _, bg) = widget.cells[0][0];
+        assert_eq!(ch, '⣿');
+        assert_eq!(bg, Color::Reset);
+    }
+
+    #[test]
+    fn test_sextant_char() {
+        assert_eq!(sextant_char(0), ' ');
+        assert_eq!(sextant_char(1), '\u{1FB00}');
+        assert_eq!(sextant_char(21), '▌');
+        assert_eq!(sextant_char(22), '\u{1FB14}');
+        assert_eq!(sextant_char(62), '\u{1FB3B}');
+        assert_eq!(sextant_char(63), '█');
+    }
+
+    #[test]
+    fn test_block_mode_cycle() {
+        let mut mode = BlockMode::HalfBlock;
+        for _ in 0..BlockMode::all().len() {
+            mode = mode.next();
+        }
+        assert_eq!(mode, BlockMode::HalfBlock);
+    }
+
+    #[test]
+    fn test_quantize() {
+        assert_eq!(
+            quantize((250, 5, 5), ColorDepth::Ansi256),
+            (Color::Indexed(196), (255, 0, 0))
+        );
+        assert_eq!(
+            quantize((128, 128, 128), ColorDepth::Ansi256).0,
+            Color::Indexed(244)
+        );
+        assert_eq!(
+            quantize((250, 5, 5), ColorDepth::Ansi16),
+            (Color::Indexed(9), (255, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_dithered_sprite_uses_palette_only() {
+        let mut img = RgbaImage::new(8, 8);
+        for y in 0..8 {
+            for x in 0..8 {
+                img.put_pixel(x, y, Rgba([(x * 30) as u8, 100, (y * 30) as u8, 255]));
+            }
+        }
+        let widget = SpriteWidget::from_image_with(
+            &DynamicImage::ImageRgba8(img),
+            8,
+            4,
+            RenderOptions {
+                depth: ColorDepth::Ansi16,
+                ..Default::default()
+            },
+        );
+        for &(_, fg, bg) in widget.cells.iter().flatten() {
+            for c in [fg, bg] {
+                assert!(matches!(c, Color::Indexed(0..=15) | Color::Reset));
+            }
+        }
+    }
+
+    #[test]
+    fn test_color_depth_detect() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(
+            ColorDepth::detect_from_env(env(&[("COLORTERM", "truecolor")])),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::detect_from_env(env(&[("TERM", "xterm-256color")])),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(
+            ColorDepth::detect_from_env(env(&[("TERM", "linux")])),
+            ColorDepth::Ansi16
+        );
+        assert_eq!(
+            ColorDepth::detect_from_env(env(&[
+                ("POKEDEX_COLORS", "16"),
+                ("COLORTERM", "truecolor")
+            ])),
+            ColorDepth::Ansi16
+        );
+    }
 }
@@ -0,0 +1,143 @@
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::keymap::{Action, Context};
+use crate::models::collection::{game_label, Status};
+use crate::ui::centered_rect;
+use crate::ui::theme::Theme;
+
+const BAR_WIDTH: usize = 20;
+
+/// List badge for a collection status, one cell wide
+pub fn badge(theme: &Theme, status: Option<Status>) -> Span<'static> {
+    match status {
+        None => Span::raw(" "),
+        Some(Status::Seen) => Span::styled("○", Style::default().fg(theme.muted)),
+        Some(Status::Caught) => Span::styled("●", Style::default().fg(theme.success)),
+        Some(Status::Shiny) => Span::styled("✦", Style::default().fg(theme.accent)),
+    }
+}
+
+/// Caught/seen/shiny per generation for the current game
+pub fn draw_progress(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " Collection: {} ",
+            game_label(&app.collection.game)
+        ))
+        .title_bottom(
+            Line::from(format!(
+                " {} close, game from the command palette ",
+                app.keymap.label(Context::Modal, Action::Back)
+            ))
+            .style(Style::default().fg(app.theme.muted)),
+        )
+        .border_style(Style::default().fg(app.theme.info));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let progress = app.collection_progress();
+    let muted = Style::default().fg(app.theme.muted);
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "{:<7}{:<w$}  {:>9}  {:>9}  {:>5}",
+            "",
+            "",
+            "caught",
+            "seen",
+            "shiny",
+            w = BAR_WIDTH
+        ),
+        muted.add_modifier(Modifier::BOLD),
+    ))];
+    let (mut total, mut seen, mut caught, mut shiny) = (0, 0, 0, 0);
+    for row in progress.iter().filter(|p| p.total > 0) {
+        total += row.total;
+        seen += row.seen;
+        caught += row.caught;
+        shiny += row.shiny;
+        let filled = (row.caught * BAR_WIDTH).div_ceil(row.total);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("Gen {:<3}", row.generation),
+                Style::default().fg(app.theme.text),
+            ),
+            Span::styled("█".repeat(filled), Style::default().fg(app.theme.success)),
+            Span::styled("░".repeat(BAR_WIDTH - filled), muted),
+            Span::styled(
+                format!(
+                    "  {:>9}  {:>9}  {:>5}",
+                    format!("{}/{}", row.caught, row.total),
+                    format!("{}/{}", row.seen, row.total),
+                    row.shiny
+                ),
+                Style::default().fg(app.theme.text),
+            ),
+        ]));
+    }
+    if total == 0 {
+        lines.push(Line::from(Span::styled(
+            "The Pokédex is still loading",
+            muted,
+        )));
+    } else {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "All: {} of {} caught ({}%), {} seen, {} shiny",
+                caught,
+                total,
+                caught * 100 / total,
+                seen,
+                shiny
+            ),
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Prompt for a bulk mark such as "1-151 caught"
+pub fn draw_mark_range(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 20, f.area());
+    f.render_widget(Clear, area);
+
+    let key = |action| app.keymap.label(Context::Modal, action);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " Mark range in {} ",
+            game_label(&app.collection.game)
+        ))
+        .title_bottom(
+            Line::from(format!(
+                " {} mark, {} cancel ",
+                key(Action::Select),
+                key(Action::Back)
+            ))
+            .style(Style::default().fg(app.theme.muted)),
+        )
+        .border_style(Style::default().fg(app.theme.accent));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).split(inner);
+    let input =
+        Paragraph::new(format!("{}▌", app.modal_search)).style(Style::default().fg(app.theme.text));
+    f.render_widget(input, rows[0]);
+    let hint =
+        Paragraph::new("Dex numbers and seen, caught, shiny or none: 1-151 caught, 25 shiny")
+            .style(Style::default().fg(app.theme.muted))
+            .wrap(Wrap { trim: true });
+    f.render_widget(hint, rows[1]);
+}
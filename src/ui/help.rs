@@ -0,0 +1,63 @@
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::keymap::{Action, Context};
+use crate::ui::centered_rect;
+
+/// Bindings for whatever is on screen, grouped by context
+pub fn draw(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Help ")
+        .title_bottom(
+            Line::from(format!(
+                " {}{} scroll, any other key closes ",
+                app.keymap.label(Context::Modal, Action::Up),
+                app.keymap.label(Context::Modal, Action::Down)
+            ))
+            .style(Style::default().fg(app.theme.muted)),
+        )
+        .border_style(Style::default().fg(app.theme.info));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let sections = app.help_sections();
+    let key_width = sections
+        .iter()
+        .flat_map(|(_, rows)| rows.iter().map(|(keys, _)| keys.chars().count()))
+        .max()
+        .unwrap_or(0)
+        .min(inner.width as usize / 2);
+
+    let mut lines = Vec::new();
+    for (title, rows) in &sections {
+        lines.push(Line::from(Span::styled(
+            title.clone(),
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (keys, description) in rows {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<width$}  ", keys, width = key_width),
+                    Style::default().fg(app.theme.info),
+                ),
+                Span::styled(description.clone(), Style::default().fg(app.theme.text)),
+            ]));
+        }
+        lines.push(Line::default());
+    }
+
+    // Keep the last page full instead of scrolling into blank space
+    let max_scroll = lines.len().saturating_sub(inner.height as usize);
+    let scroll = app.help_scroll.min(max_scroll);
+    let help = Paragraph::new(lines).scroll((scroll as u16, 0));
+    f.render_widget(help, inner);
+}
@@ -0,0 +1,76 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{App, ClickTarget};
+use crate::keymap::{Action, Context};
+use crate::ui::{capitalize, centered_rect};
+
+/// Pokémon opened on the detail screen, most recent first
+pub fn draw_recent(f: &mut Frame, app: &App) {
+    let area = centered_rect(40, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let key = |action| app.keymap.label(Context::Modal, action);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Recently viewed ")
+        .title_bottom(
+            Line::from(format!(
+                " {} open, {} close ",
+                key(Action::Select),
+                key(Action::Back)
+            ))
+            .style(Style::default().fg(app.theme.muted)),
+        )
+        .border_style(Style::default().fg(app.theme.info));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = app.recent_rows();
+    if rows.is_empty() {
+        let empty =
+            Paragraph::new("Nothing viewed yet").style(Style::default().fg(app.theme.muted));
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let visible = inner.height as usize;
+    let scroll = app.modal_selected.saturating_sub(visible.saturating_sub(1));
+    let items: Vec<ListItem> = rows
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible)
+        .map(|(i, (id, name))| {
+            let selected = i == app.modal_selected;
+            let name_style = if selected {
+                Style::default()
+                    .fg(app.theme.text)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            let line = Line::from(vec![
+                Span::styled(
+                    format!(" #{:03} ", id),
+                    Style::default().fg(app.theme.muted),
+                ),
+                Span::styled(capitalize(name), name_style),
+            ]);
+            if selected {
+                ListItem::new(line).style(app.theme.selected())
+            } else {
+                ListItem::new(line)
+            }
+        })
+        .collect();
+    f.render_widget(List::new(items), inner);
+
+    for i in scroll..rows.len().min(scroll + visible) {
+        let row = Rect::new(inner.x, inner.y + (i - scroll) as u16, inner.width, 1);
+        app.register_click(row, ClickTarget::ModalRow(i));
+    }
+}
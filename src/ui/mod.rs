@@ -1,41 +1,104 @@
+pub mod collection;
+pub mod help;
+pub mod history;
+pub mod notes;
+pub mod palette;
 pub mod pokemon_detail;
 pub mod pokemon_list;
+pub mod status;
 pub mod team_builder;
+pub mod theme;
 pub mod type_chart;
 
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::Span;
-use ratatui::widgets::{Block, Borders, Clear, Paragraph, Tabs};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Tabs, Wrap};
 use ratatui::Frame;
 
-use crate::app::{App, Screen};
+use crate::app::{App, ClickTarget, Modal, Screen};
+use crate::keymap::{Action, Context};
+use crate::models::pokemon::SpriteVariant;
+use crate::search::fuzzy::fuzzy_match;
+use crate::sprite::cache::SpriteKey;
+use crate::sprite::renderer::RenderOptions;
+use theme::Theme;
 
-pub fn draw(f: &mut Frame, app: &App) {
-    let chunks = Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).split(f.area());
+/// Draws the whole UI. Returns the area reserved for a protocol-drawn sprite, if any.
+pub fn draw(f: &mut Frame, app: &App) -> Option<Rect> {
+    app.clear_click_regions();
+    // Accessible mode adds a status line describing the selection
+    let status_height = u16::from(app.accessible);
+    let chunks = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(status_height),
+    ])
+    .split(f.area());
 
     draw_tabs(f, app, chunks[0]);
 
-    match app.screen {
-        Screen::PokemonList => pokemon_list::draw(f, app, chunks[1]),
-        Screen::PokemonDetail => pokemon_detail::draw(f, app, chunks[1]),
-        Screen::TypeChart => type_chart::draw(f, app, chunks[1]),
-        Screen::TeamBuilder => team_builder::draw(f, app, chunks[1]),
+    let mut sprite_area = None;
+    match (app.screen, app.split()) {
+        (Screen::PokemonList, Some(left)) => {
+            let [list, detail] = split_panes(chunks[1], left);
+            sprite_area = pokemon_detail::draw(f, app, detail);
+            // Last, so the type filter goes over both panes
+            pokemon_list::draw(f, app, list);
+        }
+        (Screen::TeamBuilder, Some(left)) => {
+            let [team, chart] = split_panes(chunks[1], left);
+            type_chart::draw(f, app, chart);
+            // Last, so its modals go over both panes
+            team_builder::draw(f, app, team);
+        }
+        (Screen::PokemonList, None) => pokemon_list::draw(f, app, chunks[1]),
+        (Screen::PokemonDetail, _) => sprite_area = pokemon_detail::draw(f, app, chunks[1]),
+        (Screen::TypeChart, _) => type_chart::draw(f, app, chunks[1]),
+        (Screen::TeamBuilder, None) => team_builder::draw(f, app, chunks[1]),
+    }
+
+    if app.accessible {
+        status::draw(f, app, chunks[2]);
+    }
+
+    match app.modal {
+        Some(Modal::CommandPalette) => palette::draw(f, app),
+        Some(modal @ (Modal::TagFilter | Modal::EditTags)) => notes::draw_tags(f, app, modal),
+        Some(Modal::EditNote) => notes::draw_note_editor(f, app),
+        Some(Modal::MarkRange) => collection::draw_mark_range(f, app),
+        Some(Modal::Progress) => collection::draw_progress(f, app),
+        Some(Modal::Recent) => history::draw_recent(f, app),
+        _ => {}
+    }
+    // Graphics protocol images would be drawn over any modal
+    if app.modal.is_some() {
+        sprite_area = None;
+    }
+    if app.show_help {
+        help::draw(f, app);
+        sprite_area = None;
     }
 
     // Error overlay
     if let Some(ref msg) = app.error_message {
-        let area = centered_rect(60, 20, f.area());
+        // Problems with several files at startup come as separate paragraphs
+        let height = if msg.contains("\n\n") { 50 } else { 20 };
+        let area = centered_rect(60, height, f.area());
         f.render_widget(Clear, area);
         let block = Block::default()
             .title(" Error ")
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Red));
+            .border_style(Style::default().fg(app.theme.error));
         let text = Paragraph::new(format!("{}\n\nPress any key to dismiss", msg))
             .block(block)
-            .style(Style::default().fg(Color::Red));
+            .wrap(Wrap { trim: false })
+            .style(Style::default().fg(app.theme.error));
         f.render_widget(text, area);
+        // Images sit above the text layer, so don't draw one under the overlay
+        return None;
     }
+    sprite_area
 }
 
 fn draw_tabs(f: &mut Frame, app: &App, area: Rect) {
@@ -44,27 +107,96 @@ fn draw_tabs(f: &mut Frame, app: &App, area: Rect) {
         .map(|s| {
             let style = if *s == app.screen {
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(app.theme.accent)
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(app.theme.muted)
             };
             Span::styled(format!(" {} ", s.label()), style)
         })
         .collect();
 
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Pokémon TUI ")
+        .title_style(
+            Style::default()
+                .fg(app.theme.title)
+                .add_modifier(Modifier::BOLD),
+        );
+    if let Some(ref status) = app.status_message {
+        block = block.title(
+            Line::from(Span::styled(
+                format!(" {} ", status),
+                Style::default().fg(app.theme.success),
+            ))
+            .right_aligned(),
+        );
+    } else {
+        block = block.title(
+            Line::from(Span::styled(
+                format!(
+                    " {} help  {} commands ",
+                    app.keymap.label(Context::Global, Action::Help),
+                    app.keymap.label(Context::Global, Action::CommandPalette)
+                ),
+                Style::default().fg(app.theme.muted),
+            ))
+            .right_aligned(),
+        );
+    }
+    // Tabs lays titles out as " title " with a one-column divider in between
+    let inner = block.inner(area);
+    let mut x = inner.x;
+    for (screen, title) in Screen::all().iter().zip(&titles) {
+        let width = title.width() as u16 + 2;
+        let tab = Rect::new(x, area.y, width, area.height).intersection(area);
+        app.register_click(tab, ClickTarget::Tab(*screen));
+        x = x.saturating_add(width + 1);
+    }
+
     let tabs = Tabs::new(titles)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Pokémon TUI ")
-                .title_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
-        )
+        .block(block)
         .select(app.screen.index())
-        .highlight_style(Style::default().fg(Color::Yellow));
+        .highlight_style(Style::default().fg(app.theme.accent));
     f.render_widget(tabs, area);
 }
 
+/// Columns taken by a row thumbnail
+pub fn thumbnail_width(app: &App) -> u16 {
+    if app.accessible {
+        0
+    } else if app.expanded_rows {
+        6
+    } else {
+        2
+    }
+}
+
+/// Draw a Pokémon's front sprite into a small area, requesting it if not loaded yet
+pub fn draw_thumbnail(f: &mut Frame, app: &App, pokemon_id: u32, area: Rect) {
+    if app.accessible {
+        return;
+    }
+    let Some(bytes) = app.want_thumbnail(pokemon_id) else {
+        return;
+    };
+    let key = SpriteKey {
+        pokemon_id,
+        variant: SpriteVariant::FrontDefault,
+        frame: None,
+        width: area.width,
+        height: area.height,
+        options: RenderOptions {
+            mode: app.sprite_mode,
+            depth: app.color_depth,
+        },
+    };
+    if let Some(widget) = app.sprite_cache.get_or_render(key, bytes) {
+        f.render_widget(widget.as_ref(), area.intersection(f.area()));
+    }
+}
+
 pub fn type_color(type_name: &str) -> Color {
     match type_name {
         "normal" => Color::Rgb(168, 168, 120),
@@ -89,6 +221,63 @@ pub fn type_color(type_name: &str) -> Color {
     }
 }
 
+/// Capitalized name padded to `width`, with the characters matched by the
+/// search `query` highlighted
+pub fn highlighted_name(
+    theme: &Theme,
+    name: &str,
+    query: &str,
+    width: usize,
+    style: Style,
+) -> Vec<Span<'static>> {
+    let display: Vec<char> = capitalize(name).chars().collect();
+    let matched = if query.trim().is_empty() {
+        Vec::new()
+    } else {
+        fuzzy_match(query, name)
+            .map(|m| m.indices)
+            .unwrap_or_default()
+    };
+    let highlight = style.fg(theme.accent).add_modifier(Modifier::UNDERLINED);
+
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (i, ch) in display.iter().enumerate() {
+        let is_match = matched.contains(&i);
+        if is_match != run_matched && !run.is_empty() {
+            let run_style = if run_matched { highlight } else { style };
+            spans.push(Span::styled(std::mem::take(&mut run), run_style));
+        }
+        run_matched = is_match;
+        run.push(*ch);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(
+            run,
+            if run_matched { highlight } else { style },
+        ));
+    }
+    if display.len() < width {
+        spans.push(Span::styled(" ".repeat(width - display.len()), style));
+    }
+    spans
+}
+
+/// Left and right panes of a split view, the left `left` percent wide
+fn split_panes(area: Rect, left: u16) -> [Rect; 2] {
+    Layout::horizontal([Constraint::Percentage(left), Constraint::Min(0)]).areas(area)
+}
+
+/// First letter in upper case, for names as the API spells them ("pikachu")
+pub(crate) fn capitalize(s: &str) -> String {
+    let mut c = s.chars();
+    match c.next() {
+        None => String::new(),
+        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
+    }
+}
+
 /// Helper to create a centered rect
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::vertical([
@@ -140,6 +329,25 @@ mod tests {
         assert!(matches!(type_color("invalid"), Color::White));
     }
 
+    #[test]
+    fn test_highlighted_name_marks_matches() {
+        let spans = highlighted_name(&Theme::default(), "pikachu", "pka", 10, Style::default());
+        let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "Pikachu   ");
+        // P, i, k, a, chu, padding
+        assert_eq!(spans.len(), 5);
+        assert_eq!(spans[0].style.fg, Some(Color::Yellow));
+        assert_eq!(spans[1].style.fg, None);
+        assert_eq!(spans[2].style.fg, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn test_highlighted_name_without_query() {
+        let spans = highlighted_name(&Theme::default(), "mew", "", 3, Style::default());
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].content, "Mew");
+    }
+
     #[test]
     fn test_centered_rect() {
         let parent = Rect::new(0, 0, 100, 50);
@@ -0,0 +1,222 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{App, ClickTarget, Modal};
+use crate::keymap::{Action, Context};
+use crate::ui::theme::Theme;
+use crate::ui::{capitalize, centered_rect};
+
+/// Tag picker for the detail screen's Pokémon, or for the list's tag filter
+pub fn draw_tags(f: &mut Frame, app: &App, modal: Modal) {
+    let area = centered_rect(40, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let key = |action| app.keymap.label(Context::Modal, action);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {}: {}▌ ", modal.label(), app.modal_search))
+        .title_bottom(
+            Line::from(format!(
+                " {} toggle, {} close ",
+                key(Action::Select),
+                key(Action::Back)
+            ))
+            .style(Style::default().fg(app.theme.muted)),
+        )
+        .border_style(Style::default().fg(app.theme.accent));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = app.tag_rows(modal);
+    if rows.is_empty() {
+        let hint = if modal == Modal::EditTags {
+            "Type a tag to add it"
+        } else {
+            "No tags yet; add them on the detail screen"
+        };
+        let empty = Paragraph::new(hint).style(Style::default().fg(app.theme.muted));
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let visible = inner.height as usize;
+    let scroll = app.modal_selected.saturating_sub(visible.saturating_sub(1));
+    let items: Vec<ListItem> = rows
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible)
+        .map(|(i, (tag, count, checked))| {
+            let line = if *count == 0 {
+                Line::from(Span::styled(
+                    format!("+ Add \"{}\"", tag),
+                    Style::default().fg(app.theme.accent),
+                ))
+            } else {
+                Line::from(vec![
+                    Span::styled(
+                        if *checked { "[x] " } else { "[ ] " },
+                        Style::default().fg(if *checked {
+                            app.theme.accent
+                        } else {
+                            app.theme.muted
+                        }),
+                    ),
+                    Span::styled(tag.clone(), Style::default().fg(app.theme.text)),
+                    Span::styled(
+                        format!(" ({})", count),
+                        Style::default().fg(app.theme.muted),
+                    ),
+                ])
+            };
+            if i == app.modal_selected {
+                ListItem::new(line).style(app.theme.selected())
+            } else {
+                ListItem::new(line)
+            }
+        })
+        .collect();
+    f.render_widget(List::new(items), inner);
+
+    for i in scroll..rows.len().min(scroll + visible) {
+        let row = Rect::new(inner.x, inner.y + (i - scroll) as u16, inner.width, 1);
+        app.register_click(row, ClickTarget::ModalRow(i));
+    }
+}
+
+/// Editor for the detail screen Pokémon's note, kept scrolled to the end
+pub fn draw_note_editor(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let name = app.detail.as_ref().map(|d| d.name.as_str()).unwrap_or("");
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Notes: {} ", capitalize(name)))
+        .title_bottom(
+            Line::from(format!(
+                " Markdown. Enter for a new line, {} saves and closes ",
+                app.keymap.label(Context::Modal, Action::Back)
+            ))
+            .style(Style::default().fg(app.theme.muted)),
+        )
+        .border_style(Style::default().fg(app.theme.info));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let text = format!("{}▌", app.modal_search);
+    let lines: Vec<Line> = text
+        .split('\n')
+        .flat_map(|line| wrap(line, inner.width as usize))
+        .map(|line| Line::from(Span::styled(line, Style::default().fg(app.theme.text))))
+        .collect();
+    let scroll = lines.len().saturating_sub(inner.height as usize);
+    f.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), inner);
+}
+
+/// A note as styled lines no wider than `width`: `#` headings, `-`/`*`
+/// bullets and `>` quotes are picked out, everything else is plain text
+pub fn note_lines(theme: &Theme, note: &str, width: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for line in note.lines() {
+        let trimmed = line.trim_start();
+        let (prefix, text, style) = if trimmed.starts_with('#') {
+            let heading = trimmed.trim_start_matches('#').trim();
+            let style = Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD);
+            ("", heading, style)
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            ("• ", item, Style::default().fg(theme.text))
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            let style = Style::default()
+                .fg(theme.muted)
+                .add_modifier(Modifier::ITALIC);
+            ("│ ", quote.trim_start(), style)
+        } else {
+            ("", line, Style::default().fg(theme.text))
+        };
+        let indent = prefix.chars().count();
+        for (i, part) in wrap(text, width.saturating_sub(indent))
+            .into_iter()
+            .enumerate()
+        {
+            let lead = if i == 0 {
+                prefix.to_string()
+            } else {
+                " ".repeat(indent)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(lead, Style::default().fg(theme.muted)),
+                Span::styled(part, style),
+            ]));
+        }
+    }
+    lines
+}
+
+/// Break `text` at spaces into lines of at most `width` characters, cutting
+/// words that don't fit on a line of their own
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = vec![String::new()];
+    for word in text.split(' ') {
+        let current = lines.last_mut().expect("never empty");
+        let used = current.chars().count();
+        let needed = word.chars().count() + usize::from(used > 0);
+        if used > 0 && used + needed > width {
+            lines.push(String::new());
+        } else if used > 0 {
+            current.push(' ');
+        }
+        let mut chars: Vec<char> = word.chars().collect();
+        loop {
+            let current = lines.last_mut().expect("never empty");
+            let room = width - current.chars().count();
+            if chars.len() <= room {
+                current.extend(chars);
+                break;
+            }
+            current.extend(chars.drain(..room));
+            lines.push(String::new());
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("", 10), vec![""]);
+        assert_eq!(wrap("needs EV training", 10), vec!["needs EV", "training"]);
+        assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn test_note_lines() {
+        let theme = Theme::default();
+        let lines = note_lines(&theme, "## Plan\n- lead in rain teams\n> from a forum", 12);
+        let texts: Vec<String> = lines.iter().map(text).collect();
+        assert_eq!(
+            texts,
+            vec!["Plan", "• lead in", "  rain teams", "│ from a", "  forum"]
+        );
+        assert!(lines[0].spans[1]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+    }
+}
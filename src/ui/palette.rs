@@ -0,0 +1,86 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{App, ClickTarget};
+use crate::keymap::{Action, Context};
+use crate::ui::centered_rect;
+
+pub fn draw(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" : {}▌ ", app.modal_search))
+        .title_bottom(
+            Line::from(format!(
+                " {} to run, {} to close ",
+                app.keymap.label(Context::Modal, Action::Select),
+                app.keymap.label(Context::Modal, Action::Back)
+            ))
+            .style(Style::default().fg(app.theme.muted)),
+        )
+        .border_style(Style::default().fg(app.theme.info));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let results = app.palette_results();
+    if results.is_empty() {
+        let empty = Paragraph::new("No matches").style(Style::default().fg(app.theme.muted));
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let visible_height = inner.height as usize;
+    let scroll_offset = if app.modal_selected >= visible_height {
+        app.modal_selected - visible_height + 1
+    } else {
+        0
+    };
+
+    let width = inner.width as usize;
+    let items: Vec<ListItem> = results
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(visible_height)
+        .map(|(i, entry)| {
+            let selected = i == app.modal_selected;
+            let label_style = if selected {
+                Style::default()
+                    .fg(app.theme.text)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            // Key hint flush right
+            let label_width = entry.label.chars().count() + 1;
+            let pad = width.saturating_sub(label_width + entry.keys.chars().count() + 1);
+            let line = Line::from(vec![
+                Span::styled(format!(" {}", entry.label), label_style),
+                Span::raw(" ".repeat(pad)),
+                Span::styled(entry.keys.clone(), Style::default().fg(app.theme.info)),
+            ]);
+            if selected {
+                ListItem::new(line).style(app.theme.selected())
+            } else {
+                ListItem::new(line)
+            }
+        })
+        .collect();
+
+    let list = List::new(items);
+    f.render_widget(list, inner);
+
+    let shown = results
+        .len()
+        .saturating_sub(scroll_offset)
+        .min(visible_height);
+    for row in 0..shown {
+        let row_area = Rect::new(inner.x, inner.y + row as u16, inner.width, 1);
+        app.register_click(row_area, ClickTarget::ModalRow(scroll_offset + row));
+    }
+}
@@ -1,99 +1,195 @@
 use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
-use crate::app::{App, LoadingState};
-use crate::sprite::renderer::SpriteWidget;
-use crate::ui::type_color;
+use crate::app::{App, ClickTarget, LoadingState, Screen};
+use crate::keymap::{Action, Context};
+use crate::models::collection::game_label;
+use crate::models::pokemon::PokemonDetail;
+use crate::sprite::cache::SpriteKey;
+use crate::sprite::graphics::GraphicsProtocol;
+use crate::sprite::renderer::RenderOptions;
+use crate::ui::capitalize;
+use crate::ui::collection::badge;
+use crate::ui::notes::note_lines;
 
-pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+pub fn draw(f: &mut Frame, app: &App, area: Rect) -> Option<Rect> {
     match app.detail_loading {
         LoadingState::Loading | LoadingState::Idle => {
             let loading = Paragraph::new("Loading Pokémon details...")
                 .block(Block::default().borders(Borders::ALL).title(" Detail "))
-                .style(Style::default().fg(Color::Yellow));
+                .style(Style::default().fg(app.theme.accent));
             f.render_widget(loading, area);
-            return;
+            return None;
         }
         LoadingState::Error => {
-            let error = Paragraph::new("Failed to load. Press Esc to go back.")
-                .block(Block::default().borders(Borders::ALL).title(" Detail "))
-                .style(Style::default().fg(Color::Red));
+            let error = Paragraph::new(format!(
+                "Failed to load. Press {} to go back.",
+                app.keymap.label(Context::Detail, Action::Back)
+            ))
+            .block(Block::default().borders(Borders::ALL).title(" Detail "))
+            .style(Style::default().fg(app.theme.error));
             f.render_widget(error, area);
-            return;
+            return None;
         }
         LoadingState::Loaded => {}
     }
 
     let detail = match &app.detail {
         Some(d) => d,
-        None => return,
+        None => return None,
     };
 
-    let chunks =
-        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).split(area);
-
-    // Left: sprite
-    let sprite_block = Block::default()
-        .borders(Borders::ALL)
-        .title(format!(" #{:03} ", detail.id));
-    let sprite_inner = sprite_block.inner(chunks[0]);
-    f.render_widget(sprite_block, chunks[0]);
-
-    if let Some(ref bytes) = app.sprite_bytes {
-        if let Some(widget) =
-            SpriteWidget::from_png_bytes(bytes, sprite_inner.width, sprite_inner.height)
-        {
-            f.render_widget(&widget, sprite_inner);
-        }
-    }
+    // Accessible mode leaves out the sprite and reads as a single column
+    let (sprite_area, info_area) = if app.accessible {
+        (None, area)
+    } else {
+        let chunks = Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(area);
+        (Some(chunks[0]), chunks[1])
+    };
+    let protocol_area = sprite_area.and_then(|area| draw_sprite(f, app, detail, area));
 
-    // Right: info
+    // Info
     let info_block = Block::default()
         .borders(Borders::ALL)
-        .title(format!(" {} ", capitalize(&detail.name)))
+        .title(format!(
+            " {}{} ",
+            capitalize(&detail.name),
+            if app.notes.is_favorite(detail.id) {
+                " ★"
+            } else {
+                ""
+            }
+        ))
         .title_style(
             Style::default()
-                .fg(Color::White)
+                .fg(app.theme.text)
                 .add_modifier(Modifier::BOLD),
         );
-    let info_inner = info_block.inner(chunks[1]);
-    f.render_widget(info_block, chunks[1]);
+    let info_inner = info_block.inner(info_area);
+    f.render_widget(info_block, info_area);
 
     let mut lines: Vec<Line> = Vec::new();
+    // Clickable spans as (line, column, width, target), placed once the text is laid out
+    let mut clickable: Vec<(usize, usize, usize, ClickTarget)> = Vec::new();
 
     // Types
     let mut type_spans = vec![Span::styled(
         "Types: ",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(app.theme.muted),
     )];
     for t in &detail.types {
         type_spans.push(Span::styled(
             format!(" {} ", t.type_info.name.to_uppercase()),
-            Style::default()
-                .fg(Color::White)
-                .bg(type_color(&t.type_info.name))
-                .add_modifier(Modifier::BOLD),
+            app.theme.type_badge(&t.type_info.name),
         ));
         type_spans.push(Span::raw(" "));
     }
     lines.push(Line::from(type_spans));
     lines.push(Line::from(""));
 
+    // Generation and forms come from species data
+    if let Some(ref species) = app.detail_species {
+        if let Some(gen) = species.generation_number() {
+            lines.push(Line::from(vec![
+                Span::styled("Generation: ", Style::default().fg(app.theme.muted)),
+                Span::styled(format!("{}", gen), Style::default().fg(app.theme.text)),
+            ]));
+        }
+        if species.varieties.len() > 1 {
+            let mut form_spans = vec![Span::styled(
+                "Forms: ",
+                Style::default().fg(app.theme.muted),
+            )];
+            for (i, v) in species.varieties.iter().enumerate() {
+                if i > 0 {
+                    form_spans.push(Span::raw(" "));
+                }
+                let label = form_label(&species.name, &v.pokemon.name);
+                if let Some(id) = v.pokemon.id() {
+                    let column = form_spans.iter().map(Span::width).sum();
+                    let width = label.chars().count() + 2 * usize::from(id == detail.id);
+                    clickable.push((lines.len(), column, width, ClickTarget::Form(id)));
+                }
+                if v.pokemon.id() == Some(detail.id) {
+                    form_spans.push(Span::styled(
+                        format!("[{}]", label),
+                        Style::default()
+                            .fg(app.theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                } else {
+                    form_spans.push(Span::styled(
+                        label,
+                        Style::default().fg(app.theme.secondary),
+                    ));
+                }
+            }
+            lines.push(Line::from(form_spans));
+        }
+        if let Some(chain) = app
+            .detail_evolution
+            .as_ref()
+            .filter(|c| !c.chain.evolves_to.is_empty())
+        {
+            let mut evolution_spans = vec![Span::styled(
+                "Evolution: ",
+                Style::default().fg(app.theme.muted),
+            )];
+            for (i, stage) in chain.stages().iter().enumerate() {
+                if i > 0 {
+                    evolution_spans.push(Span::styled(" → ", Style::default().fg(app.theme.muted)));
+                }
+                for (j, s) in stage.iter().enumerate() {
+                    if j > 0 {
+                        evolution_spans
+                            .push(Span::styled(" / ", Style::default().fg(app.theme.muted)));
+                    }
+                    let current = s.id() == Some(species.id);
+                    let label = if current {
+                        format!("[{}]", capitalize(&s.name))
+                    } else {
+                        capitalize(&s.name)
+                    };
+                    if let Some(id) = s.id() {
+                        let column = evolution_spans.iter().map(Span::width).sum();
+                        clickable.push((
+                            lines.len(),
+                            column,
+                            label.chars().count(),
+                            ClickTarget::Evolution(id),
+                        ));
+                    }
+                    let style = if current {
+                        Style::default()
+                            .fg(app.theme.accent)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(app.theme.secondary)
+                    };
+                    evolution_spans.push(Span::styled(label, style));
+                }
+            }
+            lines.push(Line::from(evolution_spans));
+        }
+        lines.push(Line::from(""));
+    }
+
     // Height/Weight
     lines.push(Line::from(vec![
-        Span::styled("Height: ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Height: ", Style::default().fg(app.theme.muted)),
         Span::styled(
             format!("{:.1}m", detail.height as f64 / 10.0),
-            Style::default().fg(Color::White),
+            Style::default().fg(app.theme.text),
         ),
         Span::raw("    "),
-        Span::styled("Weight: ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Weight: ", Style::default().fg(app.theme.muted)),
         Span::styled(
             format!("{:.1}kg", detail.weight as f64 / 10.0),
-            Style::default().fg(Color::White),
+            Style::default().fg(app.theme.text),
         ),
     ]));
     lines.push(Line::from(""));
@@ -101,55 +197,66 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     // Abilities
     let mut ability_spans = vec![Span::styled(
         "Abilities: ",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(app.theme.muted),
     )];
     for (i, a) in detail.abilities.iter().enumerate() {
         if i > 0 {
             ability_spans.push(Span::raw(", "));
         }
         let name = capitalize(&a.ability.name.replace('-', " "));
-        if a.is_hidden {
-            ability_spans.push(Span::styled(
-                format!("{} (H)", name),
-                Style::default().fg(Color::Rgb(180, 180, 180)),
-            ));
+        let mut style = if a.is_hidden {
+            Style::default().fg(app.theme.secondary)
         } else {
-            ability_spans.push(Span::styled(name, Style::default().fg(Color::White)));
+            Style::default().fg(app.theme.text)
+        };
+        if app.selected_ability == Some(i) {
+            style = style.add_modifier(Modifier::UNDERLINED);
         }
+        let label = if a.is_hidden {
+            format!("{} (H)", name)
+        } else {
+            name
+        };
+        let column = ability_spans.iter().map(Span::width).sum();
+        clickable.push((
+            lines.len(),
+            column,
+            label.chars().count(),
+            ClickTarget::Ability(i),
+        ));
+        ability_spans.push(Span::styled(label, style));
     }
     lines.push(Line::from(ability_spans));
+    if let Some(slot) = app.selected_ability.and_then(|i| detail.abilities.get(i)) {
+        let effect = app
+            .ability_effects
+            .get(&slot.ability.name)
+            .map(String::as_str)
+            .unwrap_or("Loading...");
+        lines.push(Line::from(Span::styled(
+            format!("  {}", effect),
+            Style::default().fg(app.theme.secondary),
+        )));
+    }
     lines.push(Line::from(""));
 
     // Stats
     lines.push(Line::from(Span::styled(
         "Base Stats",
         Style::default()
-            .fg(Color::Yellow)
+            .fg(app.theme.accent)
             .add_modifier(Modifier::BOLD),
     )));
     lines.push(Line::from(""));
 
     let stat_names = ["HP", "Atk", "Def", "Sp.Atk", "Sp.Def", "Speed"];
-    let stat_colors = [
-        Color::Rgb(255, 89, 89),   // HP - red
-        Color::Rgb(245, 172, 120), // Atk - orange
-        Color::Rgb(250, 224, 120), // Def - yellow
-        Color::Rgb(157, 183, 245), // SpAtk - blue
-        Color::Rgb(167, 219, 141), // SpDef - green
-        Color::Rgb(250, 146, 178), // Speed - pink
-    ];
-
     for (i, stat) in detail.stats.iter().enumerate() {
         let label = if i < stat_names.len() {
             stat_names[i]
         } else {
             &stat.stat.name
         };
-        let color = if i < stat_colors.len() {
-            stat_colors[i]
-        } else {
-            Color::White
-        };
+        let color = app.theme.stats.get(i).copied().unwrap_or(app.theme.text);
         let bar_width = (info_inner.width as u32).saturating_sub(16).min(40);
         let filled = ((stat.base_stat as f64 / 255.0) * bar_width as f64) as usize;
         let empty = bar_width as usize - filled;
@@ -159,43 +266,189 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
         lines.push(Line::from(vec![
             Span::styled(
                 format!("{:<7}", label),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(app.theme.muted),
             ),
             Span::styled(
                 format!("{:>3} ", stat.base_stat),
-                Style::default().fg(Color::White),
+                Style::default().fg(app.theme.text),
             ),
             Span::styled(bar_filled, Style::default().fg(color)),
-            Span::styled(bar_empty, Style::default().fg(Color::Rgb(60, 60, 60))),
+            Span::styled(bar_empty, Style::default().fg(app.theme.faint)),
         ]));
     }
 
     lines.push(Line::from(""));
     let total: u32 = detail.stats.iter().map(|s| s.base_stat).sum();
     lines.push(Line::from(vec![
-        Span::styled("Total:  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Total:  ", Style::default().fg(app.theme.muted)),
         Span::styled(
             format!("{}", total),
             Style::default()
-                .fg(Color::Yellow)
+                .fg(app.theme.accent)
                 .add_modifier(Modifier::BOLD),
         ),
     ]));
 
+    lines.push(Line::from(""));
+    let status = app.collection.status(detail.id);
+    lines.push(Line::from(vec![
+        Span::styled("Collection: ", Style::default().fg(app.theme.muted)),
+        badge(&app.theme, status),
+        Span::styled(
+            format!(
+                " {} in {}",
+                capitalize(status.map_or("not seen", |s| s.label())),
+                game_label(&app.collection.game)
+            ),
+            Style::default().fg(app.theme.text),
+        ),
+    ]));
+    let tags = app.notes.tags(detail.id);
+    if !tags.is_empty() {
+        let mut tag_spans = vec![Span::styled("Tags: ", Style::default().fg(app.theme.muted))];
+        for tag in tags {
+            tag_spans.push(Span::styled(
+                format!("#{} ", tag),
+                Style::default().fg(app.theme.info),
+            ));
+        }
+        lines.push(Line::from(tag_spans));
+    }
+    if let Some(notes) = app.notes.get(detail.id).filter(|n| !n.note.is_empty()) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Notes",
+            Style::default()
+                .fg(app.theme.title)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(note_lines(
+            &app.theme,
+            &notes.note,
+            info_inner.width as usize,
+        ));
+    }
+
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        "[a] Add to team  |  [Esc] Back",
-        Style::default().fg(Color::DarkGray),
+        footer(app),
+        Style::default().fg(app.theme.muted),
     )));
 
     let info = Paragraph::new(lines);
     f.render_widget(info, info_inner);
+    for (line, column, width, target) in clickable {
+        let area = Rect::new(
+            info_inner.x.saturating_add(column as u16),
+            info_inner.y.saturating_add(line as u16),
+            width as u16,
+            1,
+        );
+        app.register_click(area.intersection(info_inner), target);
+    }
+    protocol_area
+}
+
+fn draw_sprite(f: &mut Frame, app: &App, detail: &PokemonDetail, area: Rect) -> Option<Rect> {
+    let text_mode = app.graphics_protocol == GraphicsProtocol::HalfBlocks;
+    let mut sprite_title = format!(
+        " #{:03}  {} ",
+        detail
+            .species
+            .as_ref()
+            .and_then(|s| s.id())
+            .unwrap_or(detail.id),
+        app.sprite_variant.label()
+    );
+    if text_mode {
+        sprite_title.push_str(&format!("· {} ", app.sprite_mode.label()));
+    }
+    if app.sprite_animation.is_some() {
+        sprite_title.push_str(if app.animation_paused { "⏸ " } else { "▶ " });
+    }
+    let sprite_block = Block::default().borders(Borders::ALL).title(sprite_title);
+    let sprite_inner = sprite_block.inner(area);
+    f.render_widget(sprite_block, area);
+
+    let bytes = app.current_sprite_bytes()?;
+    if !text_mode {
+        // Leave these cells alone; the image is written over them after the frame
+        for y in sprite_inner.top()..sprite_inner.bottom() {
+            for x in sprite_inner.left()..sprite_inner.right() {
+                f.buffer_mut()[(x, y)].set_skip(true);
+            }
+        }
+        return Some(sprite_inner);
+    }
+    let key = SpriteKey {
+        pokemon_id: detail.id,
+        variant: app.sprite_variant,
+        frame: app.current_sprite_frame(),
+        width: sprite_inner.width,
+        height: sprite_inner.height,
+        options: RenderOptions {
+            mode: app.sprite_mode,
+            depth: app.color_depth,
+        },
+    };
+    if let Some(widget) = app.sprite_cache.get_or_render(key, bytes) {
+        f.render_widget(widget.as_ref(), sprite_inner);
+    }
+    None
+}
+
+/// Key hints from the active keymap: "[a] Add to team  |  [v/V] Sprite  |  ..."
+fn footer(app: &App) -> String {
+    // Beside the list, the detail's own keys are a screen away
+    if app.screen != Screen::PokemonDetail {
+        return format!(
+            "[{}] Open  |  [{}] Close split view",
+            app.keymap.label(Context::List, Action::Select),
+            app.keymap.label(Context::Global, Action::ToggleSplit)
+        );
+    }
+    let key = |action| app.keymap.label(Context::Detail, action);
+    [
+        (key(Action::AddToTeam), "Add to team"),
+        (
+            format!("{}/{}", key(Action::NextSprite), key(Action::PrevSprite)),
+            "Sprite",
+        ),
+        (key(Action::CycleBlocks), "Blocks"),
+        (key(Action::PauseAnimation), "Pause"),
+        (key(Action::ExportCard), "Card"),
+        (
+            format!("{}/{}", key(Action::Export), key(Action::CycleExportFormat)),
+            "Export",
+        ),
+        (
+            format!("{}/{}", key(Action::NextForm), key(Action::PrevForm)),
+            "Form",
+        ),
+        (key(Action::ToggleFavorite), "Favorite"),
+        (key(Action::EditTags), "Tags"),
+        (key(Action::EditNote), "Notes"),
+        (key(Action::CycleCollected), "Collection"),
+        (key(Action::Back), "Back"),
+    ]
+    .iter()
+    .map(|(keys, label)| format!("[{}] {}", keys, label))
+    .collect::<Vec<_>>()
+    .join("  |  ")
 }
 
-fn capitalize(s: &str) -> String {
-    let mut c = s.chars();
-    match c.next() {
-        None => String::new(),
-        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
+/// Short form label: "charizard-mega-x" under "charizard" -> "Mega X"
+fn form_label(species: &str, pokemon: &str) -> String {
+    match pokemon
+        .strip_prefix(species)
+        .map(|r| r.trim_start_matches('-'))
+    {
+        Some("") => "Base".to_string(),
+        Some(rest) => rest
+            .split('-')
+            .map(capitalize)
+            .collect::<Vec<_>>()
+            .join(" "),
+        None => capitalize(&pokemon.replace('-', " ")),
     }
 }
@@ -1,11 +1,34 @@
 use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph};
 use ratatui::Frame;
 
-use crate::app::{App, LoadingState};
-use crate::ui::type_color;
+use crate::app::{App, ClickTarget, LoadingState, Modal, SortKey};
+use crate::keymap::{Action, Context};
+use crate::models::pokemon::PokemonSummary;
+use crate::models::type_data::ALL_TYPES;
+use crate::ui::collection::badge;
+use crate::ui::{centered_rect, draw_thumbnail, highlighted_name, thumbnail_width};
+
+/// Minimum list width at which the base stat columns are shown
+const STATS_MIN_WIDTH: u16 = 100;
+const NAME_WIDTH: usize = 12;
+/// Collection badge and favorite star before the dex number
+const MARK_WIDTH: usize = 2;
+/// Room for two type badges
+const TYPES_WIDTH: usize = 22;
+const STAT_COLUMNS: [SortKey; 9] = [
+    SortKey::Hp,
+    SortKey::Attack,
+    SortKey::Defense,
+    SortKey::SpAttack,
+    SortKey::SpDefense,
+    SortKey::Speed,
+    SortKey::Total,
+    SortKey::Height,
+    SortKey::Weight,
+];
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::vertical([Constraint::Length(4), Constraint::Min(0)]).split(area);
@@ -16,27 +39,74 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
         None => "All Gens".to_string(),
     };
 
+    let key = |action| app.keymap.label(Context::List, action);
     let search_text = if app.search_mode {
         format!("🔍 Search: {}▌", app.search_query)
     } else if !app.search_query.is_empty() {
-        format!("🔍 Filter: {} (press / to edit)", app.search_query)
+        format!(
+            "🔍 Filter: {} (press {} to edit)",
+            app.search_query,
+            key(Action::Search)
+        )
     } else {
-        "Press / to search  |  ↑↓/jk navigate  |  Enter select".to_string()
+        format!(
+            "Press {} to search  |  {}{} navigate  |  {} select",
+            key(Action::Search),
+            key(Action::Up),
+            key(Action::Down),
+            key(Action::Select)
+        )
     };
 
-    let filter_line = format!(
-        "Generation: {} (G to cycle, 1-9 to select, 0 to clear)",
-        gen_filter_text
+    let type_filter_text = if app.type_filter.is_empty() {
+        "Any".to_string()
+    } else {
+        let names: Vec<String> = app.type_filter.iter().map(|t| t.to_uppercase()).collect();
+        format!("{}, {}", names.join("/"), app.type_match.label())
+    };
+    let mut filter_line = format!(
+        "Generation: {} ({} cycle, {}-{}, {} clear)  |  Type: {} ({} pick, {} clear)  |  Forms: {} ({})  |  {} rows",
+        gen_filter_text,
+        key(Action::NextGeneration),
+        key(Action::Generation(1)),
+        key(Action::Generation(9)),
+        key(Action::AllGenerations),
+        type_filter_text,
+        key(Action::TypeFilter),
+        key(Action::ClearTypeFilter),
+        if app.show_forms { "shown" } else { "hidden" },
+        key(Action::ToggleForms),
+        key(Action::ToggleRowSize)
     );
+    if app.favorites_only {
+        filter_line.push_str(&format!(
+            "  |  Favorites only ({})",
+            key(Action::FavoritesOnly)
+        ));
+    }
+    if !app.tag_filter.is_empty() {
+        filter_line.push_str(&format!(
+            "  |  Tags: {} ({})",
+            app.tag_filter.join(", "),
+            key(Action::TagFilter)
+        ));
+    }
+    if app.summaries_loading == LoadingState::Loading {
+        filter_line.push_str(&format!(
+            "  [types loading {}/{}]",
+            app.types_known(),
+            app.pokemon_list.len()
+        ));
+    }
     let full_text = format!("{}\n{}", search_text, filter_line);
 
     let search_block = Block::default().borders(Borders::ALL).title(" Search ");
     let search = Paragraph::new(full_text)
         .block(search_block)
         .style(if app.search_mode {
-            Style::default().fg(Color::Yellow)
+            Style::default().fg(app.theme.accent)
         } else {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(app.theme.muted)
         });
     f.render_widget(search, chunks[0]);
 
@@ -45,18 +115,42 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
         LoadingState::Loading | LoadingState::Idle => {
             let loading = Paragraph::new("Loading Pokémon list...")
                 .block(Block::default().borders(Borders::ALL).title(" Pokédex "))
-                .style(Style::default().fg(Color::Yellow));
+                .style(Style::default().fg(app.theme.accent));
             f.render_widget(loading, chunks[1]);
         }
         LoadingState::Error => {
             let error = Paragraph::new("Failed to load. Press any key.")
                 .block(Block::default().borders(Borders::ALL).title(" Pokédex "))
-                .style(Style::default().fg(Color::Red));
+                .style(Style::default().fg(app.theme.error));
             f.render_widget(error, chunks[1]);
         }
         LoadingState::Loaded => {
             let filtered = app.filtered_list();
-            let visible_height = chunks[1].height.saturating_sub(2) as usize;
+            let wide = chunks[1].width >= STATS_MIN_WIDTH;
+            let thumb_width = thumbnail_width(app);
+            let row_height = app.row_height();
+
+            let direction = if app.sort_descending { "▼" } else { "▲" };
+            let title = format!(
+                " Pokédex ({}/{})  sort: {} {}  s/S=sort r=reverse ",
+                filtered.len(),
+                app.pokemon_list.len(),
+                app.sort_key.label(),
+                direction
+            );
+            let block = Block::default().borders(Borders::ALL).title(title);
+            let inner = block.inner(chunks[1]);
+            f.render_widget(block, chunks[1]);
+
+            let list_area = if wide {
+                let parts =
+                    Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).split(inner);
+                f.render_widget(Paragraph::new(header_line(app, thumb_width)), parts[0]);
+                parts[1]
+            } else {
+                inner
+            };
+            let visible_height = (list_area.height as usize / row_height).max(1);
 
             // Calculate scroll offset to keep selection visible
             let scroll_offset = if app.list_state >= visible_height {
@@ -72,52 +166,238 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
                 .take(visible_height)
                 .map(|(i, p)| {
                     let selected = i == app.list_state;
+                    let star = if app.notes.is_favorite(p.id) {
+                        "★"
+                    } else {
+                        " "
+                    };
                     let mut spans = vec![
-                        Span::styled(
-                            format!("#{:03} ", p.id),
-                            Style::default().fg(Color::DarkGray),
-                        ),
-                        Span::styled(
-                            format!("{:<12} ", capitalize(&p.name)),
-                            if selected {
-                                Style::default()
-                                    .fg(Color::White)
-                                    .add_modifier(Modifier::BOLD)
-                            } else {
-                                Style::default().fg(Color::White)
-                            },
-                        ),
+                        Span::raw(" ".repeat(thumb_width as usize)),
+                        badge(&app.theme, app.collection.status(p.id)),
+                        Span::styled(star, Style::default().fg(app.theme.accent)),
+                        Span::styled(dex_label(p), Style::default().fg(app.theme.muted)),
                     ];
+                    let name_style = if selected {
+                        Style::default()
+                            .fg(app.theme.text)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(app.theme.text)
+                    };
+                    spans.extend(highlighted_name(
+                        &app.theme,
+                        &p.name,
+                        &app.search_query,
+                        NAME_WIDTH,
+                        name_style,
+                    ));
+                    spans.push(Span::raw(" "));
+                    let mut types_width = 0;
                     for t in &p.types {
                         spans.push(Span::styled(
                             format!(" {} ", t.to_uppercase()),
-                            Style::default()
-                                .fg(Color::White)
-                                .bg(type_color(t))
-                                .add_modifier(Modifier::BOLD),
+                            app.theme.type_badge(t),
                         ));
                         spans.push(Span::raw(" "));
+                        types_width += t.chars().count() + 3;
+                    }
+                    if wide {
+                        spans.push(Span::raw(
+                            " ".repeat(TYPES_WIDTH.saturating_sub(types_width)),
+                        ));
+                        for key in STAT_COLUMNS {
+                            let style = if key == app.sort_key {
+                                Style::default().fg(app.theme.accent)
+                            } else {
+                                Style::default().fg(app.theme.secondary)
+                            };
+                            spans.push(Span::styled(
+                                format!(
+                                    "{:>width$}",
+                                    column_value(p, key),
+                                    width = column_width(key)
+                                ),
+                                style,
+                            ));
+                        }
+                    }
+                    let mut text = Text::from(Line::from(spans));
+                    for _ in 1..row_height {
+                        text.push_line(Line::default());
                     }
-                    let line = Line::from(spans);
                     if selected {
-                        ListItem::new(line).style(Style::default().bg(Color::Rgb(40, 40, 60)))
+                        ListItem::new(text).style(app.theme.selected())
                     } else {
-                        ListItem::new(line)
+                        ListItem::new(text)
                     }
                 })
                 .collect();
 
-            let title = format!(" Pokédex ({}/{}) ", filtered.len(), app.pokemon_list.len());
-            let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
-            f.render_widget(list, chunks[1]);
+            f.render_widget(List::new(items), list_area);
+
+            // Thumbnails go over the blank space at the start of each visible row
+            for (row, p) in filtered
+                .iter()
+                .skip(scroll_offset)
+                .take(visible_height)
+                .enumerate()
+            {
+                let y = list_area.y + (row * row_height) as u16;
+                let area = Rect::new(list_area.x, y, thumb_width, row_height as u16);
+                draw_thumbnail(f, app, p.id, area);
+                let row_area = Rect::new(list_area.x, y, list_area.width, row_height as u16);
+                app.register_click(
+                    row_area.intersection(list_area),
+                    ClickTarget::ListRow(scroll_offset + row),
+                );
+            }
         }
     }
+
+    if app.modal == Some(Modal::TypeFilter) {
+        draw_type_filter(f, app);
+    }
 }
 
-fn capitalize(s: &str) -> String {
-    let mut c = s.chars();
-    match c.next() {
-        None => String::new(),
-        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
+fn draw_type_filter(f: &mut Frame, app: &App) {
+    let area = centered_rect(40, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Type Filter ({}) ", app.type_match.label()))
+        .border_style(Style::default().fg(app.theme.accent));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let mut lines: Vec<Line> = ALL_TYPES
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+            let checked = app.type_filter.iter().any(|s| s == t);
+            let line = Line::from(vec![
+                Span::styled(
+                    if checked { "[x] " } else { "[ ] " },
+                    Style::default().fg(if checked {
+                        app.theme.accent
+                    } else {
+                        app.theme.muted
+                    }),
+                ),
+                Span::styled(format!(" {} ", t.to_uppercase()), app.theme.type_badge(t)),
+            ]);
+            if i == app.modal_selected {
+                line.style(app.theme.selected())
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    lines.push(Line::from(""));
+    let key = |action| app.keymap.label(Context::Modal, action);
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{} toggle  {} either/exact  {} clear  {} close",
+            key(Action::ToggleType),
+            key(Action::MatchMode),
+            key(Action::ClearTypes),
+            key(Action::Back)
+        ),
+        Style::default().fg(app.theme.muted),
+    )));
+    if app.summaries_loading == LoadingState::Loading {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "Types still loading ({}/{}), results may be incomplete",
+                app.types_known(),
+                app.pokemon_list.len()
+            ),
+            Style::default().fg(app.theme.accent),
+        )));
     }
+
+    // Keep the cursor visible on short terminals
+    let visible = inner.height as usize;
+    let scroll = app.modal_selected.saturating_sub(visible.saturating_sub(1));
+    for i in scroll..ALL_TYPES.len().min(scroll + visible) {
+        let row = Rect::new(inner.x, inner.y + (i - scroll) as u16, inner.width, 1);
+        app.register_click(row, ClickTarget::ModalRow(i));
+    }
+    f.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), inner);
+}
+
+/// Column headers for the wide layout, with the active sort column marked
+fn header_line(app: &App, thumb_width: u16) -> Line<'static> {
+    let arrow = if app.sort_descending { "▼" } else { "▲" };
+    let header_style = |key: SortKey| {
+        if key == app.sort_key {
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.muted)
+        }
+    };
+    let label = |key: SortKey| {
+        if key == app.sort_key {
+            format!("{}{}", key.label(), arrow)
+        } else {
+            key.label().to_string()
+        }
+    };
+
+    let mut spans = vec![
+        Span::raw(" ".repeat(thumb_width as usize + MARK_WIDTH)),
+        Span::styled(
+            format!("{:<5}", label(SortKey::Id)),
+            header_style(SortKey::Id),
+        ),
+        Span::styled(
+            format!("{:<width$} ", label(SortKey::Name), width = NAME_WIDTH),
+            header_style(SortKey::Name),
+        ),
+        Span::styled(
+            format!("{:<width$}", label(SortKey::Type), width = TYPES_WIDTH),
+            header_style(SortKey::Type),
+        ),
+    ];
+    for key in STAT_COLUMNS {
+        spans.push(Span::styled(
+            format!("{:>width$}", label(key), width = column_width(key)),
+            header_style(key),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Dex number column; forms show their species' number with a marker
+pub fn dex_label(p: &PokemonSummary) -> String {
+    if p.is_form() {
+        format!("↳{:03} ", p.species_number())
+    } else {
+        format!("#{:03} ", p.id)
+    }
+}
+
+fn column_width(key: SortKey) -> usize {
+    match key {
+        SortKey::Total => 7,
+        SortKey::Height => 8,
+        SortKey::Weight => 9,
+        _ => 5,
+    }
+}
+
+fn column_value(p: &PokemonSummary, key: SortKey) -> String {
+    let value = match key {
+        SortKey::Total => p.base_stat_total().map(|t| t.to_string()),
+        SortKey::Height => p.height.map(|h| format!("{:.1}m", h as f64 / 10.0)),
+        SortKey::Weight => p.weight.map(|w| format!("{:.1}kg", w as f64 / 10.0)),
+        _ => key
+            .stat_index()
+            .and_then(|i| p.stats.get(i))
+            .map(|v| v.to_string()),
+    };
+    value.unwrap_or_else(|| "-".to_string())
 }
@@ -0,0 +1,343 @@
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::app::{App, LoadingState, Modal, Screen};
+use crate::models::collection::game_label;
+use crate::models::type_data::ALL_TYPES;
+use crate::ui::capitalize;
+use crate::ui::type_chart::{effectiveness, multiplier_label, TYPE_ORDER};
+
+/// The accessible mode status line: the last notice plus what's selected,
+/// rewritten on every change so screen readers pick it up
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let line = Paragraph::new(announcement(app)).style(Style::default().fg(app.theme.text));
+    f.render_widget(line, area);
+}
+
+pub fn announcement(app: &App) -> String {
+    let state = if app.show_help {
+        "Help. Movement keys scroll, any other key closes".to_string()
+    } else if let Some(modal) = app.modal {
+        describe_modal(app, modal)
+    } else {
+        describe_screen(app)
+    };
+    match &app.status_message {
+        Some(status) => format!("{}. {}", status, state),
+        None => state,
+    }
+}
+
+fn describe_modal(app: &App, modal: Modal) -> String {
+    let i = app.modal_selected;
+    let item = match modal {
+        Modal::TypeFilter => ALL_TYPES.get(i).map(|t| {
+            let picked = if app.type_filter.iter().any(|f| f == t) {
+                "selected"
+            } else {
+                "not selected"
+            };
+            format!(
+                "{} {}, {} of {}",
+                capitalize(t),
+                picked,
+                i + 1,
+                ALL_TYPES.len()
+            )
+        }),
+        Modal::PokemonPicker => {
+            let list = app.modal_filtered_list();
+            list.get(i).map(|p| {
+                format!(
+                    "{} #{}, {}, {} of {}",
+                    capitalize(&p.name.replace('-', " ")),
+                    p.id,
+                    type_names(&p.types),
+                    i + 1,
+                    list.len()
+                )
+            })
+        }
+        Modal::MovePicker if app.moves_loading != LoadingState::Loaded => {
+            Some("loading moves".to_string())
+        }
+        Modal::MovePicker => app.available_moves.get(i).map(|mv| {
+            let power = mv
+                .power
+                .map(|p| format!("power {}", p))
+                .unwrap_or_else(|| "no power".to_string());
+            format!(
+                "{}, {}, {}, {} of {}",
+                capitalize(&mv.name.replace('-', " ")),
+                capitalize(&mv.move_type.name),
+                power,
+                i + 1,
+                app.available_moves.len()
+            )
+        }),
+        Modal::CommandPalette => {
+            let results = app.palette_results();
+            results
+                .get(i)
+                .map(|e| format!("{}, {} of {}", e.label, i + 1, results.len()))
+        }
+        Modal::TagFilter | Modal::EditTags => {
+            let rows = app.tag_rows(modal);
+            rows.get(i).map(|(tag, count, checked)| {
+                let state = match (count, checked) {
+                    (0, _) => "new tag",
+                    (_, true) => "selected",
+                    (_, false) => "not selected",
+                };
+                format!("{} {}, {} of {}", tag, state, i + 1, rows.len())
+            })
+        }
+        Modal::MarkRange => Some(if app.modal_search.is_empty() {
+            "type a range and a status, like 1-151 caught".to_string()
+        } else {
+            app.modal_search.clone()
+        }),
+        Modal::Progress => {
+            let rows: Vec<String> = app
+                .collection_progress()
+                .iter()
+                .filter(|p| p.total > 0)
+                .map(|p| {
+                    format!(
+                        "Generation {} {} of {} caught, {} seen, {} shiny",
+                        p.generation, p.caught, p.total, p.seen, p.shiny
+                    )
+                })
+                .collect();
+            (!rows.is_empty())
+                .then(|| format!("{}. {}", game_label(&app.collection.game), rows.join(". ")))
+        }
+        Modal::Recent => {
+            let rows = app.recent_rows();
+            rows.get(i).map(|(id, name)| {
+                format!(
+                    "{} #{}, {} of {}",
+                    capitalize(&name.replace('-', " ")),
+                    id,
+                    i + 1,
+                    rows.len()
+                )
+            })
+        }
+        Modal::EditNote => Some(if app.modal_search.is_empty() {
+            "empty".to_string()
+        } else {
+            app.modal_search.lines().collect::<Vec<_>>().join(" / ")
+        }),
+    };
+    format!(
+        "{}: {}",
+        modal.label(),
+        item.unwrap_or_else(|| "nothing matches".to_string())
+    )
+}
+
+fn describe_screen(app: &App) -> String {
+    match app.screen {
+        Screen::PokemonList => {
+            if app.list_loading != LoadingState::Loaded {
+                return "Pokédex: loading".to_string();
+            }
+            let list = app.filtered_list();
+            let mut text = match list.get(app.list_state) {
+                Some(p) => format!(
+                    "Pokédex: {} #{}, {}{}, {} of {}",
+                    capitalize(&p.name.replace('-', " ")),
+                    p.id,
+                    type_names(&p.types),
+                    app.collection
+                        .status(p.id)
+                        .map(|s| format!(", {}", s.label()))
+                        .unwrap_or_default(),
+                    app.list_state + 1,
+                    list.len()
+                ),
+                None => "Pokédex: no Pokémon match".to_string(),
+            };
+            if !app.search_query.is_empty() {
+                text.push_str(&format!(". Search: {}", app.search_query));
+            }
+            if let Some(gen) = app.generation_filter {
+                text.push_str(&format!(". Generation {}", gen));
+            }
+            if !app.type_filter.is_empty() {
+                text.push_str(&format!(". Type: {}", type_names(&app.type_filter)));
+            }
+            if app.favorites_only {
+                text.push_str(". Favorites only");
+            }
+            if !app.tag_filter.is_empty() {
+                text.push_str(&format!(". Tags: {}", app.tag_filter.join(", ")));
+            }
+            text
+        }
+        Screen::PokemonDetail => {
+            let Some(detail) = app
+                .detail
+                .as_ref()
+                .filter(|_| app.detail_loading == LoadingState::Loaded)
+            else {
+                return "Detail: loading".to_string();
+            };
+            let types: Vec<String> = detail
+                .types
+                .iter()
+                .map(|t| t.type_info.name.clone())
+                .collect();
+            let stats: Vec<String> = ["HP", "Attack", "Defense", "Sp. Atk", "Sp. Def", "Speed"]
+                .iter()
+                .zip(&detail.stats)
+                .map(|(name, s)| format!("{} {}", name, s.base_stat))
+                .collect();
+            let total: u32 = detail.stats.iter().map(|s| s.base_stat).sum();
+            let mut text = format!(
+                "Detail: {} #{}, {}. {}, total {}",
+                capitalize(&detail.name.replace('-', " ")),
+                detail.id,
+                type_names(&types),
+                stats.join(", "),
+                total
+            );
+            if let Some(status) = app.collection.status(detail.id) {
+                text.push_str(&format!(
+                    ". {} in {}",
+                    capitalize(status.label()),
+                    game_label(&app.collection.game)
+                ));
+            }
+            if app.notes.is_favorite(detail.id) {
+                text.push_str(". Favorite");
+            }
+            let tags = app.notes.tags(detail.id);
+            if !tags.is_empty() {
+                text.push_str(&format!(". Tags: {}", tags.join(", ")));
+            }
+            text
+        }
+        Screen::TypeChart => {
+            if app.type_chart_loading != LoadingState::Loaded {
+                return "Type Chart: loading".to_string();
+            }
+            // The attacking type in the top visible row
+            let row = app.type_chart_scroll_y.min(TYPE_ORDER.len() - 1);
+            let matrix = effectiveness(&app.type_infos);
+            let mut text = format!("Type Chart: {} attacking", capitalize(TYPE_ORDER[row]));
+            // The matrix only holds these exact values
+            for value in [2.0, 0.5, 0.0] {
+                let targets: Vec<&str> = TYPE_ORDER
+                    .iter()
+                    .zip(&matrix[row])
+                    .filter(|(_, &v)| v == value)
+                    .map(|(t, _)| *t)
+                    .collect();
+                if !targets.is_empty() {
+                    text.push_str(&format!(
+                        ". {} against {}",
+                        multiplier_label(value, true),
+                        type_names(&targets)
+                    ));
+                }
+            }
+            text
+        }
+        Screen::TeamBuilder => {
+            let team = app.current_team();
+            let slot = app.team_slot_selected;
+            let member = match team.members.get(slot) {
+                Some(m) => {
+                    let mut text =
+                        format!("{}, {}", capitalize(&m.pokemon_name), type_names(&m.types));
+                    if !m.moves.is_empty() {
+                        let moves: Vec<String> = m
+                            .moves
+                            .iter()
+                            .map(|mv| capitalize(&mv.name.replace('-', " ")))
+                            .collect();
+                        text.push_str(&format!(", moves {}", moves.join(", ")));
+                    }
+                    text
+                }
+                None => "empty".to_string(),
+            };
+            format!(
+                "Team Builder: {}, slot {} of 6: {}",
+                team.name,
+                slot + 1,
+                member
+            )
+        }
+    }
+}
+
+/// "Fire and Flying", "Grass, Ice, Bug and Steel"
+fn type_names<S: AsRef<str>>(types: &[S]) -> String {
+    let names: Vec<String> = types.iter().map(|t| capitalize(t.as_ref())).collect();
+    match names.split_last() {
+        None => "unknown type".to_string(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::pokemon::PokemonSummary;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_announcement_describes_selection() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.list_loading = LoadingState::Loaded;
+        app.pokemon_list = vec![PokemonSummary {
+            id: 6,
+            name: "charizard".to_string(),
+            types: vec!["fire".to_string(), "flying".to_string()],
+            ..Default::default()
+        }];
+        app.status_message = Some("Sorted by id".to_string());
+        assert_eq!(
+            announcement(&app),
+            "Sorted by id. Pokédex: Charizard #6, Fire and Flying, 1 of 1"
+        );
+
+        app.status_message = None;
+        app.modal = Some(Modal::TypeFilter);
+        app.modal_selected = 1;
+        app.type_filter = vec!["fire".to_string()];
+        assert_eq!(announcement(&app), "Type filter: Fire selected, 2 of 18");
+    }
+
+    #[test]
+    fn test_announcement_reads_type_chart_row() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(tx);
+        app.screen = Screen::TypeChart;
+        app.type_chart_loading = LoadingState::Loaded;
+        app.type_infos = vec![serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "name": "normal",
+            "damage_relations": {
+                "double_damage_to": [],
+                "half_damage_to": [{"name": "rock", "url": ""}, {"name": "steel", "url": ""}],
+                "no_damage_to": [{"name": "ghost", "url": ""}],
+                "double_damage_from": [],
+                "half_damage_from": [],
+                "no_damage_from": []
+            }
+        }))
+        .unwrap()];
+        assert_eq!(
+            announcement(&app),
+            "Type Chart: Normal attacking. 1/2 against Rock and Steel. 0 against Ghost"
+        );
+    }
+}
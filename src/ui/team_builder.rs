@@ -1,16 +1,22 @@
 use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
-use ratatui::text::{Line, Span};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph};
 use ratatui::Frame;
 
-use crate::app::{App, LoadingState, Modal};
+use crate::app::{App, ClickTarget, LoadingState, Modal};
+use crate::keymap::{Action, Context};
 use crate::models::pokemon::PokemonSummary;
-use crate::ui::{centered_rect, type_color};
+use crate::ui::pokemon_list::dex_label;
+use crate::ui::{capitalize, centered_rect, draw_thumbnail, highlighted_name, thumbnail_width};
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect) {
-    let chunks =
-        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).split(area);
+    // One column in accessible mode, so text reads top to bottom
+    let chunks = if app.accessible {
+        Layout::vertical([Constraint::Length(8), Constraint::Min(0)]).split(area)
+    } else {
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).split(area)
+    };
 
     draw_team_slots(f, app, chunks[0]);
     draw_coverage(f, app, chunks[1]);
@@ -20,45 +26,66 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
         match modal {
             Modal::PokemonPicker => draw_pokemon_picker(f, app),
             Modal::MovePicker => draw_move_picker(f, app),
+            // Drawn by the Pokédex list and by ui::draw
+            Modal::TypeFilter
+            | Modal::CommandPalette
+            | Modal::TagFilter
+            | Modal::EditTags
+            | Modal::EditNote
+            | Modal::MarkRange
+            | Modal::Progress
+            | Modal::Recent => {}
         }
     }
 }
 
 fn draw_team_slots(f: &mut Frame, app: &App, area: Rect) {
     let team = app.current_team();
+    let key = |action| app.keymap.label(Context::Team, action);
     let title = format!(
-        " {} ({}/{})  ←→ switch  n=new  d=delete ",
+        " {} ({}/{})  {}{} switch  {}=new  {}=delete ",
         team.name,
         team.members.len(),
-        6
+        6,
+        key(Action::Left),
+        key(Action::Right),
+        key(Action::NewTeam),
+        key(Action::RemoveMember)
     );
     let block = Block::default().borders(Borders::ALL).title(title);
     let inner = block.inner(area);
     f.render_widget(block, area);
 
+    let thumb_width = thumbnail_width(app);
+    // Fall back to single-line slots when six tall ones don't fit
+    let row_height = if inner.height as usize >= 6 * app.row_height() {
+        app.row_height()
+    } else {
+        1
+    };
     let mut items: Vec<ListItem> = Vec::new();
     for i in 0..6 {
         let selected = i == app.team_slot_selected;
         let line = if i < team.members.len() {
             let member = &team.members[i];
             let mut spans = vec![
-                Span::styled(format!("{}. ", i + 1), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("{}. ", i + 1), Style::default().fg(app.theme.muted)),
+                Span::raw(" ".repeat(thumb_width as usize + 1)),
                 Span::styled(
                     format!("{:<12}", capitalize(&member.pokemon_name)),
-                    Style::default().fg(Color::White).add_modifier(if selected {
-                        Modifier::BOLD
-                    } else {
-                        Modifier::empty()
-                    }),
+                    Style::default()
+                        .fg(app.theme.text)
+                        .add_modifier(if selected {
+                            Modifier::BOLD
+                        } else {
+                            Modifier::empty()
+                        }),
                 ),
             ];
             for t in &member.types {
                 spans.push(Span::styled(
                     format!(" {} ", t.to_uppercase()),
-                    Style::default()
-                        .fg(Color::White)
-                        .bg(type_color(t))
-                        .add_modifier(Modifier::BOLD),
+                    app.theme.type_badge(t),
                 ));
                 spans.push(Span::raw(" "));
             }
@@ -66,32 +93,57 @@ fn draw_team_slots(f: &mut Frame, app: &App, area: Rect) {
                 spans.push(Span::raw("  "));
                 for (j, mv) in member.moves.iter().enumerate() {
                     if j > 0 {
-                        spans.push(Span::styled(", ", Style::default().fg(Color::DarkGray)));
+                        spans.push(Span::styled(", ", Style::default().fg(app.theme.muted)));
                     }
                     spans.push(Span::styled(
                         capitalize(&mv.name.replace('-', " ")),
-                        Style::default().fg(type_color(&mv.move_type)),
+                        Style::default().fg(app.theme.type_color(&mv.move_type)),
                     ));
                 }
             }
             Line::from(spans)
         } else {
             Line::from(vec![
-                Span::styled(format!("{}. ", i + 1), Style::default().fg(Color::DarkGray)),
-                Span::styled("(empty)", Style::default().fg(Color::Rgb(80, 80, 80))),
+                Span::styled(format!("{}. ", i + 1), Style::default().fg(app.theme.muted)),
+                Span::raw(" ".repeat(thumb_width as usize + 1)),
+                Span::styled("(empty)", Style::default().fg(app.theme.faint)),
             ])
         };
 
+        let mut text = Text::from(line);
+        for _ in 1..row_height {
+            text.push_line(Line::default());
+        }
         let item = if selected {
-            ListItem::new(line).style(Style::default().bg(Color::Rgb(40, 40, 60)))
+            ListItem::new(text).style(app.theme.selected())
         } else {
-            ListItem::new(line)
+            ListItem::new(text)
         };
         items.push(item);
     }
 
     let list = List::new(items);
     f.render_widget(list, inner);
+
+    for i in 0..6 {
+        let slot = Rect::new(
+            inner.x,
+            inner.y + (i * row_height) as u16,
+            inner.width,
+            row_height as u16,
+        );
+        app.register_click(slot.intersection(inner), ClickTarget::TeamSlot(i));
+    }
+
+    for (i, member) in team.members.iter().enumerate().take(6) {
+        let y = inner.y + (i * row_height) as u16;
+        if y >= inner.bottom() {
+            break;
+        }
+        // After the "1. " slot number
+        let area = Rect::new(inner.x + 3, y, thumb_width, row_height as u16);
+        draw_thumbnail(f, app, member.pokemon_id, area);
+    }
 }
 
 fn draw_coverage(f: &mut Frame, app: &App, area: Rect) {
@@ -104,7 +156,7 @@ fn draw_coverage(f: &mut Frame, app: &App, area: Rect) {
     let team = app.current_team();
     if team.members.is_empty() {
         let text = Paragraph::new("Add Pokémon to see type coverage")
-            .style(Style::default().fg(Color::DarkGray));
+            .style(Style::default().fg(app.theme.muted));
         f.render_widget(text, inner);
         return;
     }
@@ -127,7 +179,7 @@ fn draw_coverage(f: &mut Frame, app: &App, area: Rect) {
     lines.push(Line::from(Span::styled(
         "Team Types",
         Style::default()
-            .fg(Color::Yellow)
+            .fg(app.theme.accent)
             .add_modifier(Modifier::BOLD),
     )));
 
@@ -139,13 +191,7 @@ fn draw_coverage(f: &mut Frame, app: &App, area: Rect) {
         .iter()
         .flat_map(|t| {
             vec![
-                Span::styled(
-                    format!(" {} ", t.to_uppercase()),
-                    Style::default()
-                        .fg(Color::White)
-                        .bg(type_color(t))
-                        .add_modifier(Modifier::BOLD),
-                ),
+                Span::styled(format!(" {} ", t.to_uppercase()), app.theme.type_badge(t)),
                 Span::raw(" "),
             ]
         })
@@ -158,7 +204,7 @@ fn draw_coverage(f: &mut Frame, app: &App, area: Rect) {
         lines.push(Line::from(Span::styled(
             "Move Coverage",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(app.theme.accent)
                 .add_modifier(Modifier::BOLD),
         )));
         let mut unique_move_types: Vec<&str> = move_types;
@@ -168,13 +214,7 @@ fn draw_coverage(f: &mut Frame, app: &App, area: Rect) {
             .iter()
             .flat_map(|t| {
                 vec![
-                    Span::styled(
-                        format!(" {} ", t.to_uppercase()),
-                        Style::default()
-                            .fg(Color::White)
-                            .bg(type_color(t))
-                            .add_modifier(Modifier::BOLD),
-                    ),
+                    Span::styled(format!(" {} ", t.to_uppercase()), app.theme.type_badge(t)),
                     Span::raw(" "),
                 ]
             })
@@ -208,16 +248,15 @@ fn draw_coverage(f: &mut Frame, app: &App, area: Rect) {
         if !uncovered.is_empty() {
             lines.push(Line::from(Span::styled(
                 "Not Super Effective Against",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(app.theme.error)
+                    .add_modifier(Modifier::BOLD),
             )));
             let uncov_spans: Vec<Span> = uncovered
                 .iter()
                 .flat_map(|t| {
                     vec![
-                        Span::styled(
-                            format!(" {} ", t.to_uppercase()),
-                            Style::default().fg(Color::White).bg(type_color(t)),
-                        ),
+                        Span::styled(format!(" {} ", t.to_uppercase()), app.theme.type_badge(t)),
                         Span::raw(" "),
                     ]
                 })
@@ -234,20 +273,32 @@ fn draw_pokemon_picker(f: &mut Frame, app: &App) {
     let area = centered_rect(60, 70, f.area());
     f.render_widget(Clear, area);
 
+    // The list's generation filter applies here too, so say so
+    let picking = match app.generation_filter {
+        Some(gen) => format!("Pick Gen {} Pokémon", gen),
+        None => "Pick Pokémon".to_string(),
+    };
     let title = if app.search_mode {
-        format!(" Pick Pokémon (🔍 {}▌) ", app.modal_search)
+        format!(" {} (🔍 {}▌) ", picking, app.modal_search)
     } else {
-        " Pick Pokémon (/ to search, Enter to select) ".to_string()
+        format!(
+            " {} ({} to search, {} to select) ",
+            picking,
+            app.keymap.label(Context::Modal, Action::Search),
+            app.keymap.label(Context::Modal, Action::Select)
+        )
     };
     let block = Block::default()
         .borders(Borders::ALL)
         .title(title)
-        .border_style(Style::default().fg(Color::Yellow));
+        .border_style(Style::default().fg(app.theme.accent));
     let inner = block.inner(area);
     f.render_widget(block, area);
 
     let filtered = app.modal_filtered_list();
-    let visible_height = inner.height as usize;
+    let thumb_width = thumbnail_width(app);
+    let row_height = app.row_height();
+    let visible_height = (inner.height as usize / row_height).max(1);
 
     let scroll_offset = if app.modal_selected >= visible_height {
         app.modal_selected - visible_height + 1
@@ -264,43 +315,61 @@ fn draw_pokemon_picker(f: &mut Frame, app: &App) {
             let p: &PokemonSummary = p;
             let selected = i == app.modal_selected;
             let mut spans = vec![
-                Span::styled(
-                    format!("#{:03} ", p.id),
-                    Style::default().fg(Color::DarkGray),
-                ),
-                Span::styled(
-                    format!("{:<12}", capitalize(&p.name)),
-                    if selected {
-                        Style::default()
-                            .fg(Color::White)
-                            .add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().fg(Color::White)
-                    },
-                ),
+                Span::raw(" ".repeat(thumb_width as usize + 1)),
+                Span::styled(dex_label(p), Style::default().fg(app.theme.muted)),
             ];
+            let name_style = if selected {
+                Style::default()
+                    .fg(app.theme.text)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            spans.extend(highlighted_name(
+                &app.theme,
+                &p.name,
+                &app.modal_search,
+                12,
+                name_style,
+            ));
             for t in &p.types {
                 let type_name: &str = t.as_str();
                 spans.push(Span::styled(
                     format!(" {} ", type_name.to_uppercase()),
-                    Style::default()
-                        .fg(Color::White)
-                        .bg(type_color(type_name))
-                        .add_modifier(Modifier::BOLD),
+                    app.theme.type_badge(type_name),
                 ));
                 spans.push(Span::raw(" "));
             }
-            let line = Line::from(spans);
+            let mut text = Text::from(Line::from(spans));
+            for _ in 1..row_height {
+                text.push_line(Line::default());
+            }
             if selected {
-                ListItem::new(line).style(Style::default().bg(Color::Rgb(40, 40, 60)))
+                ListItem::new(text).style(app.theme.selected())
             } else {
-                ListItem::new(line)
+                ListItem::new(text)
             }
         })
         .collect();
 
     let list = List::new(items);
     f.render_widget(list, inner);
+
+    for (row, p) in filtered
+        .iter()
+        .skip(scroll_offset)
+        .take(visible_height)
+        .enumerate()
+    {
+        let y = inner.y + (row * row_height) as u16;
+        let area = Rect::new(inner.x, y, thumb_width, row_height as u16);
+        draw_thumbnail(f, app, p.id, area);
+        let row_area = Rect::new(inner.x, y, inner.width, row_height as u16);
+        app.register_click(
+            row_area.intersection(inner),
+            ClickTarget::ModalRow(scroll_offset + row),
+        );
+    }
 }
 
 fn draw_move_picker(f: &mut Frame, app: &App) {
@@ -309,13 +378,18 @@ fn draw_move_picker(f: &mut Frame, app: &App) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(" Pick Moves (Enter to add, Esc to close) ")
-        .border_style(Style::default().fg(Color::Cyan));
+        .title(format!(
+            " Pick Moves ({} to add, {} to close) ",
+            app.keymap.label(Context::Modal, Action::Select),
+            app.keymap.label(Context::Modal, Action::Back)
+        ))
+        .border_style(Style::default().fg(app.theme.info));
     let inner = block.inner(area);
     f.render_widget(block, area);
 
     if app.moves_loading != LoadingState::Loaded {
-        let loading = Paragraph::new("Loading moves...").style(Style::default().fg(Color::Yellow));
+        let loading =
+            Paragraph::new("Loading moves...").style(Style::default().fg(app.theme.accent));
         f.render_widget(loading, inner);
         return;
     }
@@ -349,30 +423,27 @@ fn draw_move_picker(f: &mut Frame, app: &App) {
                     format!("{:<20}", capitalize(&mv.name.replace('-', " "))),
                     if selected {
                         Style::default()
-                            .fg(Color::White)
+                            .fg(app.theme.text)
                             .add_modifier(Modifier::BOLD)
                     } else {
-                        Style::default().fg(Color::White)
+                        Style::default().fg(app.theme.text)
                     },
                 ),
                 Span::styled(
                     format!(" {} ", mv.move_type.name.to_uppercase()),
-                    Style::default()
-                        .fg(Color::White)
-                        .bg(type_color(&mv.move_type.name))
-                        .add_modifier(Modifier::BOLD),
+                    app.theme.type_badge(&mv.move_type.name),
                 ),
                 Span::styled(
                     format!("  Pow:{}", power_str),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(app.theme.muted),
                 ),
                 Span::styled(
                     format!("  {:<8}", class),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(app.theme.muted),
                 ),
             ]);
             if selected {
-                ListItem::new(line).style(Style::default().bg(Color::Rgb(40, 40, 60)))
+                ListItem::new(line).style(app.theme.selected())
             } else {
                 ListItem::new(line)
             }
@@ -381,6 +452,16 @@ fn draw_move_picker(f: &mut Frame, app: &App) {
 
     let list = List::new(items);
     f.render_widget(list, inner);
+
+    let shown = app
+        .available_moves
+        .len()
+        .saturating_sub(scroll_offset)
+        .min(visible_height);
+    for row in 0..shown {
+        let row_area = Rect::new(inner.x, inner.y + row as u16, inner.width, 1);
+        app.register_click(row_area, ClickTarget::ModalRow(scroll_offset + row));
+    }
 }
 
 /// Simplified super-effectiveness lookup for coverage display
@@ -440,11 +521,3 @@ fn is_super_effective(atk: &str, def: &str) -> bool {
             | ("fairy", "dark")
     )
 }
-
-fn capitalize(s: &str) -> String {
-    let mut c = s.chars();
-    match c.next() {
-        None => String::new(),
-        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
-    }
-}
@@ -0,0 +1,535 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+use crate::models::type_data::ALL_TYPES;
+
+/// Built-in color schemes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThemeName {
+    Default,
+    Light,
+    HighContrast,
+    Colorblind,
+    Basic,
+    NoColor,
+}
+
+impl ThemeName {
+    pub fn all() -> &'static [ThemeName] {
+        &[
+            ThemeName::Default,
+            ThemeName::Light,
+            ThemeName::HighContrast,
+            ThemeName::Colorblind,
+            ThemeName::Basic,
+            ThemeName::NoColor,
+        ]
+    }
+
+    /// Name used in the theme file
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThemeName::Default => "default",
+            ThemeName::Light => "light",
+            ThemeName::HighContrast => "high-contrast",
+            ThemeName::Colorblind => "colorblind",
+            ThemeName::Basic => "16-color",
+            ThemeName::NoColor => "no-color",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemeName::Default => "Default",
+            ThemeName::Light => "Light terminal",
+            ThemeName::HighContrast => "High contrast",
+            ThemeName::Colorblind => "Colorblind-safe",
+            ThemeName::Basic => "16 colors",
+            ThemeName::NoColor => "No color",
+        }
+    }
+
+    pub fn next(&self) -> ThemeName {
+        let all = ThemeName::all();
+        let i = all.iter().position(|t| t == self).unwrap_or(0);
+        all[(i + 1) % all.len()]
+    }
+
+    pub fn from_name(name: &str) -> Option<ThemeName> {
+        ThemeName::all()
+            .iter()
+            .copied()
+            .find(|t| t.name() == name.to_lowercase())
+    }
+}
+
+/// Colors by what they're used for, so every screen changes together
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: ThemeName,
+    /// Names, values
+    pub text: Color,
+    /// Field labels, hints, ids
+    pub muted: Color,
+    /// Less important text: other forms, ability effects
+    pub secondary: Color,
+    /// Empty slots and unfilled stat bars
+    pub faint: Color,
+    /// Headings, the current tab, search matches, loading text
+    pub accent: Color,
+    /// Borders of pickers and overlays, key hints
+    pub info: Color,
+    pub title: Color,
+    pub error: Color,
+    pub success: Color,
+    /// Background of the selected row
+    pub selection: Color,
+    /// Text on type badges; `None` picks black or white for each badge
+    pub badge_text: Option<Color>,
+    /// HP, Atk, Def, Sp.Atk, Sp.Def, Speed
+    pub stats: [Color; 6],
+    pub super_effective: Color,
+    pub not_very_effective: Color,
+    pub immune: Color,
+    pub neutral: Color,
+    /// In `ALL_TYPES` order
+    pub types: [Color; 18],
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::named(ThemeName::Default)
+    }
+}
+
+impl Theme {
+    pub fn named(name: ThemeName) -> Theme {
+        match name {
+            ThemeName::Default => Theme {
+                name,
+                text: Color::White,
+                muted: Color::DarkGray,
+                secondary: Color::Gray,
+                faint: Color::Rgb(80, 80, 80),
+                accent: Color::Yellow,
+                info: Color::Cyan,
+                title: Color::Red,
+                error: Color::Red,
+                success: Color::Green,
+                selection: Color::Rgb(40, 40, 60),
+                badge_text: Some(Color::White),
+                stats: [
+                    Color::Rgb(255, 89, 89),
+                    Color::Rgb(245, 172, 120),
+                    Color::Rgb(250, 224, 120),
+                    Color::Rgb(157, 183, 245),
+                    Color::Rgb(167, 219, 141),
+                    Color::Rgb(250, 146, 178),
+                ],
+                super_effective: Color::Rgb(80, 220, 80),
+                not_very_effective: Color::Rgb(220, 80, 80),
+                immune: Color::Rgb(80, 80, 80),
+                neutral: Color::Rgb(100, 100, 100),
+                types: ALL_TYPES.map(super::type_color),
+            },
+            // Dark text for terminals with a white or pale background
+            ThemeName::Light => Theme {
+                name,
+                text: Color::Black,
+                muted: Color::Rgb(110, 110, 110),
+                secondary: Color::Rgb(70, 70, 70),
+                faint: Color::Rgb(190, 190, 190),
+                accent: Color::Rgb(170, 100, 0),
+                info: Color::Rgb(0, 110, 140),
+                title: Color::Rgb(190, 30, 30),
+                error: Color::Rgb(190, 30, 30),
+                success: Color::Rgb(0, 130, 0),
+                selection: Color::Rgb(210, 218, 240),
+                badge_text: None,
+                stats: [
+                    Color::Rgb(210, 40, 40),
+                    Color::Rgb(220, 110, 30),
+                    Color::Rgb(190, 150, 0),
+                    Color::Rgb(50, 90, 210),
+                    Color::Rgb(40, 150, 40),
+                    Color::Rgb(210, 60, 130),
+                ],
+                super_effective: Color::Rgb(0, 150, 0),
+                not_very_effective: Color::Rgb(200, 40, 40),
+                immune: Color::Rgb(60, 60, 60),
+                neutral: Color::Rgb(170, 170, 170),
+                types: ALL_TYPES.map(super::type_color),
+            },
+            ThemeName::HighContrast => Theme {
+                name,
+                text: Color::White,
+                muted: Color::Gray,
+                secondary: Color::White,
+                faint: Color::Gray,
+                accent: Color::LightYellow,
+                info: Color::LightCyan,
+                title: Color::LightRed,
+                error: Color::LightRed,
+                success: Color::LightGreen,
+                selection: Color::Blue,
+                badge_text: None,
+                stats: [
+                    Color::LightRed,
+                    Color::Rgb(255, 160, 60),
+                    Color::LightYellow,
+                    Color::LightBlue,
+                    Color::LightGreen,
+                    Color::LightMagenta,
+                ],
+                super_effective: Color::LightGreen,
+                not_very_effective: Color::LightRed,
+                immune: Color::White,
+                neutral: Color::Gray,
+                types: ALL_TYPES.map(super::type_color),
+            },
+            // Okabe-Ito and Paul Tol hues: no red/green pairs carry meaning
+            ThemeName::Colorblind => Theme {
+                name,
+                stats: [
+                    Color::Rgb(213, 94, 0),
+                    Color::Rgb(230, 159, 0),
+                    Color::Rgb(240, 228, 66),
+                    Color::Rgb(0, 114, 178),
+                    Color::Rgb(0, 158, 115),
+                    Color::Rgb(204, 121, 167),
+                ],
+                badge_text: None,
+                super_effective: Color::Rgb(86, 180, 233),
+                not_very_effective: Color::Rgb(230, 159, 0),
+                types: [
+                    Color::Rgb(221, 221, 221),
+                    Color::Rgb(213, 94, 0),
+                    Color::Rgb(0, 114, 178),
+                    Color::Rgb(240, 228, 66),
+                    Color::Rgb(0, 158, 115),
+                    Color::Rgb(136, 204, 238),
+                    Color::Rgb(136, 34, 85),
+                    Color::Rgb(170, 68, 153),
+                    Color::Rgb(221, 204, 119),
+                    Color::Rgb(160, 160, 240),
+                    Color::Rgb(204, 102, 119),
+                    Color::Rgb(153, 153, 51),
+                    Color::Rgb(170, 136, 68),
+                    Color::Rgb(51, 34, 136),
+                    Color::Rgb(17, 119, 51),
+                    Color::Rgb(80, 70, 60),
+                    Color::Rgb(170, 170, 190),
+                    Color::Rgb(238, 170, 200),
+                ],
+                ..Theme::named(ThemeName::Default)
+            },
+            // Only the named ANSI colors, for terminals without RGB or 256 colors
+            ThemeName::Basic => Theme {
+                name,
+                text: Color::White,
+                muted: Color::DarkGray,
+                secondary: Color::Gray,
+                faint: Color::DarkGray,
+                accent: Color::Yellow,
+                info: Color::Cyan,
+                title: Color::Red,
+                error: Color::Red,
+                success: Color::Green,
+                selection: Color::Blue,
+                badge_text: None,
+                stats: [
+                    Color::Red,
+                    Color::LightRed,
+                    Color::Yellow,
+                    Color::Blue,
+                    Color::Green,
+                    Color::Magenta,
+                ],
+                super_effective: Color::Green,
+                not_very_effective: Color::Red,
+                immune: Color::DarkGray,
+                neutral: Color::DarkGray,
+                types: [
+                    Color::White,
+                    Color::LightRed,
+                    Color::Blue,
+                    Color::LightYellow,
+                    Color::LightGreen,
+                    Color::LightCyan,
+                    Color::Red,
+                    Color::Magenta,
+                    Color::Yellow,
+                    Color::LightBlue,
+                    Color::LightMagenta,
+                    Color::Green,
+                    Color::Yellow,
+                    Color::Magenta,
+                    Color::Cyan,
+                    Color::DarkGray,
+                    Color::Gray,
+                    Color::LightMagenta,
+                ],
+            },
+            // The terminal's own colors; selection is shown reversed
+            ThemeName::NoColor => Theme {
+                name,
+                text: Color::Reset,
+                muted: Color::Reset,
+                secondary: Color::Reset,
+                faint: Color::Reset,
+                accent: Color::Reset,
+                info: Color::Reset,
+                title: Color::Reset,
+                error: Color::Reset,
+                success: Color::Reset,
+                selection: Color::Reset,
+                badge_text: Some(Color::Reset),
+                stats: [Color::Reset; 6],
+                super_effective: Color::Reset,
+                not_very_effective: Color::Reset,
+                immune: Color::Reset,
+                neutral: Color::Reset,
+                types: [Color::Reset; 18],
+            },
+        }
+    }
+
+    /// Style of the selected row
+    pub fn selected(&self) -> Style {
+        if self.selection == Color::Reset {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().bg(self.selection)
+        }
+    }
+
+    pub fn type_color(&self, type_name: &str) -> Color {
+        ALL_TYPES
+            .iter()
+            .position(|t| *t == type_name)
+            .map(|i| self.types[i])
+            .unwrap_or(self.text)
+    }
+
+    /// Bold type name on the type's color
+    pub fn type_badge(&self, type_name: &str) -> Style {
+        let bg = self.type_color(type_name);
+        let fg = self.badge_text.unwrap_or_else(|| contrasting(bg));
+        Style::default().fg(fg).bg(bg).add_modifier(Modifier::BOLD)
+    }
+
+    /// Theme file location, next to the keymap
+    pub fn file_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("pokemon-tui")
+            .join("theme.json")
+    }
+
+    /// Load the theme file if there is one. Settings can pick another theme,
+    /// which leaves out the file's colors since they were chosen for its own.
+    /// A broken file falls back to the default theme, with a message saying
+    /// what's wrong.
+    pub fn load(name: Option<ThemeName>) -> (Theme, Option<String>) {
+        Theme::load_from(&Theme::file_path(), name)
+    }
+
+    pub fn load_from(path: &Path, name: Option<ThemeName>) -> (Theme, Option<String>) {
+        let fallback = Theme::named(name.unwrap_or(ThemeName::Default));
+        let Ok(data) = std::fs::read_to_string(path) else {
+            return (fallback, None);
+        };
+        let file: ThemeFile = match serde_json::from_str(&data) {
+            Ok(file) => file,
+            Err(e) => {
+                let message = format!("Ignoring {}: {}", path.display(), e);
+                return (fallback, Some(message));
+            }
+        };
+        match Theme::from_file(&file) {
+            Ok(theme) if name.is_some_and(|name| name != theme.name) => (fallback, None),
+            Ok(theme) => (theme, None),
+            Err(problems) => {
+                let message = format!(
+                    "Ignoring {}, using the default theme:\n{}",
+                    path.display(),
+                    problems.join("\n")
+                );
+                (fallback, Some(message))
+            }
+        }
+    }
+
+    fn from_file(file: &ThemeFile) -> Result<Theme, Vec<String>> {
+        let mut problems = Vec::new();
+        let name = match file.theme.as_deref() {
+            None => ThemeName::Default,
+            Some(name) => ThemeName::from_name(name).unwrap_or_else(|| {
+                problems.push(format!("unknown theme '{}'", name));
+                ThemeName::Default
+            }),
+        };
+        let mut theme = Theme::named(name);
+        for (role, value) in &file.colors {
+            let Ok(color) = Color::from_str(value) else {
+                problems.push(format!("{}: unknown color '{}'", role, value));
+                continue;
+            };
+            match theme.color_mut(role) {
+                Some(slot) => *slot = color,
+                None => problems.push(format!("unknown color name '{}'", role)),
+            }
+        }
+        if problems.is_empty() {
+            Ok(theme)
+        } else {
+            problems.sort();
+            Err(problems)
+        }
+    }
+
+    /// A color by the name used in theme files: a role or a type
+    fn color_mut(&mut self, role: &str) -> Option<&mut Color> {
+        if let Some(i) = ALL_TYPES.iter().position(|t| *t == role) {
+            return Some(&mut self.types[i]);
+        }
+        let stat = [
+            "hp",
+            "attack",
+            "defense",
+            "sp_attack",
+            "sp_defense",
+            "speed",
+        ]
+        .iter()
+        .position(|s| *s == role);
+        if let Some(i) = stat {
+            return Some(&mut self.stats[i]);
+        }
+        Some(match role {
+            "text" => &mut self.text,
+            "muted" => &mut self.muted,
+            "secondary" => &mut self.secondary,
+            "faint" => &mut self.faint,
+            "accent" => &mut self.accent,
+            "info" => &mut self.info,
+            "title" => &mut self.title,
+            "error" => &mut self.error,
+            "success" => &mut self.success,
+            "selection" => &mut self.selection,
+            "super_effective" => &mut self.super_effective,
+            "not_very_effective" => &mut self.not_very_effective,
+            "immune" => &mut self.immune,
+            "neutral" => &mut self.neutral,
+            "badge_text" => self.badge_text.insert(Color::White),
+            _ => return None,
+        })
+    }
+}
+
+/// Theme file: a built-in theme plus single colors, e.g.
+/// `{"theme": "light", "colors": {"accent": "#b06000", "fire": "red"}}`
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    #[serde(default)]
+    theme: Option<String>,
+    #[serde(default)]
+    colors: HashMap<String, String>,
+}
+
+/// Black on light backgrounds, white on dark ones
+fn contrasting(bg: Color) -> Color {
+    let (r, g, b) = match bg {
+        Color::Rgb(r, g, b) => (r, g, b),
+        // Typical terminal defaults
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 0, 0),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Blue => (0, 0, 238),
+        Color::Magenta => (205, 0, 205),
+        Color::Cyan => (0, 205, 205),
+        Color::Gray => (229, 229, 229),
+        Color::DarkGray => (127, 127, 127),
+        Color::LightRed => (255, 0, 0),
+        Color::LightGreen => (0, 255, 0),
+        Color::LightYellow => (255, 255, 0),
+        Color::LightBlue => (92, 92, 255),
+        Color::LightMagenta => (255, 0, 255),
+        Color::LightCyan => (0, 255, 255),
+        Color::White => (255, 255, 255),
+        _ => return Color::White,
+    };
+    let luma = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+    if luma > 140.0 {
+        Color::Black
+    } else {
+        Color::White
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_theme_matches_type_colors() {
+        let theme = Theme::default();
+        assert_eq!(theme.type_color("fire"), super::super::type_color("fire"));
+        assert_eq!(theme.type_color("unknown"), Color::White);
+        assert_eq!(theme.type_badge("electric").fg, Some(Color::White));
+
+        // Badges pick readable text when the theme doesn't fix it
+        let theme = Theme::named(ThemeName::Basic);
+        assert_eq!(theme.type_badge("electric").fg, Some(Color::Black));
+        assert_eq!(theme.type_badge("water").fg, Some(Color::White));
+        assert_eq!(ThemeName::NoColor.next(), ThemeName::Default);
+        assert_eq!(
+            ThemeName::from_name("High-Contrast"),
+            Some(ThemeName::HighContrast)
+        );
+    }
+
+    #[test]
+    fn test_load_theme_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("theme.json");
+
+        let (theme, error) = Theme::load_from(&path, None);
+        assert_eq!(error, None);
+        assert_eq!(theme.name, ThemeName::Default);
+
+        std::fs::write(
+            &path,
+            r##"{"theme": "light", "colors": {"accent": "#b06000", "fire": "red"}}"##,
+        )
+        .unwrap();
+        let (theme, error) = Theme::load_from(&path, None);
+        assert_eq!(error, None);
+        assert_eq!(theme.name, ThemeName::Light);
+        assert_eq!(theme.accent, Color::Rgb(176, 96, 0));
+        assert_eq!(theme.type_color("fire"), Color::Red);
+        // Another theme picked in settings doesn't take the file's colors
+        let (theme, _) = Theme::load_from(&path, Some(ThemeName::HighContrast));
+        assert_eq!(theme, Theme::named(ThemeName::HighContrast));
+        let (theme, _) = Theme::load_from(&path, Some(ThemeName::Light));
+        assert_eq!(theme.accent, Color::Rgb(176, 96, 0));
+
+        std::fs::write(
+            &path,
+            r#"{"theme": "neon", "colors": {"glow": "red", "text": "sparkly"}}"#,
+        )
+        .unwrap();
+        let (theme, error) = Theme::load_from(&path, None);
+        let error = error.unwrap();
+        assert!(error.contains("unknown theme 'neon'"));
+        assert!(error.contains("unknown color name 'glow'"));
+        assert!(error.contains("text: unknown color 'sparkly'"));
+        assert_eq!(theme, Theme::default());
+    }
+}
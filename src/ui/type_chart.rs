@@ -1,13 +1,14 @@
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
 use crate::app::{App, LoadingState};
-use crate::ui::type_color;
+use crate::keymap::{Action, Context};
+use crate::models::type_data::TypeInfo;
 
-const TYPE_ORDER: [&str; 18] = [
+pub const TYPE_ORDER: [&str; 18] = [
     "normal", "fire", "water", "electric", "grass", "ice", "fighting", "poison", "ground",
     "flying", "psychic", "bug", "rock", "ghost", "dragon", "dark", "steel", "fairy",
 ];
@@ -17,47 +18,44 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
         LoadingState::Loading | LoadingState::Idle => {
             let loading = Paragraph::new("Loading type data...")
                 .block(Block::default().borders(Borders::ALL).title(" Type Chart "))
-                .style(Style::default().fg(Color::Yellow));
+                .style(Style::default().fg(app.theme.accent));
             f.render_widget(loading, area);
             return;
         }
         LoadingState::Error => {
             let error = Paragraph::new("Failed to load type data.")
                 .block(Block::default().borders(Borders::ALL).title(" Type Chart "))
-                .style(Style::default().fg(Color::Red));
+                .style(Style::default().fg(app.theme.error));
             f.render_widget(error, area);
             return;
         }
         LoadingState::Loaded => {}
     }
 
-    // Build effectiveness matrix: matrix[attacker][defender] = multiplier
-    let mut matrix = [[1.0f32; 18]; 18];
-    for info in &app.type_infos {
-        let atk_idx = match type_index(&info.name) {
-            Some(i) => i,
-            None => continue,
-        };
-        for target in &info.damage_relations.double_damage_to {
-            if let Some(def_idx) = type_index(&target.name) {
-                matrix[atk_idx][def_idx] = 2.0;
-            }
-        }
-        for target in &info.damage_relations.half_damage_to {
-            if let Some(def_idx) = type_index(&target.name) {
-                matrix[atk_idx][def_idx] = 0.5;
-            }
-        }
-        for target in &info.damage_relations.no_damage_to {
-            if let Some(def_idx) = type_index(&target.name) {
-                matrix[atk_idx][def_idx] = 0.0;
-            }
-        }
-    }
+    let matrix = effectiveness(&app.type_infos);
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .title(" Type Chart (ATK → DEF)  ↑↓←→/hjkl to scroll ");
+    // "↑↓←→/hjkl" with the default keys
+    let directions = [Action::Up, Action::Down, Action::Left, Action::Right];
+    let nth_keys = |n: usize| -> Option<String> {
+        directions
+            .iter()
+            .map(|&a| {
+                app.keymap
+                    .keys(Context::Chart, a)
+                    .get(n)
+                    .map(|k| k.to_string())
+            })
+            .collect()
+    };
+    let scroll_keys = match (nth_keys(0), nth_keys(1)) {
+        (Some(first), Some(second)) => format!("{}/{}", first, second),
+        (Some(first), None) => first,
+        _ => "keys".to_string(),
+    };
+    let block = Block::default().borders(Borders::ALL).title(format!(
+        " Type Chart (ATK → DEF)  {} to scroll ",
+        scroll_keys
+    ));
     let inner = block.inner(area);
     f.render_widget(block, area);
 
@@ -78,14 +76,14 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     // Header row
     let mut header_spans = vec![Span::styled(
         format!("{:>width$}", "DEF→", width = label_width),
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(app.theme.muted),
     )];
     for c in start_col..(start_col + visible_cols).min(18) {
         let name = &TYPE_ORDER[c][..TYPE_ORDER[c].len().min(col_width - 1)];
         header_spans.push(Span::styled(
             format!("{:>width$}", name.to_uppercase(), width = col_width),
             Style::default()
-                .fg(type_color(TYPE_ORDER[c]))
+                .fg(app.theme.type_color(TYPE_ORDER[c]))
                 .add_modifier(Modifier::BOLD),
         ));
     }
@@ -98,16 +96,17 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
         let mut row_spans = vec![Span::styled(
             format!("{:>width$}", label.to_uppercase(), width = label_width),
             Style::default()
-                .fg(type_color(type_name))
+                .fg(app.theme.type_color(type_name))
                 .add_modifier(Modifier::BOLD),
         )];
         for val in &matrix[r][start_col..(start_col + visible_cols).min(18)] {
-            let (text, color) = match *val {
-                v if v >= 2.0 => ("2×", Color::Rgb(80, 220, 80)),
-                v if v <= 0.0 => ("0", Color::Rgb(80, 80, 80)),
-                v if v < 1.0 => ("½×", Color::Rgb(220, 80, 80)),
-                _ => ("·", Color::Rgb(100, 100, 100)),
+            let color = match *val {
+                v if v >= 2.0 => app.theme.super_effective,
+                v if v <= 0.0 => app.theme.immune,
+                v if v < 1.0 => app.theme.not_very_effective,
+                _ => app.theme.neutral,
             };
+            let text = multiplier_label(*val, app.accessible);
             row_spans.push(Span::styled(
                 format!("{:>width$}", text, width = col_width),
                 Style::default().fg(color),
@@ -120,6 +119,46 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(chart, inner);
 }
 
+/// Damage multipliers: `matrix[attacker][defender]`, in `TYPE_ORDER`
+pub fn effectiveness(type_infos: &[TypeInfo]) -> [[f32; 18]; 18] {
+    let mut matrix = [[1.0f32; 18]; 18];
+    for info in type_infos {
+        let atk_idx = match type_index(&info.name) {
+            Some(i) => i,
+            None => continue,
+        };
+        for target in &info.damage_relations.double_damage_to {
+            if let Some(def_idx) = type_index(&target.name) {
+                matrix[atk_idx][def_idx] = 2.0;
+            }
+        }
+        for target in &info.damage_relations.half_damage_to {
+            if let Some(def_idx) = type_index(&target.name) {
+                matrix[atk_idx][def_idx] = 0.5;
+            }
+        }
+        for target in &info.damage_relations.no_damage_to {
+            if let Some(def_idx) = type_index(&target.name) {
+                matrix[atk_idx][def_idx] = 0.0;
+            }
+        }
+    }
+    matrix
+}
+
+/// Cell text; plain ASCII that screen readers pronounce in accessible mode
+pub fn multiplier_label(value: f32, accessible: bool) -> &'static str {
+    match (value, accessible) {
+        (v, false) if v >= 2.0 => "2×",
+        (v, true) if v >= 2.0 => "2x",
+        (v, _) if v <= 0.0 => "0",
+        (v, false) if v < 1.0 => "½×",
+        (v, true) if v < 1.0 => "1/2",
+        (_, false) => "·",
+        (_, true) => "1",
+    }
+}
+
 fn type_index(name: &str) -> Option<usize> {
     TYPE_ORDER.iter().position(|&t| t == name)
 }